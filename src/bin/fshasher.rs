@@ -0,0 +1,378 @@
+//! Command-line interface for `fshasher`. Available with feature "cli".
+//!
+//! Calculates the summary hash of the given entries and prints it as a hex string. With `--list`,
+//! the hash of each file is printed as well (in the format of `sha256sum`/`b3sum`, with paths
+//! relative to the entries). With `--dry-run`, files are listed with their sizes without hashing.
+//!
+//! Exit codes:
+//! - `0` - the hash has been calculated (and matches `--expect`, if it's given);
+//! - `1` - the hash doesn't match `--expect`;
+//! - `2` - an error occurred (including invalid arguments).
+
+use clap::{Parser, ValueEnum};
+use fshasher::{
+    walker::{FileKey, Origin},
+    ContextFile, Entry, Filter, HasherKind, Options, PatternFilter, ReaderKind, ReadingStrategy,
+    Tolerance,
+};
+use std::{
+    env,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+const EXIT_CHANGED: u8 = 1;
+const EXIT_ERROR: u8 = 2;
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum StrategyKind {
+    Buffer,
+    Complete,
+    Mmap,
+}
+
+impl From<StrategyKind> for ReadingStrategy {
+    fn from(kind: StrategyKind) -> Self {
+        match kind {
+            StrategyKind::Buffer => ReadingStrategy::Buffer,
+            StrategyKind::Complete => ReadingStrategy::Complete,
+            StrategyKind::Mmap => ReadingStrategy::MemoryMapped,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum ToleranceKind {
+    /// Log errors and continue
+    Log,
+    /// Ignore errors silently and continue
+    Silent,
+    /// Stop on the first error
+    Stop,
+}
+
+impl From<ToleranceKind> for Tolerance {
+    fn from(kind: ToleranceKind) -> Self {
+        match kind {
+            ToleranceKind::Log => Tolerance::LogErrors,
+            ToleranceKind::Silent => Tolerance::DoNotLogErrors,
+            ToleranceKind::Stop => Tolerance::StopOnErrors,
+        }
+    }
+}
+
+/// Scan the destination folders and make a hash of all files to get the current state.
+#[derive(Debug, Parser)]
+#[command(name = "fshasher", version, about)]
+struct Args {
+    /// Folders and files to be hashed. Relative paths are resolved against the current directory.
+    /// Files are hashed as is, without filtering.
+    #[arg(required = true)]
+    entries: Vec<PathBuf>,
+
+    /// Global include filter as KIND:GLOB, where KIND is "files", "folders" or "common". Predicates
+    /// are defined as "larger:BYTES", "smaller:BYTES", "ext:EXT[,EXT...]" or "hidden".
+    #[arg(long, value_name = "KIND:GLOB")]
+    include: Vec<String>,
+
+    /// Global exclude filter as KIND:GLOB, where KIND is "files", "folders" or "common". Predicates
+    /// are defined as "larger:BYTES", "smaller:BYTES", "ext:EXT[,EXT...]" or "hidden".
+    #[arg(long, value_name = "KIND:GLOB")]
+    exclude: Vec<String>,
+
+    /// Glob pattern applied to the full path; matching paths are accepted (`PatternFilter::Accept`).
+    #[arg(long, value_name = "GLOB")]
+    accept: Vec<String>,
+
+    /// Glob pattern applied to the full path; matching paths are ignored (`PatternFilter::Ignore`).
+    #[arg(long, value_name = "GLOB")]
+    ignore: Vec<String>,
+
+    /// Name of context file with ignore rules (like ".gitignore").
+    #[arg(long, value_name = "FILENAME")]
+    context_ignore: Vec<String>,
+
+    /// Name of context file with accept rules.
+    #[arg(long, value_name = "FILENAME")]
+    context_accept: Vec<String>,
+
+    /// Reading strategy.
+    #[arg(long, value_enum, default_value = "buffer")]
+    strategy: StrategyKind,
+
+    /// Tolerance to errors.
+    #[arg(long, value_enum, default_value = "log")]
+    tolerance: ToleranceKind,
+
+    /// Number of threads for collecting and hashing (default: number of cores).
+    #[arg(long)]
+    threads: Option<usize>,
+
+    /// Hasher to use: "blake", "sha256", "sha512", "sha1", "md5", "xxh3-64", "xxh3-128" or "crc32c"
+    /// (depending on enabled features).
+    #[arg(long, default_value = "blake")]
+    hasher: HasherKind,
+
+    /// Reader to use: "buffering", "mapping" or "md".
+    #[arg(long, default_value = "buffering")]
+    reader: ReaderKind,
+
+    /// Print the hash of each file before the summary hash (relative to entries, sorted).
+    #[arg(long)]
+    list: bool,
+
+    /// List files, which would be hashed, with their sizes (relative to entries, sorted) and exit
+    /// without hashing.
+    #[arg(long, conflicts_with_all = ["list", "expect"])]
+    dry_run: bool,
+
+    /// Expected summary hash (hex). If the calculated hash differs, exits with code 1.
+    #[arg(long, value_name = "HEX")]
+    expect: Option<String>,
+}
+
+/// Parses a filter given as KIND:GLOB, as "larger:BYTES", "smaller:BYTES", "ext:EXT[,EXT...]" or
+/// as "hidden".
+fn parse_filter(value: &str) -> Result<Filter<&str>, String> {
+    if value == "hidden" {
+        return Ok(Filter::Hidden);
+    }
+    let (kind, glob) = value
+        .split_once(':')
+        .ok_or_else(|| format!("Filter \"{value}\" should be defined as KIND:GLOB"))?;
+    let size = || {
+        glob.parse::<u64>()
+            .map_err(|_| format!("Filter \"{value}\" should have a size in bytes"))
+    };
+    match kind {
+        "files" => Ok(Filter::Files(glob)),
+        "folders" => Ok(Filter::Folders(glob)),
+        "common" => Ok(Filter::Common(glob)),
+        "larger" => Ok(Filter::LargerThan(size()?)),
+        "smaller" => Ok(Filter::SmallerThan(size()?)),
+        "ext" => Ok(Filter::Extension(glob.split(',').collect())),
+        _ => Err(format!(
+            "Unknown kind of filter \"{kind}\"; expected \"files\", \"folders\", \"common\", \"larger\", \"smaller\", \"ext\" or \"hidden\""
+        )),
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Formats a line of `sha256sum`/`b3sum`: if the path contains `\`, a line feed or a carriage
+/// return, they are escaped and the line starts with `\` (as GNU coreutils do).
+fn checksum_line(hash: &[u8], path: &Path) -> String {
+    let path = path.to_string_lossy();
+    if path.contains(['\\', '\n', '\r']) {
+        let path = path
+            .replace('\\', "\\\\")
+            .replace('\n', "\\n")
+            .replace('\r', "\\r");
+        format!("\\{}  {path}", to_hex(hash))
+    } else {
+        format!("{}  {path}", to_hex(hash))
+    }
+}
+
+/// Entries as they are given: folders (which become entries) and files (which are added with
+/// `Options::file()`) in the same order as in `options()`.
+struct Given<'a> {
+    folders: Vec<&'a PathBuf>,
+    files: Vec<&'a PathBuf>,
+}
+
+impl<'a> Given<'a> {
+    fn new(args: &'a Args) -> Result<Self, String> {
+        let cwd = env::current_dir().map_err(|err| err.to_string())?;
+        let (files, folders) = args
+            .entries
+            .iter()
+            .partition(|path| cwd.join(path).is_file());
+        Ok(Self { folders, files })
+    }
+
+    /// Returns the path of a file to be printed: the path relative to its entry prefixed with the
+    /// entry as it's given (like `find` does) or the file as it's given. So the output doesn't
+    /// depend on the location of the tree.
+    fn path(&self, key: &FileKey) -> Option<PathBuf> {
+        match key.origin {
+            Origin::Entry(index) => self
+                .folders
+                .get(index)
+                .map(|folder| folder.join(String::from_utf8_lossy(&key.relative).as_ref())),
+            Origin::File(index) => self.files.get(index).map(|file| file.to_path_buf()),
+        }
+    }
+}
+
+fn options(args: &Args) -> Result<Options, String> {
+    let cwd = env::current_dir().map_err(|err| err.to_string())?;
+    let mut opt = Options::new()
+        .tolerance(args.tolerance.into())
+        .reading_strategy(args.strategy.into())
+        .map_err(|err| err.to_string())?;
+    if let Some(threads) = args.threads {
+        opt = opt.threads(threads).map_err(|err| err.to_string())?;
+    }
+    for path in args.entries.iter() {
+        let path = cwd.join(path);
+        if path.is_file() {
+            opt = opt.file(path).map_err(|err| err.to_string())?;
+            continue;
+        }
+        let mut entry = Entry::from(path).map_err(|err| err.to_string())?;
+        for glob in args.accept.iter() {
+            entry = entry
+                .pattern(PatternFilter::Accept(glob))
+                .map_err(|err| err.to_string())?;
+        }
+        for glob in args.ignore.iter() {
+            entry = entry
+                .pattern(PatternFilter::Ignore(glob))
+                .map_err(|err| err.to_string())?;
+        }
+        for filename in args.context_ignore.iter() {
+            entry = entry.context(ContextFile::Ignore(filename));
+        }
+        for filename in args.context_accept.iter() {
+            entry = entry.context(ContextFile::Accept(filename));
+        }
+        opt = opt.entry(entry).map_err(|err| err.to_string())?;
+    }
+    for filter in args.include.iter() {
+        opt = opt
+            .include(parse_filter(filter)?)
+            .map_err(|err| err.to_string())?;
+    }
+    for filter in args.exclude.iter() {
+        opt = opt
+            .exclude(parse_filter(filter)?)
+            .map_err(|err| err.to_string())?;
+    }
+    Ok(opt)
+}
+
+/// Prints files, which would be hashed, as "SIZE<TAB>PATH" lines. Paths are printed relative to
+/// entries prefixed with the entries as they are given (like `find` does); files given directly
+/// are printed as they are given.
+fn dry_run(args: &Args) -> Result<(), String> {
+    let given = Given::new(args)?;
+    let mut walker = options(args)?.walker().map_err(|err| err.to_string())?;
+    for file in walker.list().map_err(|err| err.to_string())? {
+        let size = file.size.map(|size| size.to_string()).unwrap_or_default();
+        let path = given.path(&file.key).unwrap_or(file.path);
+        println!("{size}\t{}", path.display());
+    }
+    Ok(())
+}
+
+fn run(args: &Args) -> Result<String, String> {
+    let mut walker = options(args)?
+        .hasher(args.hasher.clone())
+        .reader(args.reader.clone())
+        .walker()
+        .map_err(|err| err.to_string())?;
+    let hash = to_hex(
+        walker
+            .collect()
+            .map_err(|err| err.to_string())?
+            .hash_dyn()
+            .map_err(|err| err.to_string())?,
+    );
+    if args.list {
+        let given = Given::new(args)?;
+        for (key, state) in walker.results().map_err(|err| err.to_string())? {
+            let Some(path) = given.path(&key) else {
+                continue;
+            };
+            match state {
+                Some(Ok(hash)) => println!("{}", checksum_line(hash, &path)),
+                Some(Err(err)) => eprintln!("{}: {err}", path.display()),
+                None => {}
+            }
+        }
+    }
+    Ok(hash)
+}
+
+fn main() -> ExitCode {
+    env_logger::init();
+    let args = Args::parse();
+    if args.dry_run {
+        return match dry_run(&args) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(err) => {
+                eprintln!("{err}");
+                ExitCode::from(EXIT_ERROR)
+            }
+        };
+    }
+    let result = run(&args);
+    match result {
+        Ok(hash) => {
+            println!("{hash}");
+            match args.expect {
+                Some(expected) if !expected.eq_ignore_ascii_case(&hash) => {
+                    ExitCode::from(EXIT_CHANGED)
+                }
+                _ => ExitCode::SUCCESS,
+            }
+        }
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::from(EXIT_ERROR)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn filters() {
+        assert!(matches!(
+            parse_filter("files:*.rs"),
+            Ok(Filter::Files("*.rs"))
+        ));
+        assert!(matches!(
+            parse_filter("folders:target"),
+            Ok(Filter::Folders("target"))
+        ));
+        assert!(matches!(
+            parse_filter("common:**/*.tmp"),
+            Ok(Filter::Common("**/*.tmp"))
+        ));
+        assert!(matches!(
+            parse_filter("larger:1024"),
+            Ok(Filter::LargerThan(1024))
+        ));
+        assert!(matches!(parse_filter("hidden"), Ok(Filter::Hidden)));
+        assert!(matches!(
+            parse_filter("ext:rs,toml"),
+            Ok(Filter::Extension(exts)) if exts == vec!["rs", "toml"]
+        ));
+        assert!(parse_filter("smaller:big").is_err());
+        assert!(parse_filter("*.rs").is_err());
+        assert!(parse_filter("unknown:*.rs").is_err());
+    }
+
+    #[test]
+    fn hex() {
+        assert_eq!(to_hex(&[0, 15, 255]), "000fff");
+    }
+
+    #[test]
+    fn checksum_lines() {
+        assert_eq!(
+            checksum_line(&[0, 255], Path::new("a/b.txt")),
+            "00ff  a/b.txt"
+        );
+        assert_eq!(
+            checksum_line(&[0, 255], Path::new("a\\b\nc\rd")),
+            "\\00ff  a\\\\b\\nc\\rd"
+        );
+    }
+}
@@ -1,157 +1,406 @@
-use crate::{collector::E, entry::ContextFileAccepted};
-use glob::Pattern;
+use crate::{
+    collector::{
+        git::{Repository, Tracked, GITIGNORE, GIT_DIR},
+        wildmatch::wildmatch,
+        Reason, E,
+    },
+    entry::{ContextFileAccepted, GitAware},
+};
 use std::{
-    collections::HashMap,
-    fs::File,
-    io::{self, BufRead},
+    cell::OnceCell,
+    collections::{HashMap, HashSet},
+    fs,
     path::{Path, PathBuf},
+    sync::Arc,
 };
-#[derive(Default, Debug)]
-struct ContextPatterns {
-    accept: Vec<(Pattern, bool)>,
-    ignore: Vec<(Pattern, bool)>,
+
+/// A rule of a context file. Rules are parsed and applied in the same way as `.gitignore` rules.
+#[derive(Debug)]
+pub struct Rule {
+    /// The pattern without the leading "!", the trailing "/" and the leading "/" of anchored
+    /// patterns.
+    pattern: Vec<u8>,
+    /// The rule starts with "!" (re-includes paths in ignore files and excludes paths in accept
+    /// files).
+    negative: bool,
+    /// The rule ends with "/" and matches folders only.
+    dir_only: bool,
+    /// The rule has no "/" (besides a trailing one) and is matched against the name of a path;
+    /// otherwise, it's matched against the path relative to the folder of the context file.
+    basename: bool,
+    /// The rule as it's written in the context file (without trailing spaces).
+    text: String,
+    /// The context file the rule is defined in.
+    file: Arc<PathBuf>,
+    /// The number of the line with the rule (starting from 1).
+    line: usize,
 }
 
-impl ContextPatterns {
-    pub fn append(
-        &mut self,
-        context_file: &ContextFileAccepted,
-        mut patterns: Vec<(Pattern, bool)>,
-    ) {
-        if patterns.is_empty() {
-            return;
+impl Rule {
+    /// Parses a line of a context file.
+    ///
+    /// # Parameters
+    ///
+    /// - `line`: The line without the line break.
+    /// - `file`: The context file.
+    /// - `number`: The number of the line (starting from 1).
+    ///
+    /// # Returns
+    ///
+    /// - `Option<Rule>`: The rule or `None` if the line is blank or a comment.
+    fn parse(line: &[u8], file: &Arc<PathBuf>, number: usize) -> Option<Self> {
+        if line.first().map_or(true, |ch| *ch == b'#') {
+            return None;
         }
-        match context_file {
-            ContextFileAccepted::Accept(_) => self.accept.append(&mut patterns),
-            ContextFileAccepted::Ignore(_) => self.ignore.append(&mut patterns),
+        let line = trim_trailing_spaces(line);
+        let text = String::from_utf8_lossy(line).into_owned();
+        let (negative, mut pattern) = match line.strip_prefix(b"!") {
+            Some(pattern) => (true, pattern),
+            None => (false, line),
+        };
+        let dir_only = pattern.last() == Some(&b'/');
+        if dir_only {
+            pattern = &pattern[..pattern.len() - 1];
+        }
+        let basename = !pattern.contains(&b'/');
+        if !basename {
+            pattern = pattern.strip_prefix(b"/").unwrap_or(pattern);
         }
+        if pattern.is_empty() {
+            return None;
+        }
+        Some(Self {
+            pattern: pattern.to_vec(),
+            negative,
+            dir_only,
+            basename,
+            text,
+            file: file.clone(),
+            line: number,
+        })
     }
 
-    pub fn merge(&mut self, pattern: &ContextPatterns) {
-        for (pattern, ng) in pattern.ignore.iter() {
-            if !self.ignore.iter().any(|(p, _)| p == pattern) {
-                self.ignore.push((pattern.clone(), *ng))
-            }
+    /// Describes the rule as a reason of a decision on a path.
+    fn reason(&self) -> Reason {
+        Reason::Context {
+            file: self.file.as_ref().clone(),
+            line: self.line,
+            rule: self.text.clone(),
         }
     }
 
-    pub fn filtered(&self, path: &Path) -> bool {
-        if self.ignore.is_empty() && self.accept.is_empty() {
-            return true;
-        }
-        // Path is exeption in ingore; no accept filter
-        if self
-            .ignore
-            .iter()
-            .filter(|(_, negative)| *negative)
-            .any(|(p, _)| p.matches_path(path))
-            && self.accept.is_empty()
-        {
-            return true;
-        }
-        // Path is ignored
-        if self
-            .ignore
-            .iter()
-            .filter(|(_, negative)| !*negative)
-            .any(|(p, _)| p.matches_path(path))
-        {
-            return false;
-        }
-        // Path isn't ignored; no accept lists
-        if self.accept.is_empty() {
-            return true;
-        }
-        // Accept filtering is applying only to files
-        if !path.is_file() {
-            return true;
+    /// Checks whether the rule matches the path.
+    ///
+    /// # Parameters
+    ///
+    /// - `rel`: The path relative to the folder of the context file, with "/" as a separator.
+    /// - `name`: The name of the path.
+    /// - `is_dir`: Checks whether the path is a folder.
+    ///
+    /// # Returns
+    ///
+    /// - `bool`: `true` if the rule matches the path.
+    fn matches<F: Fn() -> bool>(&self, rel: &[u8], name: &[u8], is_dir: F) -> bool {
+        let matched = wildmatch(&self.pattern, if self.basename { name } else { rel });
+        matched && (!self.dir_only || is_dir())
+    }
+}
+
+/// Removes trailing spaces, which aren't escaped with a backslash.
+fn trim_trailing_spaces(line: &[u8]) -> &[u8] {
+    let mut last_space = None;
+    let mut i = 0;
+    while i < line.len() {
+        match line[i] {
+            b' ' => {
+                last_space.get_or_insert(i);
+            }
+            b'\\' => {
+                // The escaped character is kept as it is
+                i += 1;
+                last_space = None;
+            }
+            _ => {
+                last_space = None;
+            }
         }
-        // Path is exeption in accept list
-        if self
-            .accept
-            .iter()
-            .filter(|(_, negative)| *negative)
-            .any(|(p, _)| p.matches_path(path))
-        {
-            return false;
+        i += 1;
+    }
+    &line[..last_space.unwrap_or(line.len())]
+}
+
+/// Rules of context files of one folder.
+#[derive(Default, Debug)]
+struct ContextRules {
+    accept: Vec<Rule>,
+    ignore: Vec<Rule>,
+}
+
+impl ContextRules {
+    pub fn append(&mut self, context_file: &ContextFileAccepted, mut rules: Vec<Rule>) {
+        match context_file {
+            ContextFileAccepted::Accept(_) => self.accept.append(&mut rules),
+            ContextFileAccepted::Ignore(_) => self.ignore.append(&mut rules),
         }
-        // Path is in accept list or not
-        self.accept
-            .iter()
-            .filter(|(_, negative)| !*negative)
-            .any(|(p, _)| p.matches_path(path))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.accept.is_empty() && self.ignore.is_empty()
     }
 }
 
+/// Rules of context files collected from visited folders. As in git, rules of a context file are
+/// applied to paths inside of its folder relatively to this folder. The last matching rule wins:
+/// rules of deeper folders take precedence over rules of parent folders and, within a file, later
+/// rules take precedence over earlier ones.
 #[derive(Debug)]
 pub struct Context {
     files: Vec<ContextFileAccepted>,
-    patterns: HashMap<PathBuf, ContextPatterns>,
+    rules: HashMap<PathBuf, ContextRules>,
+    /// Folders, which context files have been read already.
+    considered: HashSet<PathBuf>,
+    /// Rules of git's exclude files (see `GitAware::Ignore`) along with the root of the working
+    /// tree; they have lower priority than rules of context files.
+    excludes: Vec<(PathBuf, ContextRules)>,
+    /// Files tracked by git (see `GitAware::Tracked`).
+    tracked: Option<Tracked>,
+    /// `true` if git's view is considered; the `.git` folder is skipped.
+    git: bool,
 }
 
 impl Context {
     pub fn new(files: &[ContextFileAccepted]) -> Self {
         Self {
             files: files.to_vec(),
-            patterns: HashMap::new(),
+            rules: HashMap::new(),
+            considered: HashSet::new(),
+            excludes: Vec::new(),
+            tracked: None,
+            git: false,
         }
     }
+
+    /// Makes the context follow the view of git on the entry.
+    ///
+    /// # Parameters
+    ///
+    /// - `mode`: The way git's view is considered.
+    /// - `entry`: The path of the entry.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the entry doesn't belong to a git repository, if an exclude file
+    /// cannot be read or if the index cannot be read.
+    pub fn git(&mut self, mode: &GitAware, entry: &Path) -> Result<(), E> {
+        let repo = Repository::discover(entry).ok_or(E::NotGitRepository(entry.to_path_buf()))?;
+        self.git = true;
+        match mode {
+            GitAware::Ignore => {
+                let gitignore = ContextFileAccepted::Ignore(String::from(GITIGNORE));
+                if !self.files.contains(&gitignore) {
+                    self.files.push(gitignore);
+                }
+                // Rules of the parent folders of the entry up to the root of the working tree
+                for parent in entry.ancestors().skip(1) {
+                    if !parent.starts_with(&repo.workdir) {
+                        break;
+                    }
+                    self.consider(&parent.to_path_buf())?;
+                }
+                for file in repo.excludes() {
+                    let rules = ContextRules {
+                        accept: Vec::new(),
+                        ignore: self.parse(&file)?,
+                    };
+                    self.excludes.push((repo.workdir.clone(), rules));
+                }
+            }
+            GitAware::Tracked => {
+                self.tracked = Some(repo.tracked()?);
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads the context files of the folder. Each folder is read only once, so the same context
+    /// can be reused to check many paths.
     pub fn consider(&mut self, parent: &PathBuf) -> Result<(), E> {
-        let mut context_patterns = ContextPatterns::default();
+        if self.considered.contains(parent) {
+            return Ok(());
+        }
+        let mut rules = ContextRules::default();
         for file in self.files.iter() {
             if let Some(filepath) = file.filepath(parent) {
-                context_patterns.append(file, self.parse(&filepath)?);
+                rules.append(file, self.parse(&filepath)?);
             }
         }
-        if !context_patterns.ignore.is_empty() || !context_patterns.accept.is_empty() {
-            for rel in self.relevant_to(parent) {
-                context_patterns.merge(rel);
-            }
-            self.patterns.insert(parent.clone(), context_patterns);
+        if !rules.is_empty() {
+            self.rules.insert(parent.clone(), rules);
         }
+        self.considered.insert(parent.clone());
         Ok(())
     }
 
     pub fn filtered(&self, path: &Path) -> bool {
-        if let Some(cx) = self.relevant_to(path).first() {
-            cx.filtered(path)
-        } else {
-            true
+        self.decide(path).accepted()
+    }
+
+    /// Applies the rules to the path and returns the decisive one. `filtered()` is a shortcut for
+    /// `decide(path).accepted()`.
+    pub fn decide(&self, path: &Path) -> Verdict<'_> {
+        if self.git && path.file_name().is_some_and(|name| name == GIT_DIR) {
+            return Verdict::GitDir;
+        }
+        if self
+            .tracked
+            .as_ref()
+            .is_some_and(|tracked| !tracked.filtered(path))
+        {
+            return Verdict::Untracked;
+        }
+        let mut scopes = self.relevant_to(path);
+        scopes.extend(
+            self.excludes
+                .iter()
+                .map(|(base, rules)| (base.as_path(), rules)),
+        );
+        if scopes.is_empty() {
+            return Verdict::Default;
+        }
+        let is_dir = OnceCell::new();
+        // Like git, symlinks aren't followed: a symlink to a folder isn't matched by dir-only rules
+        let is_dir = || {
+            *is_dir.get_or_init(|| {
+                path.symlink_metadata()
+                    .map(|md| md.is_dir())
+                    .unwrap_or(false)
+            })
+        };
+        // Path is ignored, unless it's re-included by a later negative rule
+        let ignore = self.last_match(path, &scopes, |rules| &rules.ignore, is_dir);
+        if let Some(rule) = ignore.filter(|rule| !rule.negative) {
+            return Verdict::Ignored(rule);
+        }
+        let default = ignore.map_or(Verdict::Default, Verdict::Reincluded);
+        // Path isn't ignored; no accept lists
+        if scopes.iter().all(|(_, rules)| rules.accept.is_empty()) {
+            return default;
+        }
+        // Accept filtering is applying only to files
+        if !path.is_file() {
+            return default;
+        }
+        // Path is in accept list and isn't excluded by a later negative rule
+        match self.last_match(path, &scopes, |rules| &rules.accept, is_dir) {
+            Some(rule) if rule.negative => Verdict::Excluded(rule),
+            Some(rule) => Verdict::Accepted(rule),
+            None => Verdict::NotAccepted,
+        }
+    }
+
+    /// Returns the last rule (in the order of precedence) matching the path.
+    fn last_match<'a, S: Fn(&'a ContextRules) -> &'a Vec<Rule>, F: Fn() -> bool>(
+        &self,
+        path: &Path,
+        scopes: &[(&Path, &'a ContextRules)],
+        select: S,
+        is_dir: F,
+    ) -> Option<&'a Rule> {
+        let name = path
+            .file_name()
+            .map(|name| name.as_encoded_bytes())
+            .unwrap_or_default();
+        // Scopes are ordered from the deepest folder
+        for (base, rules) in scopes.iter() {
+            let rules = select(rules);
+            if rules.is_empty() {
+                continue;
+            }
+            let Ok(rel) = path.strip_prefix(base) else {
+                continue;
+            };
+            let rel = rel
+                .components()
+                .map(|component| component.as_os_str().as_encoded_bytes())
+                .collect::<Vec<&[u8]>>()
+                .join(&b'/');
+            if let Some(rule) = rules
+                .iter()
+                .rev()
+                .find(|rule| rule.matches(&rel, name, &is_dir))
+            {
+                return Some(rule);
+            }
         }
+        None
     }
 
-    fn relevant_to(&self, path: &Path) -> Vec<&ContextPatterns> {
+    /// Returns the rules of the parent folders of the path, starting from the closest one.
+    fn relevant_to(&self, path: &Path) -> Vec<(&Path, &ContextRules)> {
         let mut current = path;
         let mut all = Vec::new();
         while let Some(parent) = current.parent() {
-            if let Some(patterns) = self.patterns.get(&parent.to_path_buf()) {
-                all.push(patterns)
+            if let Some((parent, rules)) = self.rules.get_key_value(parent) {
+                all.push((parent.as_path(), rules))
             }
             current = parent;
         }
         all
     }
 
-    fn parse(&self, filename: &PathBuf) -> Result<Vec<(Pattern, bool)>, E> {
-        let file = File::open(filename)?;
-        let reader = io::BufReader::new(file);
-        let mut patterns: Vec<(Pattern, bool)> = Vec::new();
-        for ln in reader.lines().collect::<io::Result<Vec<String>>>()? {
-            if ln.trim().is_empty() {
-                continue;
-            }
-            if ln.trim().starts_with('#') {
-                continue;
-            }
-            let negative = ln.trim().starts_with('!');
-            let pattern = Pattern::new(if negative {
-                &ln.trim()[1..]
-            } else {
-                &ln.trim()[0..]
-            })
-            .map_err(|err: glob::PatternError| (ln.to_string(), err))?;
-            patterns.push((pattern, negative))
-        }
-        Ok(patterns)
+    fn parse(&self, filename: &PathBuf) -> Result<Vec<Rule>, E> {
+        let content = fs::read(filename)?;
+        let content = content.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(&content);
+        let file = Arc::new(filename.clone());
+        Ok(content
+            .split(|ch| *ch == b'\n')
+            .enumerate()
+            .filter_map(|(i, ln)| Rule::parse(ln.strip_suffix(b"\r").unwrap_or(ln), &file, i + 1))
+            .collect())
+    }
+}
+
+/// The decision of `Context::decide()` along with the rule, which made it.
+#[derive(Debug)]
+pub enum Verdict<'a> {
+    /// Accepted; no rule matches the path.
+    Default,
+    /// Rejected; the path is git's folder.
+    GitDir,
+    /// Rejected; the path isn't tracked by git.
+    Untracked,
+    /// Rejected by the rule of an ignore file.
+    Ignored(&'a Rule),
+    /// Accepted; re-included by the negative rule of an ignore file.
+    Reincluded(&'a Rule),
+    /// Accepted by the rule of an accept file.
+    Accepted(&'a Rule),
+    /// Rejected by the negative rule of an accept file.
+    Excluded(&'a Rule),
+    /// Rejected; accept files are defined, but no rule matches the file.
+    NotAccepted,
+}
+
+impl Verdict<'_> {
+    /// Returns `true` if the path is accepted.
+    pub fn accepted(&self) -> bool {
+        matches!(
+            self,
+            Self::Default | Self::Reincluded(..) | Self::Accepted(..)
+        )
+    }
+
+    /// Describes the decision; `None` if no rule matches the path.
+    pub fn reason(&self) -> Option<Reason> {
+        Some(match self {
+            Self::Default => return None,
+            Self::GitDir => Reason::GitDir,
+            Self::Untracked => Reason::Untracked,
+            Self::Ignored(rule)
+            | Self::Reincluded(rule)
+            | Self::Accepted(rule)
+            | Self::Excluded(rule) => rule.reason(),
+            Self::NotAccepted => Reason::NotInContext,
+        })
     }
 }
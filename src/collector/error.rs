@@ -1,5 +1,5 @@
 use glob::PatternError;
-use std::io;
+use std::{io, path::PathBuf};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -18,6 +18,10 @@ pub enum E {
     Aborted,
     #[error("Channel \"{0}\" isn't available")]
     ChannelErr(String),
+    #[error("Path {0} doesn't belong to a git repository")]
+    NotGitRepository(PathBuf),
+    #[error("Fail to read git index {0}: {1}")]
+    GitIndex(PathBuf, String),
 }
 
 impl From<(String, PatternError)> for E {
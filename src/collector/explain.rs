@@ -0,0 +1,412 @@
+use super::{
+    context,
+    context::Context,
+    worker::{device_of, is_loop},
+    SymlinkPolicy, E,
+};
+use crate::{
+    breaker::Breaker,
+    entry::{Decision, Entry},
+};
+use std::{
+    fmt,
+    fs::read_dir,
+    path::{Path, PathBuf},
+};
+
+/// The rule (or the circumstance), which decides whether a path is collected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reason {
+    /// No rule matches the path; it's collected by default.
+    Default,
+    /// The path has been added with `Options::file()`; such files aren't filtered.
+    File,
+    /// The path doesn't belong to the entry (or to any entry of `Walker`).
+    OutsideEntry,
+    /// The path doesn't exist or isn't a file, a folder or a symlink.
+    Unavailable,
+    /// The path doesn't satisfy the depth limits (`Entry::max_depth()`, `Entry::min_depth()`).
+    Depth,
+    /// The path matches the including filter (`Entry::include()`).
+    Include(String),
+    /// The path doesn't match any including filter.
+    NotIncluded,
+    /// The path matches the excluding filter (`Entry::exclude()`).
+    Exclude(String),
+    /// The path is accepted by the pattern filter (`Entry::pattern()`).
+    Pattern(String),
+    /// The path isn't accepted by any pattern filter.
+    NoPattern,
+    /// The path is rejected by the custom filter with the given identity (`Entry::path_filter()`).
+    Custom(String),
+    /// The rule of a context file (or of git's exclude file) matches the path.
+    ///
+    /// # Fields
+    ///
+    /// * `file` - The full path to the context file.
+    /// * `line` - The number of the line with the rule (starting from 1).
+    /// * `rule` - The rule as it's written in the file.
+    Context {
+        file: PathBuf,
+        line: usize,
+        rule: String,
+    },
+    /// Accept context files are defined, but none of their rules matches the file.
+    NotInContext,
+    /// The path is git's folder (see `GitAware`).
+    GitDir,
+    /// The file isn't tracked by git (see `GitAware::Tracked`).
+    Untracked,
+    /// The symlink isn't followed because of `SymlinkPolicy`.
+    Symlink,
+    /// The symlink leads to one of the folders on its own path.
+    SymlinkLoop,
+    /// The path is on another file system (see `Entry::one_file_system()`).
+    OtherFileSystem,
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Default => write!(f, "no matching rules"),
+            Self::File => write!(f, "added as a file"),
+            Self::OutsideEntry => write!(f, "outside of entries"),
+            Self::Unavailable => write!(f, "not a file or a folder"),
+            Self::Depth => write!(f, "depth limits"),
+            Self::Include(filter) => write!(f, "include filter \"{filter}\""),
+            Self::NotIncluded => write!(f, "no matching include filters"),
+            Self::Exclude(filter) => write!(f, "exclude filter \"{filter}\""),
+            Self::Pattern(pattern) => write!(f, "pattern \"{pattern}\""),
+            Self::NoPattern => write!(f, "no accepting patterns"),
+            Self::Custom(identity) => write!(f, "custom filter \"{identity}\""),
+            Self::Context { file, line, rule } => {
+                write!(f, "rule \"{rule}\" at {}:{line}", file.display())
+            }
+            Self::NotInContext => write!(f, "no matching rules of accept context files"),
+            Self::GitDir => write!(f, "git folder"),
+            Self::Untracked => write!(f, "not tracked by git"),
+            Self::Symlink => write!(f, "symlink policy"),
+            Self::SymlinkLoop => write!(f, "symlink loop"),
+            Self::OtherFileSystem => write!(f, "another file system"),
+        }
+    }
+}
+
+/// Explains whether a path is collected and which rule decides it. A path is collected only if
+/// each folder on the way from the entry to the path is accepted, so the decisive rule can be
+/// about one of the parent folders (see `subject`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explanation {
+    /// The explained path.
+    pub path: PathBuf,
+    /// `true` if the path is collected (for a folder, if the folder is read).
+    pub accepted: bool,
+    /// The path the decisive rule is applied to: the path itself or its parent folder, which
+    /// isn't read.
+    pub subject: PathBuf,
+    /// The decisive rule. For an accepted path, it's the rule, which accepts the path itself
+    /// (`Reason::Default` if no rule matches it).
+    pub reason: Reason,
+    /// `true` if the decisive rule belongs to the global settings (`Options::global`).
+    pub global: bool,
+}
+
+impl Explanation {
+    fn new(path: &Path, accepted: bool, reason: Reason, global: bool) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            accepted,
+            subject: path.to_path_buf(),
+            reason,
+            global,
+        }
+    }
+
+    /// Creates an explanation of a path, which isn't filtered at all.
+    pub(crate) fn file(path: &Path) -> Self {
+        Self::new(path, true, Reason::File, false)
+    }
+
+    /// Creates an explanation of a path outside of entries.
+    pub(crate) fn outside(path: &Path) -> Self {
+        Self::new(path, false, Reason::OutsideEntry, false)
+    }
+
+    /// Applies the decision about a folder on the way to the path to the path itself.
+    fn about(mut self, path: &Path) -> Self {
+        self.path = path.to_path_buf();
+        self
+    }
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} by {}{}",
+            self.path.display(),
+            if self.accepted { "accepted" } else { "skipped" },
+            if self.global { "global " } else { "" },
+            self.reason
+        )?;
+        if self.subject != self.path {
+            write!(f, " (applied to {})", self.subject.display())?;
+        }
+        Ok(())
+    }
+}
+
+/// Describes the decision of `Entry::decide()`; `None` if no filter matches the path.
+fn reason(decision: &Decision) -> Option<Reason> {
+    Some(match decision {
+        Decision::Default => return None,
+        Decision::Included(filter) => Reason::Include(filter.to_string()),
+        Decision::NotIncluded => Reason::NotIncluded,
+        Decision::Excluded(filter) => Reason::Exclude(filter.to_string()),
+        Decision::Pattern(pattern) => Reason::Pattern(pattern.to_string()),
+        Decision::NoPattern => Reason::NoPattern,
+        Decision::Custom(filter) => Reason::Custom(filter.identity.clone()),
+    })
+}
+
+/// Checks paths of an entry in the same way as workers and `collect_entry()` do, but keeps the
+/// decisive rules. Folders should be checked before their content, because context files are read
+/// from accepted folders.
+struct Tracer<'a> {
+    entry: &'a Entry,
+    global: &'a Entry,
+    symlinks: &'a SymlinkPolicy,
+    context: &'a mut Context,
+    /// Resolved path of the entry to check targets of symlinks.
+    root: Option<PathBuf>,
+    /// Device of the entry to stay on its file system.
+    device: Option<u64>,
+}
+
+impl<'a> Tracer<'a> {
+    fn new(
+        entry: &'a Entry,
+        global: &'a Entry,
+        symlinks: &'a SymlinkPolicy,
+        context: &'a mut Context,
+    ) -> Result<Self, E> {
+        context.consider(&entry.entry)?;
+        Ok(Self {
+            entry,
+            global,
+            symlinks,
+            context,
+            root: if *symlinks == SymlinkPolicy::WithinEntry {
+                entry.entry.canonicalize().ok()
+            } else {
+                None
+            },
+            device: if entry.one_file_system || global.one_file_system {
+                device_of(&entry.entry)
+            } else {
+                None
+            },
+        })
+    }
+
+    /// Checks whether the path is collected (for a folder, read) assuming its parent folder is
+    /// read.
+    ///
+    /// # Parameters
+    ///
+    /// - `path`: The full path to check.
+    ///
+    /// # Returns
+    ///
+    /// - `Result<(Explanation, bool), E>`: The decision and `true` if the path is read as a folder.
+    fn check(&mut self, path: &Path) -> Result<(Explanation, bool), E> {
+        let skipped = |reason: Reason, global: bool| {
+            Ok((Explanation::new(path, false, reason, global), false))
+        };
+        let is_symlink = path.is_symlink();
+        let hash_target = is_symlink && *self.symlinks == SymlinkPolicy::HashTarget;
+        // Metadata is read once for checking the presence and for all filters
+        let md = path.metadata().ok();
+        if md.is_none() && !hash_target {
+            return skipped(Reason::Unavailable, false);
+        }
+        let decisions = [
+            (self.entry.decide_with(path, md.as_ref()), false),
+            (self.global.decide_with(path, md.as_ref()), true),
+        ];
+        if let Some((decision, global)) = decisions.iter().find(|(d, _)| !d.accepted()) {
+            return skipped(reason(decision).unwrap_or(Reason::Default), *global);
+        }
+        if is_symlink {
+            match self.symlinks {
+                SymlinkPolicy::Skip => return skipped(Reason::Symlink, false),
+                SymlinkPolicy::WithinEntry => {
+                    let within = match (&self.root, path.canonicalize()) {
+                        (Some(root), Ok(target)) => target.starts_with(root),
+                        _ => false,
+                    };
+                    if !within {
+                        return skipped(Reason::Symlink, false);
+                    }
+                }
+                SymlinkPolicy::HashTarget | SymlinkPolicy::Follow => {}
+            }
+            if !hash_target && path.is_dir() && is_loop(path) {
+                return skipped(Reason::SymlinkLoop, false);
+            }
+        }
+        // The symlink itself is collected as a file with `SymlinkPolicy::HashTarget`
+        let is_dir = !hash_target && path.is_dir();
+        let depth = path
+            .strip_prefix(&self.entry.entry)
+            .map(|rel| rel.components().count())
+            .unwrap_or_default();
+        if !self.entry.within_depth(depth, is_dir) {
+            return skipped(Reason::Depth, false);
+        }
+        if !self.global.within_depth(depth, is_dir) {
+            return skipped(Reason::Depth, true);
+        }
+        if !hash_target {
+            if self.device.is_some() && device_of(path) != self.device {
+                return skipped(Reason::OtherFileSystem, false);
+            }
+            if !path.is_file() && !is_dir {
+                return skipped(Reason::Unavailable, false);
+            }
+        }
+        if is_dir {
+            // Context files of the folder are applied to its content
+            self.context.consider(&path.to_path_buf())?;
+        }
+        let verdict = self.context.decide(path);
+        if !verdict.accepted() {
+            return skipped(verdict.reason().unwrap_or(Reason::Default), false);
+        }
+        let (reason, global) = decisions
+            .iter()
+            .find_map(|(decision, global)| reason(decision).map(|reason| (reason, *global)))
+            .or_else(|| verdict.reason().map(|reason| (reason, false)))
+            .unwrap_or((Reason::Default, false));
+        Ok((Explanation::new(path, true, reason, global), is_dir))
+    }
+}
+
+/// Explains whether the given path would be collected by `collect_entry()`. Each folder on the way
+/// from the entry to the path should be accepted by the entry's and global filters and by context
+/// files (which are read from each of these folders).
+///
+/// # Parameters
+///
+/// - `entry`: The entry the path belongs to.
+/// - `global`: Global settings applied in addition to the entry's settings.
+/// - `symlinks`: The way symlinks are handled.
+/// - `path`: The full path to explain.
+///
+/// # Returns
+///
+/// - `Result<Explanation, E>`: The decision along with the decisive rule; an error if a context
+///   file cannot be read.
+pub(crate) fn explain(
+    entry: &Entry,
+    global: &Entry,
+    symlinks: &SymlinkPolicy,
+    path: &Path,
+) -> Result<Explanation, E> {
+    if !path.starts_with(&entry.entry) {
+        return Ok(Explanation::outside(path));
+    }
+    explain_in(entry, global, symlinks, &mut context(entry, global)?, path)
+}
+
+/// Does the same as `explain()`, but with the given context of the entry (created by `context()`).
+/// The context keeps the rules of visited folders, so context files, git's exclude files and index
+/// aren't read again, if the same context is used to explain many paths.
+///
+/// # Parameters
+///
+/// - `entry`: The entry the path belongs to.
+/// - `global`: Global settings applied in addition to the entry's settings.
+/// - `symlinks`: The way symlinks are handled.
+/// - `context`: The context of the entry.
+/// - `path`: The full path to explain.
+///
+/// # Returns
+///
+/// - `Result<Explanation, E>`: The decision along with the decisive rule; an error if a context
+///   file cannot be read.
+pub(crate) fn explain_in(
+    entry: &Entry,
+    global: &Entry,
+    symlinks: &SymlinkPolicy,
+    context: &mut Context,
+    path: &Path,
+) -> Result<Explanation, E> {
+    let Ok(rel) = path.strip_prefix(&entry.entry) else {
+        return Ok(Explanation::outside(path));
+    };
+    let mut tracer = Tracer::new(entry, global, symlinks, context)?;
+    let mut current = entry.entry.clone();
+    let mut explanation = Explanation::new(path, true, Reason::Default, false);
+    for component in rel.components() {
+        current = current.join(component);
+        let (step, read) = tracer.check(&current)?;
+        if !step.accepted {
+            return Ok(step.about(path));
+        }
+        if current != path && !read {
+            // A symlink to a folder, which is collected as a file (`SymlinkPolicy::HashTarget`)
+            return Ok(Explanation::new(&current, false, Reason::Symlink, false).about(path));
+        }
+        explanation = step.about(path);
+    }
+    Ok(explanation)
+}
+
+/// Looks for paths of the entry, which aren't collected, and explains why they are skipped. The
+/// content of a skipped folder isn't listed: the folder is reported instead.
+///
+/// # Parameters
+///
+/// - `entry`: The entry to look through.
+/// - `global`: Global settings applied in addition to the entry's settings.
+/// - `symlinks`: The way symlinks are handled.
+/// - `breaker`: A breaker to handle interruptions.
+///
+/// # Returns
+///
+/// - `Result<Vec<Explanation>, E>`: Explanations of skipped paths sorted by paths.
+///
+/// # Errors
+///
+/// Returns an error if the operation is interrupted or a context file cannot be read. Folders,
+/// which cannot be read, are skipped silently; they are reported by collecting.
+pub(crate) fn skipped(
+    entry: &Entry,
+    global: &Entry,
+    symlinks: &SymlinkPolicy,
+    breaker: &Breaker,
+) -> Result<Vec<Explanation>, E> {
+    let mut context = context(entry, global)?;
+    let mut tracer = Tracer::new(entry, global, symlinks, &mut context)?;
+    let mut skipped = Vec::new();
+    let mut folders = vec![entry.entry.clone()];
+    while let Some(folder) = folders.pop() {
+        if breaker.is_aborted() {
+            return Err(E::Aborted);
+        }
+        let Ok(els) = read_dir(&folder) else {
+            continue;
+        };
+        for el in els.flatten() {
+            let (explanation, read) = tracer.check(&el.path())?;
+            if !explanation.accepted {
+                skipped.push(explanation);
+            } else if read {
+                folders.push(explanation.path);
+            }
+        }
+    }
+    skipped.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(skipped)
+}
@@ -0,0 +1,348 @@
+use crate::collector::E;
+use std::{
+    collections::HashSet,
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+/// The name of git's folder (or file, for worktrees and submodules).
+pub const GIT_DIR: &str = ".git";
+/// The name of git's context files.
+pub const GITIGNORE: &str = ".gitignore";
+
+/// The signature of the index file.
+const INDEX_SIGNATURE: &[u8] = b"DIRC";
+/// The size of fixed fields of an index entry before the object id: ctime, mtime, dev, ino, mode,
+/// uid, gid and size (4 bytes each, except 8 bytes of times).
+const INDEX_ENTRY_STAT: usize = 40;
+/// The offset of the mode in an index entry.
+const INDEX_ENTRY_MODE: usize = 24;
+/// The flag of an index entry with extended flags (index version 3 and later).
+const INDEX_EXTENDED_FLAG: u16 = 0x4000;
+
+/// A git repository found for a path.
+#[derive(Debug)]
+pub struct Repository {
+    /// The root of the working tree.
+    pub workdir: PathBuf,
+    /// The git folder of the working tree (it has the index).
+    gitdir: PathBuf,
+    /// The common git folder (it has the configuration and `info/exclude`); differs from `gitdir`
+    /// for linked worktrees.
+    common: PathBuf,
+}
+
+impl Repository {
+    /// Looks for the repository of the path in the path itself and its parents.
+    ///
+    /// # Parameters
+    ///
+    /// - `path`: The full path to a folder.
+    ///
+    /// # Returns
+    ///
+    /// - `Option<Repository>`: The repository or `None` if the path doesn't belong to a repository.
+    pub fn discover(path: &Path) -> Option<Self> {
+        for workdir in path.ancestors() {
+            let dotgit = workdir.join(GIT_DIR);
+            let gitdir = if dotgit.is_dir() {
+                dotgit
+            } else if dotgit.is_file() {
+                // Worktrees and submodules have a file with a path to the git folder
+                let content = fs::read_to_string(&dotgit).ok()?;
+                let gitdir = content.trim().strip_prefix("gitdir:")?.trim();
+                workdir.join(gitdir)
+            } else {
+                continue;
+            };
+            let common = fs::read_to_string(gitdir.join("commondir"))
+                .map(|common| gitdir.join(common.trim()))
+                .unwrap_or_else(|_| gitdir.clone());
+            return Some(Self {
+                workdir: workdir.to_path_buf(),
+                gitdir,
+                common,
+            });
+        }
+        None
+    }
+
+    /// Returns the exclude files of the repository in the order of priority: `info/exclude` and
+    /// the file defined by `core.excludesFile`. Only existing files are returned.
+    pub fn excludes(&self) -> Vec<PathBuf> {
+        self.all_excludes()
+            .into_iter()
+            .filter(|path| path.is_file())
+            .collect()
+    }
+
+    /// Returns the exclude files of the repository in the order of priority including missing ones.
+    pub fn all_excludes(&self) -> Vec<PathBuf> {
+        [
+            Some(self.common.join("info").join("exclude")),
+            self.config("core", "excludesfile")
+                .map(|path| expand_home(&path))
+                .or_else(|| xdg_config().map(|xdg| xdg.join("git").join("ignore"))),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    /// Returns the path to the index of the repository.
+    pub fn index(&self) -> PathBuf {
+        self.gitdir.join("index")
+    }
+
+    /// Reads the index of the repository and returns the list of tracked files. Gitlinks
+    /// (submodules) and folders of sparse indexes aren't files and are skipped. A missing index
+    /// is considered as an empty one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the index cannot be read or has an unsupported format.
+    pub fn tracked(&self) -> Result<Tracked, E> {
+        let filename = self.index();
+        if !filename.exists() {
+            return Ok(Tracked::default());
+        }
+        let content = fs::read(&filename)?;
+        let hash_len = match self.config("extensions", "objectformat").as_deref() {
+            Some("sha256") => 32,
+            _ => 20,
+        };
+        let paths = parse_index(&content, hash_len)
+            .map_err(|err| E::GitIndex(filename.clone(), err.to_owned()))?;
+        let mut tracked = Tracked::default();
+        for path in paths {
+            let path = self.workdir.join(path);
+            // Folders up to the root of the working tree are accepted to be read
+            let mut parent = path.parent();
+            while let Some(folder) = parent {
+                if !tracked.folders.insert(folder.to_path_buf()) || folder == self.workdir {
+                    break;
+                }
+                parent = folder.parent();
+            }
+            tracked.files.insert(path);
+        }
+        Ok(tracked)
+    }
+
+    /// Reads a value of the configuration. Configuration files are read in git's order (system,
+    /// global, repository); the last found value wins.
+    fn config(&self, section: &str, key: &str) -> Option<String> {
+        let mut files = Vec::new();
+        if env::var_os("GIT_CONFIG_NOSYSTEM").is_none() {
+            files.push(PathBuf::from("/etc/gitconfig"));
+        }
+        if let Some(global) = env::var_os("GIT_CONFIG_GLOBAL") {
+            files.push(PathBuf::from(global));
+        } else {
+            if let Some(xdg) = xdg_config() {
+                files.push(xdg.join("git").join("config"));
+            }
+            if let Some(home) = home() {
+                files.push(home.join(".gitconfig"));
+            }
+        }
+        files.push(self.common.join("config"));
+        files
+            .iter()
+            .rev()
+            .filter_map(|file| fs::read_to_string(file).ok())
+            .find_map(|content| config_value(&content, section, key))
+    }
+}
+
+/// Files tracked by git and the folders containing them (full paths).
+#[derive(Debug, Default)]
+pub struct Tracked {
+    files: HashSet<PathBuf>,
+    folders: HashSet<PathBuf>,
+}
+
+impl Tracked {
+    /// Checks whether the path is a tracked file or a folder with tracked files.
+    pub fn filtered(&self, path: &Path) -> bool {
+        self.files.contains(path) || self.folders.contains(path)
+    }
+}
+
+fn home() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+}
+
+fn xdg_config() -> Option<PathBuf> {
+    env::var_os("XDG_CONFIG_HOME")
+        .filter(|xdg| !xdg.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| home().map(|home| home.join(".config")))
+}
+
+/// Expands the leading "~/" of a path from the configuration.
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), home()) {
+        (Some(rel), Some(home)) => home.join(rel),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// Looks for the value of a key in the content of a configuration file. Sections and keys are
+/// case-insensitive; subsections (like `[remote "origin"]`) aren't supported, because they aren't
+/// needed. The last found value wins.
+fn config_value(content: &str, section: &str, key: &str) -> Option<String> {
+    let mut current = String::new();
+    let mut found = None;
+    for line in content.lines() {
+        let mut line = line.trim();
+        if let Some(header) = line.strip_prefix('[') {
+            let Some((name, rest)) = header.split_once(']') else {
+                continue;
+            };
+            current = name.trim().to_lowercase();
+            // A key can follow the header on the same line
+            line = rest.trim();
+        }
+        let Some((name, value)) = line.split_once('=') else {
+            continue;
+        };
+        if current == section && name.trim().eq_ignore_ascii_case(key) {
+            found = Some(config_unquote(value.trim()));
+        }
+    }
+    found
+}
+
+/// Parses a value of the configuration: removes quotes and comments, resolves escapes.
+fn config_unquote(value: &str) -> String {
+    let mut result = String::new();
+    let mut quoted = false;
+    let mut chars = value.chars();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '"' => quoted = !quoted,
+            '#' | ';' if !quoted => break,
+            '\\' => match chars.next() {
+                Some('n') => result.push('\n'),
+                Some('t') => result.push('\t'),
+                Some(ch) => result.push(ch),
+                None => break,
+            },
+            ch => result.push(ch),
+        }
+    }
+    if quoted {
+        result
+    } else {
+        result.trim_end().to_owned()
+    }
+}
+
+/// Reads a big-endian number of the given size at the position.
+fn read_be(content: &[u8], pos: usize, size: usize) -> Result<u32, &'static str> {
+    content
+        .get(pos..pos + size)
+        .map(|bytes| bytes.iter().fold(0u32, |acc, b| (acc << 8) | *b as u32))
+        .ok_or("unexpected end of file")
+}
+
+/// Parses the index file (versions 2, 3 and 4) and returns paths of tracked files relative to the
+/// root of the working tree. Conflicting files (with several stages) are listed once.
+///
+/// # Parameters
+///
+/// - `content`: The content of the index file.
+/// - `hash_len`: The size of object ids (20 for SHA-1, 32 for SHA-256).
+///
+/// # Returns
+///
+/// - `Result<Vec<PathBuf>, &str>`: Paths of tracked files or the reason of a failure.
+fn parse_index(content: &[u8], hash_len: usize) -> Result<Vec<PathBuf>, &'static str> {
+    if !content.starts_with(INDEX_SIGNATURE) {
+        return Err("invalid signature");
+    }
+    let version = read_be(content, 4, 4)?;
+    if !(2..=4).contains(&version) {
+        return Err("unsupported version");
+    }
+    let count = read_be(content, 8, 4)?;
+    let mut pos = 12;
+    let mut paths = Vec::new();
+    let mut previous: Vec<u8> = Vec::new();
+    for _ in 0..count {
+        let start = pos;
+        let mode = read_be(content, pos + INDEX_ENTRY_MODE, 4)?;
+        pos += INDEX_ENTRY_STAT + hash_len;
+        let flags = read_be(content, pos, 2)? as u16;
+        pos += 2;
+        if version >= 3 && flags & INDEX_EXTENDED_FLAG != 0 {
+            pos += 2;
+        }
+        let name = if version == 4 {
+            // The name is compressed: the number of bytes to remove from the end of the previous
+            // name, followed by the suffix to append
+            let mut strip: usize = 0;
+            loop {
+                let byte = *content.get(pos).ok_or("unexpected end of file")?;
+                pos += 1;
+                strip = (strip << 7) | (byte & 0x7f) as usize;
+                if byte & 0x80 == 0 {
+                    break;
+                }
+                strip += 1;
+            }
+            let len = previous
+                .len()
+                .checked_sub(strip)
+                .ok_or("invalid path compression")?;
+            let suffix = content[pos.min(content.len())..]
+                .split(|b| *b == 0)
+                .next()
+                .ok_or("unexpected end of file")?;
+            pos += suffix.len() + 1;
+            let mut name = previous[..len].to_vec();
+            name.extend_from_slice(suffix);
+            name
+        } else {
+            let name = content[pos.min(content.len())..]
+                .split(|b| *b == 0)
+                .next()
+                .ok_or("unexpected end of file")?
+                .to_vec();
+            // Entries are padded with 1-8 zero bytes to a multiple of 8
+            pos = start + ((pos - start + name.len() + 8) & !7);
+            name
+        };
+        if pos > content.len() {
+            return Err("unexpected end of file");
+        }
+        // Regular files and symlinks; gitlinks (0o160000) and sparse folders (0o040000) are
+        // skipped
+        let kind = mode >> 12;
+        if (kind == 0o10 || kind == 0o12) && previous != name {
+            paths.push(path_from_bytes(&name));
+        }
+        previous = name;
+    }
+    Ok(paths)
+}
+
+/// Converts a path of the index ("/" as a separator) into a relative path.
+fn path_from_bytes(name: &[u8]) -> PathBuf {
+    name.split(|b| *b == b'/')
+        .map(|component| {
+            #[cfg(unix)]
+            {
+                use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+                PathBuf::from(OsStr::from_bytes(component))
+            }
+            #[cfg(not(unix))]
+            {
+                PathBuf::from(String::from_utf8_lossy(component).as_ref())
+            }
+        })
+        .collect()
+}
@@ -1,17 +1,26 @@
 mod context;
 pub mod error;
+mod explain;
+mod git;
 mod pool;
+pub(crate) mod wildmatch;
 mod worker;
 
+#[cfg(all(feature = "watcher", target_os = "linux"))]
+use crate::entry::GitAware;
 use crate::{
     breaker::Breaker,
-    entry::Entry,
+    entry::{ContextFileAccepted, Entry},
     walker::{options, JobType, Progress},
 };
-use context::Context;
+pub(crate) use context::Context;
 pub use error::E;
+pub(crate) use explain::{explain, skipped};
+pub use explain::{Explanation, Reason};
 use log::{debug, error, warn};
 pub use pool::Pool;
+#[cfg(all(feature = "watcher", target_os = "linux"))]
+use std::{ffi::OsStr, path::Path};
 use std::{
     path::PathBuf,
     sync::mpsc::{channel, Receiver, Sender},
@@ -23,10 +32,11 @@ pub use worker::Worker;
 /// Defines tolerance levels for errors during the collection of file paths. In some cases,
 /// an attempt to read a file or folder can cause an error (for example, a permissions error). To
 /// handle such situations, users can define the behavior of the collector.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub enum Tolerance {
     /// All errors during collection will be logged but will not stop the collecting
     /// process. A list of paths that caused errors will be returned by `collect()`.
+    #[default]
     LogErrors,
     /// All errors during collection will be ignored without logging. The collecting
     /// process will not be stopped. A list of paths that caused errors will be returned
@@ -36,10 +46,25 @@ pub enum Tolerance {
     StopOnErrors,
 }
 
-impl Default for Tolerance {
-    fn default() -> Self {
-        Self::LogErrors
-    }
+/// Defines how symlinks are handled during the collection of file paths.
+///
+/// Regardless of the policy, a symlink to a folder, which is an ancestor of the symlink itself
+/// (a loop), is never followed. Loops are detected by inodes (device and inode numbers on Unix).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum SymlinkPolicy {
+    /// Symlinks to files and folders are followed. Files are collected with the paths of symlinks
+    /// (not with the paths of their targets).
+    #[default]
+    Follow,
+    /// Symlinks are ignored.
+    Skip,
+    /// Symlinks aren't followed; each symlink is collected as a file, and its hash is the hash of
+    /// the target string stored in the link (for example, "../lib/libfoo.so.1"). Broken symlinks
+    /// are collected as well.
+    HashTarget,
+    /// Symlinks are followed only if their targets are inside the entry. It prevents wandering
+    /// out of the entry (for example, into system folders).
+    WithinEntry,
 }
 
 /// Message for communication between `collect()` and workers during collecting.
@@ -150,6 +175,51 @@ pub fn collect(
     breaker: &Breaker,
     tolerance: &Tolerance,
     threads: &Option<usize>,
+) -> CollectingResult {
+    collect_entry(
+        progress,
+        entry,
+        &Entry::default(),
+        breaker,
+        tolerance,
+        threads,
+        &SymlinkPolicy::default(),
+    )
+}
+
+/// Collects file paths based on the provided entry and global settings. Used by `Walker` to apply
+/// `Options::global` to each entry.
+///
+/// Precedence rules:
+/// - A path is collected only if it's accepted by both the entry's own filters and the global
+///   filters. Each of them is evaluated with the regular rules of `Entry::filtered()`.
+/// - As a result, an exclude filter (or `PatternFilter::Ignore`) of the entry or of the global
+///   settings is enough to skip a path; if both the entry and the global settings define include
+///   filters, a path should match both of them.
+/// - Context files of the global settings are considered in addition to the entry's context files.
+/// - `GitAware` of the entry takes precedence over `GitAware` of the global settings.
+///
+/// # Parameters
+///
+/// - `progress`: An optional progress tracker.
+/// - `entry`: The entry point for collecting file paths.
+/// - `global`: Global settings applied in addition to the entry's settings.
+/// - `breaker`: A breaker to handle interruptions.
+/// - `tolerance`: The tolerance level for error handling.
+/// - `threads`: The optional number of threads to use for processing.
+/// - `symlinks`: The way symlinks are handled.
+///
+/// # Returns
+///
+/// - `CollectingResult`: The same as `collect()`.
+pub(crate) fn collect_entry(
+    progress: &Option<Progress>,
+    entry: &Entry,
+    global: &Entry,
+    breaker: &Breaker,
+    tolerance: &Tolerance,
+    threads: &Option<usize>,
+    symlinks: &SymlinkPolicy,
 ) -> CollectingResult {
     let now = Instant::now();
     let (tx_queue, rx_queue): (Sender<Action>, Receiver<Action>) = channel();
@@ -170,11 +240,20 @@ pub fn collect(
     }
     let threads = threads.unwrap_or(cores);
     let entry_inner = entry.clone();
-    let mut context = Context::new(&entry.context);
+    let global_inner = global.clone();
+    let symlinks = symlinks.clone();
+    let mut context = context(entry, global)?;
     let handle: JoinHandle<CollectingResult> = thread::spawn(move || {
         let mut collected: Vec<PathBuf> = Vec::new();
         let mut invalid: Vec<(PathBuf, E)> = Vec::new();
-        let mut workers = Pool::new(threads, entry_inner.clone(), tx_queue.clone(), &breaker);
+        let mut workers = Pool::new(
+            threads,
+            entry_inner.clone(),
+            global_inner,
+            symlinks,
+            tx_queue.clone(),
+            &breaker,
+        );
         debug!("Created pool with {threads} workers for paths collecting");
         let mut pending: Option<Action> = None;
         let mut queue: isize = 0;
@@ -285,3 +364,103 @@ pub fn collect(
     );
     Ok((collected, ignored))
 }
+
+/// Creates the context of the entry: rules of context files of the entry and the global settings
+/// and the view of git on the entry (`GitAware` of the entry takes precedence over the global one).
+///
+/// # Errors
+///
+/// Returns an error if git's view is required, but cannot be read.
+pub(crate) fn context(entry: &Entry, global: &Entry) -> Result<Context, E> {
+    let mut context = Context::new(&context_files(entry, global));
+    if let Some(mode) = entry.git.as_ref().or(global.git.as_ref()) {
+        context.git(mode, &entry.entry)?;
+    }
+    Ok(context)
+}
+
+/// Returns the files, which define the view of git on the entry, but aren't collected from it (see
+/// `GitAware`): the index of the repository with `GitAware::Tracked`; git's exclude files and
+/// `.gitignore` files of the parent folders of the entry up to the root of the working tree with
+/// `GitAware::Ignore`. Files are returned even if they don't exist (yet).
+///
+/// # Returns
+///
+/// - `Vec<PathBuf>`: The files; empty if git's view isn't considered or the entry doesn't belong
+///   to a repository.
+#[cfg(all(feature = "watcher", target_os = "linux"))]
+pub(crate) fn git_sources(entry: &Entry, global: &Entry) -> Vec<PathBuf> {
+    let Some(mode) = entry.git.as_ref().or(global.git.as_ref()) else {
+        return Vec::new();
+    };
+    let Some(repo) = git::Repository::discover(&entry.entry) else {
+        return Vec::new();
+    };
+    match mode {
+        GitAware::Tracked => vec![repo.index()],
+        GitAware::Ignore => {
+            let mut sources = repo.all_excludes();
+            for parent in entry.entry.ancestors().skip(1) {
+                if !parent.starts_with(&repo.workdir) {
+                    break;
+                }
+                sources.push(parent.join(git::GITIGNORE));
+            }
+            sources
+        }
+    }
+}
+
+/// Checks whether a file with the given name is a context file of the entry: one of the context
+/// files of the entry or the global settings or `.gitignore` with `GitAware::Ignore`.
+#[cfg(all(feature = "watcher", target_os = "linux"))]
+pub(crate) fn is_context_file(entry: &Entry, global: &Entry, filename: &OsStr) -> bool {
+    let git = entry.git.as_ref().or(global.git.as_ref());
+    (matches!(git, Some(GitAware::Ignore)) && filename == git::GITIGNORE)
+        || context_files(entry, global)
+            .iter()
+            .any(|cx| filename == cx.filename())
+}
+
+/// Returns the list of context files of the entry extended by the context files of the global
+/// settings.
+pub(crate) fn context_files(entry: &Entry, global: &Entry) -> Vec<ContextFileAccepted> {
+    entry
+        .context
+        .iter()
+        .chain(
+            global
+                .context
+                .iter()
+                .filter(|cx| !entry.context.contains(cx)),
+        )
+        .cloned()
+        .collect()
+}
+
+/// Checks whether the given path would be collected by `collect_entry()`. It's a shortcut for
+/// `explain(entry, global, symlinks, path)?.accepted`, which reuses the given context of the entry
+/// (created by `context()`), so context files aren't read again for each path.
+///
+/// # Parameters
+///
+/// - `entry`: The entry the path belongs to.
+/// - `global`: Global settings applied in addition to the entry's settings.
+/// - `symlinks`: The way symlinks are handled.
+/// - `context`: The context of the entry.
+/// - `path`: The full path to check.
+///
+/// # Returns
+///
+/// - `Result<bool, E>`: `true` if the path is accepted; `false` if the path is filtered out or
+///   doesn't belong to the entry; an error if a context file cannot be read.
+#[cfg(all(feature = "watcher", target_os = "linux"))]
+pub(crate) fn accepted(
+    entry: &Entry,
+    global: &Entry,
+    symlinks: &SymlinkPolicy,
+    context: &mut Context,
+    path: &Path,
+) -> Result<bool, E> {
+    Ok(explain::explain_in(entry, global, symlinks, context, path)?.accepted)
+}
@@ -1,4 +1,4 @@
-use super::{Action, Worker};
+use super::{Action, SymlinkPolicy, Worker};
 use crate::{breaker::Breaker, entry::Entry};
 use std::sync::mpsc::Sender;
 
@@ -16,17 +16,28 @@ impl Pool {
     ///
     /// - `count`: The number of workers to create.
     /// - `entry`: The entry point for collecting file paths.
+    /// - `global`: Global settings applied in addition to the entry's filters.
+    /// - `symlinks`: The way symlinks are handled.
     /// - `tx_queue`: The sender channel for sending actions to the workers.
     /// - `breaker`: The breaker to handle interruptions.
     ///
     /// # Returns
     ///
     /// - A new `Pool` instance.
-    pub fn new(count: usize, entry: Entry, tx_queue: Sender<Action>, breaker: &Breaker) -> Self {
+    pub fn new(
+        count: usize,
+        entry: Entry,
+        global: Entry,
+        symlinks: SymlinkPolicy,
+        tx_queue: Sender<Action>,
+        breaker: &Breaker,
+    ) -> Self {
         let mut workers: Vec<Worker> = Vec::new();
         for _ in 0..count {
             workers.push(Worker::run(
                 entry.clone(),
+                global.clone(),
+                symlinks.clone(),
                 tx_queue.clone(),
                 breaker.clone(),
             ));
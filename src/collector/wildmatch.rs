@@ -0,0 +1,261 @@
+//! A port of git's `wildmatch()`, which is used to match patterns of ignore files exactly in the
+//! way git does it.
+
+/// Result of matching. Besides "match" and "no match", the matching can be aborted, which allows
+/// cutting the backtracking of asterisks.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Matched {
+    Match,
+    NoMatch,
+    /// Nothing can match; stop backtracking completely.
+    AbortAll,
+    /// Stop backtracking up to the closest "**".
+    AbortToStarStar,
+}
+
+/// Checks whether the text matches the pattern.
+///
+/// # Parameters
+///
+/// - `pattern`: The glob pattern. Supported: `*`, `?`, `[...]` (ranges, negation with `!` or `^`,
+///   classes like `[:alpha:]`), `\` to escape the next character and `**`. Wildcards don't
+///   match `/`, except `**` as a whole path component (git's `WM_PATHNAME` mode).
+/// - `text`: The text to match.
+///
+/// # Returns
+///
+/// - `bool`: `true` if the text matches the pattern.
+pub(crate) fn wildmatch(pattern: &[u8], text: &[u8]) -> bool {
+    dowild(pattern, text) == Matched::Match
+}
+
+/// Characters, which have a special meaning in patterns.
+fn is_glob_special(ch: u8) -> bool {
+    matches!(ch, b'*' | b'?' | b'[' | b'\\')
+}
+
+fn dowild(pattern: &[u8], text: &[u8]) -> Matched {
+    // Out of bounds is considered as the end of a string (like '\0' in C)
+    let at = |s: &[u8], i: usize| s.get(i).copied().unwrap_or(0);
+    let mut p = 0;
+    let mut t = 0;
+    while at(pattern, p) != 0 {
+        let mut p_ch = at(pattern, p);
+        let mut t_ch = at(text, t);
+        if t_ch == 0 && p_ch != b'*' {
+            return Matched::AbortAll;
+        }
+        match p_ch {
+            b'?' => {
+                // Matches anything, but '/'
+                if t_ch == b'/' {
+                    return Matched::NoMatch;
+                }
+            }
+            b'*' => {
+                let match_slash;
+                p += 1;
+                if at(pattern, p) == b'*' {
+                    let prev = p.checked_sub(2);
+                    while at(pattern, p) == b'*' {
+                        p += 1;
+                    }
+                    let after = at(pattern, p);
+                    if prev.map_or(true, |prev| pattern[prev] == b'/')
+                        && (after == 0
+                            || after == b'/'
+                            || (after == b'\\' && at(pattern, p + 1) == b'/'))
+                    {
+                        // "**/" can match nothing: "foo/**/bar" matches both "foo/bar" and
+                        // "foo/a/bar"
+                        if after == b'/' && dowild(&pattern[p + 1..], &text[t..]) == Matched::Match
+                        {
+                            return Matched::Match;
+                        }
+                        match_slash = true;
+                    } else {
+                        // "**" not as a whole component is the same as "*"
+                        match_slash = false;
+                    }
+                } else {
+                    match_slash = false;
+                }
+                if at(pattern, p) == 0 {
+                    // Trailing "**" matches everything; trailing "*" matches only if there are
+                    // no more slashes
+                    if !match_slash && text[t..].contains(&b'/') {
+                        return Matched::NoMatch;
+                    }
+                    return Matched::Match;
+                } else if !match_slash && at(pattern, p) == b'/' {
+                    // One asterisk followed by a slash matches the next folder
+                    let Some(slash) = text[t..].iter().position(|ch| *ch == b'/') else {
+                        return Matched::NoMatch;
+                    };
+                    t += slash;
+                    // The slash is consumed by the loop
+                    t += 1;
+                    p += 1;
+                    continue;
+                }
+                loop {
+                    if t_ch == 0 {
+                        break;
+                    }
+                    // Advance faster if the asterisk is followed by a literal: everything before
+                    // the literal belongs to the asterisk
+                    if !is_glob_special(at(pattern, p)) {
+                        let literal = at(pattern, p);
+                        loop {
+                            t_ch = at(text, t);
+                            if t_ch == 0 || (!match_slash && t_ch == b'/') || t_ch == literal {
+                                break;
+                            }
+                            t += 1;
+                        }
+                        if t_ch != literal {
+                            return if match_slash {
+                                Matched::AbortAll
+                            } else {
+                                Matched::AbortToStarStar
+                            };
+                        }
+                    }
+                    let matched = dowild(&pattern[p..], &text[t..]);
+                    if matched != Matched::NoMatch {
+                        if !match_slash || matched != Matched::AbortToStarStar {
+                            return matched;
+                        }
+                    } else if !match_slash && t_ch == b'/' {
+                        return Matched::AbortToStarStar;
+                    }
+                    t += 1;
+                    t_ch = at(text, t);
+                }
+                return Matched::AbortAll;
+            }
+            b'[' => {
+                p += 1;
+                p_ch = at(pattern, p);
+                if p_ch == b'!' {
+                    p_ch = b'^';
+                }
+                let negated = p_ch == b'^';
+                if negated {
+                    p += 1;
+                    p_ch = at(pattern, p);
+                }
+                let mut prev_ch: u8 = 0;
+                let mut matched = false;
+                loop {
+                    if p_ch == 0 {
+                        return Matched::AbortAll;
+                    }
+                    if p_ch == b'\\' {
+                        p += 1;
+                        p_ch = at(pattern, p);
+                        if p_ch == 0 {
+                            return Matched::AbortAll;
+                        }
+                        if t_ch == p_ch {
+                            matched = true;
+                        }
+                    } else if p_ch == b'-'
+                        && prev_ch != 0
+                        && at(pattern, p + 1) != 0
+                        && at(pattern, p + 1) != b']'
+                    {
+                        p += 1;
+                        p_ch = at(pattern, p);
+                        if p_ch == b'\\' {
+                            p += 1;
+                            p_ch = at(pattern, p);
+                            if p_ch == 0 {
+                                return Matched::AbortAll;
+                            }
+                        }
+                        if t_ch <= p_ch && t_ch >= prev_ch {
+                            matched = true;
+                        }
+                        // Makes "prev_ch" to be reset
+                        p_ch = 0;
+                    } else if p_ch == b'[' && at(pattern, p + 1) == b':' {
+                        p += 2;
+                        let start = p;
+                        while at(pattern, p) != 0 && at(pattern, p) != b']' {
+                            p += 1;
+                        }
+                        if at(pattern, p) == 0 {
+                            return Matched::AbortAll;
+                        }
+                        if p == start || pattern[p - 1] != b':' {
+                            // No ":]", so it's a regular set starting with '['
+                            p = start - 2;
+                            p_ch = b'[';
+                            if t_ch == p_ch {
+                                matched = true;
+                            }
+                        } else {
+                            let Some(class) = class(&pattern[start..p - 1], t_ch) else {
+                                // Malformed class
+                                return Matched::AbortAll;
+                            };
+                            if class {
+                                matched = true;
+                            }
+                            p_ch = 0;
+                        }
+                    } else if t_ch == p_ch {
+                        matched = true;
+                    }
+                    prev_ch = p_ch;
+                    p += 1;
+                    p_ch = at(pattern, p);
+                    if p_ch == b']' {
+                        break;
+                    }
+                }
+                if matched == negated || t_ch == b'/' {
+                    return Matched::NoMatch;
+                }
+            }
+            _ => {
+                if p_ch == b'\\' {
+                    // Literal match with the next character
+                    p += 1;
+                    p_ch = at(pattern, p);
+                }
+                if t_ch != p_ch {
+                    return Matched::NoMatch;
+                }
+            }
+        }
+        p += 1;
+        t += 1;
+    }
+    if at(text, t) == 0 {
+        Matched::Match
+    } else {
+        Matched::NoMatch
+    }
+}
+
+/// Checks whether the character belongs to the named class (like "alpha" in `[[:alpha:]]`).
+/// Returns `None` if the class is unknown.
+fn class(name: &[u8], ch: u8) -> Option<bool> {
+    Some(match name {
+        b"alnum" => ch.is_ascii_alphanumeric(),
+        b"alpha" => ch.is_ascii_alphabetic(),
+        b"blank" => ch == b' ' || ch == b'\t',
+        b"cntrl" => ch.is_ascii_control(),
+        b"digit" => ch.is_ascii_digit(),
+        b"graph" => ch.is_ascii_graphic(),
+        b"lower" => ch.is_ascii_lowercase(),
+        b"print" => ch.is_ascii_graphic() || ch == b' ',
+        b"punct" => ch.is_ascii_punctuation(),
+        b"space" => matches!(ch, b' ' | b'\t' | b'\n' | b'\r'),
+        b"upper" => ch.is_ascii_uppercase(),
+        b"xdigit" => ch.is_ascii_hexdigit(),
+        _ => return None,
+    })
+}
@@ -1,10 +1,10 @@
-use super::Action;
+use super::{Action, SymlinkPolicy};
 
 use crate::{breaker::Breaker, entry::Entry};
-use log::error;
+use log::{debug, error};
 use std::{
-    fs::{read_dir, read_link},
-    path::PathBuf,
+    fs::read_dir,
+    path::{Path, PathBuf},
     sync::{
         atomic::{AtomicBool, Ordering},
         mpsc::{channel, Receiver, Sender},
@@ -42,19 +42,39 @@ impl Worker {
     /// # Parameters
     ///
     /// - `entry`: The entry point for collecting file paths.
+    /// - `global`: Global settings applied in addition to the entry's filters.
+    /// - `symlinks`: The way symlinks are handled.
     /// - `tx_queue`: The sender channel for sending actions to `collect()` function.
     /// - `breaker`: The breaker to handle interruptions.
     ///
     /// # Returns
     ///
     /// - A new `Worker` instance.
-    pub fn run(entry: Entry, tx_queue: Sender<Action>, breaker: Breaker) -> Self {
+    pub fn run(
+        entry: Entry,
+        global: Entry,
+        symlinks: SymlinkPolicy,
+        tx_queue: Sender<Action>,
+        breaker: Breaker,
+    ) -> Self {
         let (tx_task, rx_task): (Sender<Task>, Receiver<Task>) = channel();
         let queue = Arc::new(RwLock::new(0));
         let available: Arc<AtomicBool> = Arc::new(AtomicBool::new(true));
         let available_inner = available.clone();
         let queue_inner = queue.clone();
         let handle = thread::spawn(move || {
+            // Resolved path of the entry to check targets of symlinks
+            let root = if symlinks == SymlinkPolicy::WithinEntry {
+                entry.entry.canonicalize().ok()
+            } else {
+                None
+            };
+            // Device of the entry to stay on its file system
+            let device = if entry.one_file_system || global.one_file_system {
+                device_of(&entry.entry)
+            } else {
+                None
+            };
             let send = |action: Action| {
                 tx_queue.send(action).inspect_err(|_err| {
                     error!(
@@ -114,36 +134,66 @@ impl Worker {
                             }
                         }
                     };
-                    if !path.exists() {
+                    let is_symlink = path.is_symlink();
+                    // Metadata is read once for checking the presence and for all filters
+                    let md = path.metadata().ok();
+                    // Broken symlinks still have targets to be hashed
+                    let present =
+                        md.is_some() || (is_symlink && symlinks == SymlinkPolicy::HashTarget);
+                    if !present {
                         // It might be after folder read, file already doesn't exist
                         continue;
                     }
-                    if !entry.filtered(&path) {
+                    if !entry.filtered_with(&path, md.as_ref())
+                        || !global.filtered_with(&path, md.as_ref())
+                    {
                         continue;
                     }
-                    if path.is_file() || path.is_dir() {
-                        if check(path, &mut collected).is_err() {
-                            break 'outer;
-                        }
-                    } else if path.is_symlink() {
-                        let path = match read_link(&path) {
-                            Ok(path) => path,
-                            Err(err) => {
-                                if response(Action::Error(path, err.into())).is_err() {
-                                    break 'outer;
-                                } else {
-                                    continue;
+                    let depth = path
+                        .strip_prefix(&entry.entry)
+                        .map(|rel| rel.components().count())
+                        .unwrap_or_default();
+                    let is_dir = md.as_ref().is_some_and(|md| md.is_dir());
+                    if is_symlink {
+                        match symlinks {
+                            SymlinkPolicy::Skip => continue,
+                            SymlinkPolicy::HashTarget => {
+                                // The symlink itself is collected as a file
+                                if entry.within_depth(depth, false)
+                                    && global.within_depth(depth, false)
+                                {
+                                    collected.push(path);
                                 }
+                                continue;
                             }
-                        };
-                        if path.exists() && (path.is_file() || path.is_dir()) {
-                            if check(path, &mut collected).is_err() {
-                                break 'outer;
+                            SymlinkPolicy::WithinEntry => {
+                                let within = match (&root, path.canonicalize()) {
+                                    (Some(root), Ok(target)) => target.starts_with(root),
+                                    _ => false,
+                                };
+                                if !within {
+                                    continue;
+                                }
                             }
-                        } else {
+                            SymlinkPolicy::Follow => {}
+                        }
+                        if is_dir && is_loop(&path) {
+                            debug!("Symlink {} leads to a loop; ignored", path.display());
                             continue;
                         }
                     }
+                    if !entry.within_depth(depth, is_dir) || !global.within_depth(depth, is_dir) {
+                        continue;
+                    }
+                    if device.is_some() && device_of(&path) != device {
+                        debug!("{} is on another file system; ignored", path.display());
+                        continue;
+                    }
+                    if (md.as_ref().is_some_and(|md| md.is_file()) || is_dir)
+                        && check(path, &mut collected).is_err()
+                    {
+                        break 'outer;
+                    }
                 }
                 if response(Action::Processed(Ok(collected))).is_err() {
                     break 'outer;
@@ -195,3 +245,48 @@ impl Worker {
         }
     }
 }
+
+/// Identity of a folder used to detect loops of symlinks.
+#[cfg(unix)]
+type FileId = (u64, u64);
+#[cfg(not(unix))]
+type FileId = PathBuf;
+
+/// Returns the identity of a file or a folder: device and inode numbers on Unix; the resolved
+/// path on other platforms. Symlinks are followed.
+fn file_id(path: &Path) -> Option<FileId> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        path.metadata().ok().map(|md| (md.dev(), md.ino()))
+    }
+    #[cfg(not(unix))]
+    {
+        path.canonicalize().ok()
+    }
+}
+
+/// Returns the device of a file or a folder (`st_dev`). Symlinks are followed. On non-Unix
+/// platforms devices aren't compared, so `None` is returned.
+pub(super) fn device_of(path: &Path) -> Option<u64> {
+    #[cfg(unix)]
+    {
+        file_id(path).map(|(dev, _)| dev)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = path;
+        None
+    }
+}
+
+/// Checks whether a symlink to a folder leads to one of the folders on its own path. Following
+/// such a symlink would make an endless loop.
+pub(super) fn is_loop(link: &Path) -> bool {
+    let Some(target) = file_id(link) else {
+        return false;
+    };
+    link.ancestors()
+        .skip(1)
+        .any(|ancestor| file_id(ancestor).as_ref() == Some(&target))
+}
@@ -5,6 +5,13 @@ use std::path::{Path, PathBuf};
 /// `ContextFile` is used to define a rule's file, similar to `.gitignore`.
 /// This file will be used to obtain rules for filtering.
 ///
+/// Rules are parsed and matched following `.gitignore` semantics: patterns are relative to the
+/// folder of the file (a leading or middle `/` anchors the pattern, otherwise it matches the name
+/// at any depth), a trailing `/` matches folders only (not symlinks to folders), `**` matches any
+/// number of folders, `\` escapes special characters (like `\#` or `\!`), and the last matching
+/// rule wins: a rule of a deeper folder overrides rules of parent folders, and `!` re-includes a
+/// path (unless its parent folder is excluded).
+///
 /// # Type Parameters
 ///
 /// * `T` - A type that can be referenced as a string slice.
@@ -15,8 +22,8 @@ pub enum ContextFile<T: AsRef<str>> {
     ///
     /// # Note
     ///
-    /// Ignore rules are used regularly. This means the rule will be applied to the full path:
-    /// both to check folder paths and file paths.
+    /// Ignore rules are used regularly. This means the rule will be applied to both folder paths
+    /// and file paths; content of ignored folders isn't read.
     Ignore(T),
     /// All rules in the file will be used as accept rules. If the path matches,
     /// it will be accepted. If this rule from the file doesn't match, the file will be ignored.
@@ -24,7 +31,8 @@ pub enum ContextFile<T: AsRef<str>> {
     /// # Note
     ///
     /// Accept rules are used in a non-regular way. This means the rule will be applied only
-    /// to file paths; folder path checks will be skipped.
+    /// to file paths; folder path checks will be skipped. A negative rule (`!`) excludes files
+    /// accepted by previous rules.
     Accept(T),
 }
 
@@ -77,7 +85,7 @@ impl ContextFileAccepted {
     /// # Returns
     ///
     /// - A string slice representing the filename.
-    fn filename(&self) -> &str {
+    pub(crate) fn filename(&self) -> &str {
         match self {
             Self::Accept(s) => s,
             Self::Ignore(s) => s,
@@ -0,0 +1,106 @@
+use std::{fmt, fs::Metadata, path::Path, sync::Arc};
+
+/// A custom filter defined by the application's logic (for example, skipping files owned by a
+/// registry of generated code). `PathFilter` is applied to files and folders in addition to
+/// `Filter` and `PatternFilter`; if it rejects a folder, the folder isn't read.
+///
+/// `PathFilter` is implemented for closures `Fn(&Path, &Metadata) -> bool`.
+///
+/// # Example
+///
+/// ```
+/// use fshasher::{Entry, PathFilter};
+/// use std::{env::temp_dir, fs::Metadata, path::Path};
+///
+/// struct NotEmpty;
+///
+/// impl PathFilter for NotEmpty {
+///     fn accept(&self, _path: &Path, md: &Metadata) -> bool {
+///         md.is_dir() || md.len() > 0
+///     }
+/// }
+///
+/// let entry = Entry::from(temp_dir())
+///     .unwrap()
+///     .path_filter("not-empty", NotEmpty)
+///     .path_filter("no-locks", |path: &Path, _md: &Metadata| {
+///         path.extension().map_or(true, |ext| ext != "lock")
+///     });
+/// ```
+pub trait PathFilter: Send + Sync {
+    /// Checks whether the path should be collected.
+    ///
+    /// # Parameters
+    ///
+    /// - `path`: The full path of a file or a folder.
+    /// - `md`: The metadata of the path (symlinks are followed).
+    ///
+    /// # Returns
+    ///
+    /// - `bool`: `true` if the path is accepted, `false` if it should be skipped.
+    fn accept(&self, path: &Path, md: &Metadata) -> bool;
+}
+
+impl<F: Fn(&Path, &Metadata) -> bool + Send + Sync> PathFilter for F {
+    fn accept(&self, path: &Path, md: &Metadata) -> bool {
+        self(path, md)
+    }
+}
+
+/// Represents an accepted `PathFilter` along with its identity. The identity is given by the user
+/// and stands for the filter in the tracking key (the logic of the filter cannot be compared), so
+/// it should be changed whenever the logic of the filter is changed.
+#[derive(Clone)]
+pub struct PathFilterAccepted {
+    /// The identity of the filter.
+    pub identity: String,
+    filter: Arc<dyn PathFilter>,
+}
+
+impl PathFilterAccepted {
+    /// Creates a new `PathFilterAccepted`.
+    ///
+    /// # Parameters
+    ///
+    /// - `identity`: The identity of the filter.
+    /// - `filter`: The filter.
+    ///
+    /// # Returns
+    ///
+    /// - A new `PathFilterAccepted` instance.
+    pub fn new<S: AsRef<str>, F: PathFilter + 'static>(identity: S, filter: F) -> Self {
+        Self {
+            identity: identity.as_ref().to_owned(),
+            filter: Arc::new(filter),
+        }
+    }
+
+    /// Applies the filter to the path.
+    ///
+    /// # Parameters
+    ///
+    /// - `path`: The path to filter.
+    /// - `md`: The metadata of the path.
+    ///
+    /// # Returns
+    ///
+    /// - `bool`: `true` if the path is accepted, `false` otherwise.
+    pub fn filtered(&self, path: &Path, md: &Metadata) -> bool {
+        self.filter.accept(path, md)
+    }
+}
+
+impl fmt::Debug for PathFilterAccepted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PathFilterAccepted")
+            .field("identity", &self.identity)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "tracking")]
+impl fmt::Display for PathFilterAccepted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.identity)
+    }
+}
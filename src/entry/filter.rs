@@ -1,12 +1,24 @@
 pub use super::E;
 use glob::Pattern;
-#[cfg(feature = "tracking")]
-use std::fmt;
-use std::path::Path;
+use std::{
+    fmt,
+    fs::Metadata,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 /// With `Filter`, a glob pattern can be applied to a file's name or a folder's name only,
 /// whereas a regular glob pattern is applied to the full path. This allows for more accurate filtering.
+///
+/// Besides glob patterns, `Filter` supports predicates on the metadata of files (size, modification
+/// time) and on the nature of paths (hidden paths, extensions). Like `Filter::Files`, predicates
+/// on metadata and extensions are applied to files only. Predicates without strings need the type
+/// of the filter to be specified, for example, `Filter::<&str>::LargerThan(1024)`.
+///
+/// New kinds of filters can be added in minor versions, so matching on `Filter` outside of this
+/// crate needs a wildcard arm.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum Filter<T: AsRef<str>> {
     /// A glob pattern that will be applied to a folder's name only.
     Folders(T),
@@ -14,6 +26,18 @@ pub enum Filter<T: AsRef<str>> {
     Files(T),
     /// A glob pattern that will be applied to the full path (regular usage of glob patterns).
     Common(T),
+    /// Matches files larger than the given size in bytes.
+    LargerThan(u64),
+    /// Matches files smaller than the given size in bytes.
+    SmallerThan(u64),
+    /// Matches files modified after the given time.
+    ModifiedAfter(SystemTime),
+    /// Matches hidden files and folders: names starting with a dot (and, on Windows, paths with
+    /// the hidden attribute).
+    Hidden,
+    /// Matches files with one of the given extensions (with or without a leading dot, for example,
+    /// "rs" or ".rs"). Extensions are compared case-insensitively.
+    Extension(Vec<T>),
 }
 
 impl<T: AsRef<str>> Filter<T> {
@@ -21,12 +45,18 @@ impl<T: AsRef<str>> Filter<T> {
     ///
     /// # Returns
     ///
-    /// - `&str`: The glob pattern as a string slice.
+    /// - `&str`: The glob pattern as a string slice; an empty string for predicates, which don't
+    ///   have glob patterns.
     pub fn as_str(&self) -> &str {
         match self {
             Self::Files(s) => s.as_ref(),
             Self::Folders(s) => s.as_ref(),
             Self::Common(s) => s.as_ref(),
+            Self::LargerThan(..)
+            | Self::SmallerThan(..)
+            | Self::ModifiedAfter(..)
+            | Self::Hidden
+            | Self::Extension(..) => "",
         }
     }
 }
@@ -41,16 +71,25 @@ impl<T: AsRef<str>> TryInto<FilterAccepted> for Filter<T> {
     /// - `Result<FilterAccepted, Self::Error>`: A `FilterAccepted` instance or an error if the pattern is invalid.
     fn try_into(self) -> Result<FilterAccepted, Self::Error> {
         let pattern =
-            Pattern::new(self.as_str()).map_err(|err| (self.as_str().to_string(), err))?;
-        Ok(match self {
-            Self::Files(..) => FilterAccepted::Files(pattern),
-            Self::Folders(..) => FilterAccepted::Folders(pattern),
-            Self::Common(..) => FilterAccepted::Common(pattern),
+            || Pattern::new(self.as_str()).map_err(|err| (self.as_str().to_string(), err));
+        Ok(match &self {
+            Self::Files(..) => FilterAccepted::Files(pattern()?),
+            Self::Folders(..) => FilterAccepted::Folders(pattern()?),
+            Self::Common(..) => FilterAccepted::Common(pattern()?),
+            Self::LargerThan(size) => FilterAccepted::LargerThan(*size),
+            Self::SmallerThan(size) => FilterAccepted::SmallerThan(*size),
+            Self::ModifiedAfter(time) => FilterAccepted::ModifiedAfter(*time),
+            Self::Hidden => FilterAccepted::Hidden,
+            Self::Extension(exts) => FilterAccepted::Extension(
+                exts.iter()
+                    .map(|ext| ext.as_ref().trim_start_matches('.').to_lowercase())
+                    .collect(),
+            ),
         })
     }
 }
 
-/// Represents an accepted filter with a compiled glob pattern.
+/// Represents an accepted filter with a compiled glob pattern or a predicate.
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 pub enum FilterAccepted {
     /// A compiled glob pattern applied to folder names.
@@ -59,25 +98,41 @@ pub enum FilterAccepted {
     Files(Pattern),
     /// A compiled glob pattern applied to the full path.
     Common(Pattern),
+    /// Files larger than the given size in bytes.
+    LargerThan(u64),
+    /// Files smaller than the given size in bytes.
+    SmallerThan(u64),
+    /// Files modified after the given time.
+    ModifiedAfter(SystemTime),
+    /// Hidden files and folders.
+    Hidden,
+    /// Files with one of the given extensions (lowercased, without leading dots).
+    Extension(Vec<String>),
 }
 
-#[cfg(feature = "tracking")]
 impl fmt::Display for FilterAccepted {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                Self::Folders(p) => p.as_str(),
-                Self::Files(p) => p.as_str(),
-                Self::Common(p) => p.as_str(),
-            }
-        )
+        match self {
+            Self::Folders(p) => write!(f, "{}", p.as_str()),
+            Self::Files(p) => write!(f, "{}", p.as_str()),
+            Self::Common(p) => write!(f, "{}", p.as_str()),
+            Self::LargerThan(size) => write!(f, "size>{size}"),
+            Self::SmallerThan(size) => write!(f, "size<{size}"),
+            Self::ModifiedAfter(time) => write!(
+                f,
+                "modified>{}",
+                time.duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_nanos())
+                    .unwrap_or_default()
+            ),
+            Self::Hidden => write!(f, "hidden"),
+            Self::Extension(exts) => write!(f, "ext:{}", exts.join(",")),
+        }
     }
 }
 
 impl FilterAccepted {
-    /// Filters a given path based on the compiled glob pattern.
+    /// Filters a given path based on the compiled glob pattern or the predicate.
     ///
     /// # Parameters
     ///
@@ -88,30 +143,70 @@ impl FilterAccepted {
     /// - `Option<bool>`: `Some(true)` if the path matches the pattern, `Some(false)` if it doesn't,
     ///   or `None` if the path type does not match the filter (e.g., a file filter applied to a directory).
     pub fn filtered<P: AsRef<Path>>(&self, full_path: P) -> Option<bool> {
-        let path = match self {
-            Self::Files(..) => Path::new(full_path.as_ref().file_name()?),
-            Self::Folders(..) => full_path.as_ref(),
-            Self::Common(..) => full_path.as_ref(),
+        // Only glob patterns applied to the full path don't need metadata
+        let md = if !matches!(self, Self::Common(..)) {
+            full_path.as_ref().metadata().ok()
+        } else {
+            None
         };
-        Some(
-            match self {
-                Self::Files(p) => {
-                    if full_path.as_ref().is_file() {
-                        p
-                    } else {
-                        return None;
-                    }
-                }
-                Self::Folders(p) => {
-                    if full_path.as_ref().is_dir() {
-                        p
-                    } else {
-                        return None;
-                    }
-                }
-                Self::Common(p) => p,
+        self.filtered_with(full_path, md.as_ref())
+    }
+
+    /// The same as `filtered()`, but with the metadata of the path taken in advance. It allows
+    /// reading the metadata once for all filters of an entry.
+    ///
+    /// # Parameters
+    ///
+    /// - `full_path`: The path to be filtered.
+    /// - `md`: The metadata of the path (symlinks are followed); `None` if it isn't available.
+    ///
+    /// # Returns
+    ///
+    /// - `Option<bool>`: The same as `filtered()`.
+    pub(crate) fn filtered_with<P: AsRef<Path>>(
+        &self,
+        full_path: P,
+        md: Option<&Metadata>,
+    ) -> Option<bool> {
+        let full_path = full_path.as_ref();
+        let is_file = md.is_some_and(|md| md.is_file());
+        let is_dir = md.is_some_and(|md| md.is_dir());
+        match self {
+            Self::Files(p) => {
+                let name = Path::new(full_path.file_name()?);
+                is_file.then(|| p.matches_path(name))
             }
-            .matches_path(path),
-        )
+            Self::Folders(p) => is_dir.then(|| p.matches_path(full_path)),
+            Self::Common(p) => Some(p.matches_path(full_path)),
+            Self::LargerThan(size) => md.filter(|md| md.is_file()).map(|md| md.len() > *size),
+            Self::SmallerThan(size) => md.filter(|md| md.is_file()).map(|md| md.len() < *size),
+            Self::ModifiedAfter(time) => md
+                .filter(|md| md.is_file())
+                .map(|md| md.modified().is_ok_and(|modified| modified > *time)),
+            Self::Hidden => Some(is_hidden(full_path, md)),
+            Self::Extension(exts) => is_file.then(|| {
+                full_path.extension().is_some_and(|ext| {
+                    let ext = ext.to_string_lossy().to_lowercase();
+                    exts.iter().any(|expected| expected == &ext)
+                })
+            }),
+        }
+    }
+}
+
+/// Checks whether a path is hidden: its name starts with a dot or, on Windows, it has the hidden
+/// attribute.
+fn is_hidden(path: &Path, md: Option<&Metadata>) -> bool {
+    #[cfg(windows)]
+    {
+        use std::os::windows::fs::MetadataExt;
+        const FILE_ATTRIBUTE_HIDDEN: u32 = 0x2;
+        if md.is_some_and(|md| md.file_attributes() & FILE_ATTRIBUTE_HIDDEN != 0) {
+            return true;
+        }
     }
+    #[cfg(not(windows))]
+    let _ = md;
+    path.file_name()
+        .is_some_and(|name| name.to_string_lossy().starts_with('.'))
 }
@@ -0,0 +1,32 @@
+#[cfg(feature = "tracking")]
+use std::fmt;
+
+/// `GitAware` makes collecting follow the view of git on the entry. The repository is found by
+/// looking for a `.git` folder (or a `.git` file of worktrees and submodules) in the entry's
+/// folder and its parents; the `.git` folder itself is never collected. Git's files are read
+/// directly, the git binary isn't required.
+#[derive(Debug, PartialEq, PartialOrd, Clone)]
+pub enum GitAware {
+    /// Paths ignored by git are skipped. All ignore sources of git are considered (from highest
+    /// to lowest priority): `.gitignore` files (including files in the parent folders of the
+    /// entry up to the root of the repository), `.git/info/exclude` and the file defined by
+    /// `core.excludesFile` (by default, `$XDG_CONFIG_HOME/git/ignore`).
+    Ignore,
+    /// Only files tracked by git (listed in the index, `.git/index`) are collected. Ignore rules
+    /// aren't considered, because tracked files can match them.
+    Tracked,
+}
+
+#[cfg(feature = "tracking")]
+impl fmt::Display for GitAware {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Ignore => "ignore",
+                Self::Tracked => "tracked",
+            }
+        )
+    }
+}
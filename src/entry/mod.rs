@@ -1,16 +1,23 @@
 mod context;
+mod custom;
 mod error;
 mod filter;
+mod git;
 mod pattern;
 
 pub use context::{ContextFile, ContextFileAccepted};
+pub use custom::{PathFilter, PathFilterAccepted};
 pub use error::E;
 pub use filter::Filter;
 pub(crate) use filter::FilterAccepted;
+pub use git::GitAware;
 pub use pattern::PatternFilter;
 pub(crate) use pattern::PatternFilterAccepted;
+
+use crate::collector::{self, Explanation, SymlinkPolicy};
 #[cfg(feature = "tracking")]
 use std::fmt;
+use std::fs::Metadata;
 use std::path::{Path, PathBuf};
 
 /// Represents an entry with filtering options for file and directory paths. `Entry` provides a powerful
@@ -29,6 +36,9 @@ use std::path::{Path, PathBuf};
 ///   - `Filter::Files("*key_word_in_file_name*")` will be applied to each found file name.
 ///   - `Filter::Folders("*key_word_in_folder_name*")` will be applied to each found folder name.
 ///   - `Filter::Common("*key_word*")` will apply the filter to any target to full path.
+///   - `Filter::LargerThan(..)`, `Filter::SmallerThan(..)`, `Filter::ModifiedAfter(..)`,
+///     `Filter::Hidden` and `Filter::Extension(..)` are predicates on the metadata and the nature of
+///     paths; the metadata of a path is read once for all filters of the entry.
 ///
 /// Excluding filters have higher priority than including filters. If a target matches an excluding filter, the target
 /// will be ignored even if it matches an including filter.
@@ -39,7 +49,7 @@ use std::path::{Path, PathBuf};
 ///   with `PatternFilter`.
 ///
 ///   - `PatternFilter::Ignore("**/*/*.ts")` - ignore all paths (both folders and files) if they match
-///      the glob pattern.
+///     the glob pattern.
 ///   - `PatternFilter::Accept("**/*/*.ts")` - include all paths (both folders and files) if they match
 ///     the glob pattern.
 ///   - `PatternFilter::Cmb(vec![PatternFilter::Ignore("**/*/*.ts"), PatternFilter::Ignore("**/*/*.tjs")])` -
@@ -53,6 +63,12 @@ use std::path::{Path, PathBuf};
 ///
 /// `PatternFilter` filters have higher priority than `Filter`. If at least one `PatternFilter` is defined,
 /// any `Filter` will be ignored.
+///
+/// - Custom filtering with `PathFilter`. Filters defined by the application's logic are added with
+///   `path_filter(..)` and applied in addition to `Filter` and `PatternFilter`.
+///
+/// - Git-aware collecting with `GitAware`. With `git(..)`, the entry skips paths ignored by git or
+///   takes only files tracked by git.
 #[derive(Default, Debug, Clone)]
 pub struct Entry {
     /// The path of the entry.
@@ -64,6 +80,19 @@ pub struct Entry {
     /// A list of patterns for filtering paths.
     pub patterns: Vec<PatternFilterAccepted>,
     pub context: Vec<ContextFileAccepted>,
+    /// A list of custom filters (see `PathFilter`).
+    pub custom: Vec<PathFilterAccepted>,
+    /// The maximum depth of collected files relative to the entry; files directly in the entry's
+    /// folder have a depth of 1. Deeper folders aren't read at all.
+    pub max_depth: Option<usize>,
+    /// The minimum depth of collected files relative to the entry. Files above this depth aren't
+    /// collected, but folders are still read.
+    pub min_depth: Option<usize>,
+    /// If `true`, collecting doesn't cross file system boundaries (mount points): files and
+    /// folders on a device other than the entry's one are skipped.
+    pub one_file_system: bool,
+    /// The way git's view on the entry is considered (see `GitAware`).
+    pub git: Option<GitAware>,
 }
 
 #[cfg(feature = "tracking")]
@@ -93,7 +122,35 @@ impl fmt::Display for Entry {
                 .map(|v| v.to_string())
                 .collect::<Vec<String>>()
                 .join(";")
-        )
+        )?;
+        // Custom filters and limits are added only if they are defined to keep the hash of
+        // previous versions
+        if !self.custom.is_empty() {
+            write!(
+                f,
+                "custom({});",
+                self.custom
+                    .iter()
+                    .map(|v| v.to_string())
+                    .collect::<Vec<String>>()
+                    .join(";")
+            )?;
+        }
+        if self.max_depth.is_some() || self.min_depth.is_some() {
+            write!(
+                f,
+                "depth({}..{});",
+                self.min_depth.map(|v| v.to_string()).unwrap_or_default(),
+                self.max_depth.map(|v| v.to_string()).unwrap_or_default()
+            )?;
+        }
+        if self.one_file_system {
+            write!(f, "one_file_system;")?;
+        }
+        if let Some(git) = self.git.as_ref() {
+            write!(f, "git({git});")?;
+        }
+        Ok(())
     }
 }
 
@@ -187,6 +244,7 @@ impl Entry {
     ///
     /// - The entry path will be accepted only if it is a path to an existing folder; in all other cases, it will
     ///   cause an error.
+    ///   To hash separate files, use `Options::file()` or `Options::files()`.
     pub fn entry<T: AsRef<Path>>(mut self, path: T) -> Result<Self, E> {
         let path = path.as_ref().to_path_buf();
         if !path.is_absolute() {
@@ -295,6 +353,127 @@ impl Entry {
         Ok(self)
     }
 
+    /// Adds a custom filter to the entry. The filter is applied to files and folders in addition
+    /// to other filters; if it rejects a folder, the folder isn't read. A filter with the same
+    /// identity is replaced.
+    ///
+    /// # Parameters
+    ///
+    /// - `identity`: A string, which identifies the filter in the tracking key. It should be
+    ///   changed whenever the logic of the filter is changed.
+    /// - `filter`: The filter (an implementation of `PathFilter` or a closure).
+    ///
+    /// # Returns
+    ///
+    /// - The modified `Entry` instance.
+    pub fn path_filter<S: AsRef<str>, F: PathFilter + 'static>(
+        mut self,
+        identity: S,
+        filter: F,
+    ) -> Self {
+        let accepted = PathFilterAccepted::new(identity, filter);
+        self.custom.retain(|f| f.identity != accepted.identity);
+        self.custom.push(accepted);
+        self
+    }
+
+    /// Sets the maximum depth of collected files relative to the entry. Files directly in the
+    /// entry's folder have a depth of 1, files in its subfolders have a depth of 2, and so on.
+    /// Folders deeper than the limit aren't read at all.
+    ///
+    /// # Parameters
+    ///
+    /// - `depth`: The maximum depth; with 0, nothing is collected.
+    ///
+    /// # Returns
+    ///
+    /// - The modified `Entry` instance.
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Sets the minimum depth of collected files relative to the entry (see `max_depth()`). Files
+    /// above this depth aren't collected, but nested folders are still read.
+    ///
+    /// # Parameters
+    ///
+    /// - `depth`: The minimum depth.
+    ///
+    /// # Returns
+    ///
+    /// - The modified `Entry` instance.
+    pub fn min_depth(mut self, depth: usize) -> Self {
+        self.min_depth = Some(depth);
+        self
+    }
+
+    /// Restricts collecting to the file system of the entry. Mount points (for example, mounted
+    /// caches or network shares) aren't crossed: files and folders on other devices are skipped.
+    /// Devices are compared by `st_dev`; on non-Unix platforms this setting is ignored.
+    ///
+    /// # Parameters
+    ///
+    /// - `value`: `true` to stay on the file system of the entry.
+    ///
+    /// # Returns
+    ///
+    /// - The modified `Entry` instance.
+    pub fn one_file_system(mut self, value: bool) -> Self {
+        self.one_file_system = value;
+        self
+    }
+
+    /// Makes collecting follow the view of git on the entry: paths ignored by git are skipped
+    /// (`GitAware::Ignore`) or only files tracked by git are collected (`GitAware::Tracked`).
+    ///
+    /// # Parameters
+    ///
+    /// - `mode`: The way git's view is considered.
+    ///
+    /// # Returns
+    ///
+    /// - The modified `Entry` instance.
+    ///
+    /// # Errors
+    ///
+    /// Collecting fails if the entry doesn't belong to a git repository or if the index of the
+    /// repository cannot be read.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fshasher::{Entry, GitAware};
+    /// use std::env::temp_dir;
+    ///
+    /// let entry = Entry::from(temp_dir()).unwrap().git(GitAware::Tracked);
+    /// ```
+    pub fn git(mut self, mode: GitAware) -> Self {
+        self.git = Some(mode);
+        self
+    }
+
+    /// Checks whether a path at the given depth relative to the entry satisfies the depth limits.
+    /// A file is accepted if its depth is within the limits; a folder is accepted (to be read) if
+    /// files in it could be within the limits.
+    ///
+    /// # Parameters
+    ///
+    /// - `depth`: The number of components of the path relative to the entry.
+    /// - `is_dir`: `true` if the path is a folder.
+    ///
+    /// # Returns
+    ///
+    /// - `bool`: `true` if the path is accepted, `false` otherwise.
+    pub(crate) fn within_depth(&self, depth: usize, is_dir: bool) -> bool {
+        if is_dir {
+            self.max_depth.map_or(true, |max| depth < max)
+        } else {
+            self.max_depth.map_or(true, |max| depth <= max)
+                && self.min_depth.map_or(true, |min| depth >= min)
+        }
+    }
+
     /// Filters a given path based on the entry's include, exclude, and pattern filters.
     ///
     /// # Parameters
@@ -305,21 +484,176 @@ impl Entry {
     ///
     /// - `bool`: `true` if the path is accepted, `false` otherwise.
     pub fn filtered<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.decide(path).accepted()
+    }
+
+    /// The same as `filtered()`, but with the metadata of the path taken in advance. It allows
+    /// reading the metadata once for the entry and the global filters.
+    ///
+    /// # Parameters
+    ///
+    /// - `path`: The path to filter.
+    /// - `md`: The metadata of the path (symlinks are followed); `None` if it isn't available.
+    ///
+    /// # Returns
+    ///
+    /// - `bool`: `true` if the path is accepted, `false` otherwise.
+    pub(crate) fn filtered_with<P: AsRef<Path>>(&self, path: P, md: Option<&Metadata>) -> bool {
+        self.decide_with(path, md).accepted()
+    }
+
+    /// Explains whether the path would be collected with this entry and which rule decides it.
+    /// The path is checked in the same way as during collecting: each folder on the way from the
+    /// entry to the path should be accepted as well. Symlinks are considered with the default
+    /// `SymlinkPolicy`.
+    ///
+    /// # Parameters
+    ///
+    /// - `path`: The full path to explain.
+    ///
+    /// # Returns
+    ///
+    /// - `Result<Explanation, collector::E>`: The decision with the decisive rule.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a context file or git's files cannot be read.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fshasher::{Entry, Filter};
+    /// use std::env::temp_dir;
+    ///
+    /// let entry = Entry::from(temp_dir())
+    ///     .unwrap()
+    ///     .exclude(Filter::Files("*.log"))
+    ///     .unwrap();
+    /// let explanation = entry.explain(temp_dir().join("a.log")).unwrap();
+    /// println!("{explanation}");
+    /// ```
+    pub fn explain<P: AsRef<Path>>(&self, path: P) -> Result<Explanation, collector::E> {
+        collector::explain(
+            self,
+            &Entry::default(),
+            &SymlinkPolicy::default(),
+            path.as_ref(),
+        )
+    }
+
+    /// Applies the entry's filters to the path and returns the decisive one. `filtered()` is a
+    /// shortcut for `decide(path).accepted()`.
+    ///
+    /// # Parameters
+    ///
+    /// - `path`: The path to filter.
+    ///
+    /// # Returns
+    ///
+    /// - `Decision`: The decision along with the filter, which made it.
+    pub(crate) fn decide<P: AsRef<Path>>(&self, path: P) -> Decision<'_> {
+        // Metadata is read once for all filters, which need it
+        let md = if self.needs_metadata() {
+            path.as_ref().metadata().ok()
+        } else {
+            None
+        };
+        self.decide_with(path, md.as_ref())
+    }
+
+    /// The same as `decide()`, but with the metadata of the path taken in advance.
+    ///
+    /// # Parameters
+    ///
+    /// - `path`: The path to filter.
+    /// - `md`: The metadata of the path (symlinks are followed); `None` if it isn't available.
+    ///
+    /// # Returns
+    ///
+    /// - `Decision`: The decision along with the filter, which made it.
+    pub(crate) fn decide_with<P: AsRef<Path>>(
+        &self,
+        path: P,
+        md: Option<&Metadata>,
+    ) -> Decision<'_> {
+        // Custom filters are skipped if metadata isn't available (for example, for broken symlinks)
+        if let Some(md) = md {
+            if let Some(filter) = self
+                .custom
+                .iter()
+                .find(|filter| !filter.filtered(path.as_ref(), md))
+            {
+                return Decision::Custom(filter);
+            }
+        }
         if !self.patterns.is_empty() {
-            return self.patterns.iter().any(|pattern| pattern.filtered(&path));
-        } else if self
+            return self
+                .patterns
+                .iter()
+                .find(|pattern| pattern.filtered(&path))
+                .map_or(Decision::NoPattern, Decision::Pattern);
+        }
+        if let Some(filter) = self
             .exclude
             .iter()
-            .any(|filter| filter.filtered(&path).unwrap_or_default())
+            .find(|filter| filter.filtered_with(&path, md).unwrap_or_default())
         {
-            return false;
+            return Decision::Excluded(filter);
         }
         if self.include.is_empty() {
-            true
-        } else {
-            self.include
-                .iter()
-                .any(|filter| filter.filtered(&path).unwrap_or(true))
+            return Decision::Default;
+        }
+        let mut decision = Decision::NotIncluded;
+        for filter in self.include.iter() {
+            match filter.filtered_with(&path, md) {
+                Some(true) => return Decision::Included(filter),
+                // A filter, which isn't applicable to the path (like `Filter::Files` to a
+                // folder), doesn't reject it
+                None => decision = Decision::Default,
+                Some(false) => {}
+            }
         }
+        decision
+    }
+
+    /// Checks whether any filter of the entry needs the metadata of paths.
+    ///
+    /// # Returns
+    ///
+    /// - `bool`: `true` if the metadata should be read to filter paths.
+    fn needs_metadata(&self) -> bool {
+        !self.custom.is_empty()
+            || (self.patterns.is_empty()
+                && self
+                    .exclude
+                    .iter()
+                    .chain(self.include.iter())
+                    .any(|filter| !matches!(filter, FilterAccepted::Common(..))))
+    }
+}
+
+/// The decision of `Entry::decide()` along with the filter, which made it.
+#[derive(Debug)]
+pub(crate) enum Decision<'a> {
+    /// Accepted; no filter rejects the path and no including filter is defined (or applicable).
+    Default,
+    /// Accepted by the including filter.
+    Included(&'a FilterAccepted),
+    /// Rejected; the path doesn't match any including filter.
+    NotIncluded,
+    /// Rejected by the excluding filter.
+    Excluded(&'a FilterAccepted),
+    /// Accepted by the pattern filter.
+    Pattern(&'a PatternFilterAccepted),
+    /// Rejected; the path isn't accepted by any pattern filter.
+    NoPattern,
+    /// Rejected by the custom filter.
+    Custom(&'a PathFilterAccepted),
+}
+
+impl Decision<'_> {
+    /// Returns `true` if the path is accepted.
+    pub fn accepted(&self) -> bool {
+        matches!(self, Self::Default | Self::Included(..) | Self::Pattern(..))
     }
 }
@@ -1,8 +1,6 @@
 pub use super::E;
 use glob::Pattern;
-#[cfg(feature = "tracking")]
-use std::fmt;
-use std::path::Path;
+use std::{fmt, path::Path};
 const MAX_DEPTH: usize = 1;
 
 /// Allows applying a glob pattern in a regular way. With `PatternFilter`, a glob pattern will be applied to
@@ -58,7 +56,6 @@ pub enum PatternFilterAccepted {
     Cmb(Vec<PatternFilterAccepted>),
 }
 
-#[cfg(feature = "tracking")]
 impl fmt::Display for PatternFilterAccepted {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
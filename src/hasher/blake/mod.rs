@@ -1,12 +1,17 @@
 mod error;
 
-use super::Hasher;
-use blake3::{Hash, Hasher as BlakeHasher};
+use super::{Hasher, HasherConfig};
+use crate::walker;
+use blake3::Hasher as BlakeHasher;
 use error::E;
 /// Hasher based on `blake3` crate.
+///
+/// With `Hasher::configured()`, it supports keyed mode (`HasherConfig::key`, 32 bytes),
+/// derive-key mode (`HasherConfig::context`) and extended output (`HasherConfig::length`).
 pub struct Blake {
     hasher: BlakeHasher,
-    hash: Option<Hash>,
+    length: Option<usize>,
+    hash: Option<Vec<u8>>,
 }
 
 impl Default for Blake {
@@ -14,6 +19,7 @@ impl Default for Blake {
     fn default() -> Self {
         Blake {
             hasher: BlakeHasher::new(),
+            length: None,
             hash: None,
         }
     }
@@ -24,6 +30,7 @@ impl Blake {
     pub fn new() -> Self {
         Blake {
             hasher: BlakeHasher::new(),
+            length: None,
             hash: None,
         }
     }
@@ -40,6 +47,48 @@ impl Hasher for Blake {
         Self::new()
     }
 
+    /// Creates a new instance of `Blake` hasher in keyed mode (if a key is given), in derive-key
+    /// mode (if a context is given) or in default mode.
+    ///
+    /// # Errors
+    ///
+    /// Returns `E::InvalidHasherConfig` if the key isn't 32 bytes long, if both a key and a context
+    /// are given or if the length of output is zero.
+    fn configured(config: &HasherConfig) -> Result<Self, walker::E>
+    where
+        Self: Sized,
+    {
+        let hasher = match (&config.key, &config.context) {
+            (Some(_), Some(_)) => {
+                return Err(walker::E::InvalidHasherConfig(String::from(
+                    "key and context cannot be used together",
+                )));
+            }
+            (Some(key), None) => {
+                let key: [u8; blake3::KEY_LEN] = key.as_slice().try_into().map_err(|_| {
+                    walker::E::InvalidHasherConfig(format!(
+                        "key should be {} bytes long; given {} bytes",
+                        blake3::KEY_LEN,
+                        key.len()
+                    ))
+                })?;
+                BlakeHasher::new_keyed(&key)
+            }
+            (None, Some(context)) => BlakeHasher::new_derive_key(context),
+            (None, None) => BlakeHasher::new(),
+        };
+        if config.length == Some(0) {
+            return Err(walker::E::InvalidHasherConfig(String::from(
+                "length of output cannot be zero",
+            )));
+        }
+        Ok(Blake {
+            hasher,
+            length: config.length,
+            hash: None,
+        })
+    }
+
     /// Returns the computed hash.
     ///
     /// # Returns
@@ -47,7 +96,7 @@ impl Hasher for Blake {
     /// - `Ok(&[u8])` containing the hash bytes if hashing is finished.
     /// - `Err(E)` if the hash is not yet finalized.
     fn hash(&self) -> Result<&[u8], E> {
-        Ok(self.hash.as_ref().ok_or(E::NotFinished)?.as_bytes())
+        Ok(self.hash.as_ref().ok_or(E::NotFinished)?)
     }
 
     /// Absorbs input data into the hasher.
@@ -72,7 +121,14 @@ impl Hasher for Blake {
     /// - `Ok(())` on success.
     /// - `Err(E)` on error.
     fn finish(&mut self) -> Result<(), E> {
-        self.hash = Some(self.hasher.finalize());
+        self.hash = Some(match self.length {
+            Some(length) => {
+                let mut hash = vec![0; length];
+                self.hasher.finalize_xof().fill(&mut hash);
+                hash
+            }
+            None => self.hasher.finalize().as_bytes().to_vec(),
+        });
         Ok(())
     }
 }
@@ -80,11 +136,22 @@ impl Hasher for Blake {
 #[cfg(test)]
 mod test {
     use crate::{
-        hasher, reader,
+        hasher::{self, HasherConfig},
+        reader,
         test::{usecase::*, utils},
-        ReadingStrategy, E,
+        Options, ReadingStrategy, E,
     };
 
+    fn hash(usecase: &UseCase, config: HasherConfig) -> Result<Vec<u8>, E> {
+        let mut walker = Options::from(&usecase.root)?
+            .hasher_config(config)
+            .walker()?;
+        Ok(walker
+            .collect()?
+            .hash::<hasher::blake::Blake, reader::buffering::Buffering>()?
+            .to_vec())
+    }
+
     #[test]
     fn correction_buffering() -> Result<(), E> {
         let usecase = UseCase::unnamed(2, 2, 2, &[])?;
@@ -148,4 +215,114 @@ mod test {
         usecase.clean()?;
         Ok(())
     }
+
+    #[test]
+    fn keyed() -> Result<(), E> {
+        let usecase = UseCase::unnamed(2, 2, 2, &[])?;
+        let plain = hash(&usecase, HasherConfig::new())?;
+        let keyed = hash(&usecase, HasherConfig::new().key([1u8; 32]))?;
+        assert_ne!(plain, keyed);
+        assert_eq!(keyed, hash(&usecase, HasherConfig::new().key([1u8; 32]))?);
+        assert_ne!(keyed, hash(&usecase, HasherConfig::new().key([2u8; 32]))?);
+        usecase.clean()?;
+        Ok(())
+    }
+
+    #[test]
+    fn derive_key() -> Result<(), E> {
+        let usecase = UseCase::unnamed(2, 2, 2, &[])?;
+        let plain = hash(&usecase, HasherConfig::new())?;
+        let derived = hash(&usecase, HasherConfig::new().context("fshasher test v1"))?;
+        assert_ne!(plain, derived);
+        assert_ne!(
+            derived,
+            hash(&usecase, HasherConfig::new().context("fshasher test v2"))?
+        );
+        usecase.clean()?;
+        Ok(())
+    }
+
+    #[test]
+    fn length() -> Result<(), E> {
+        let usecase = UseCase::unnamed(2, 2, 2, &[])?;
+        assert_eq!(hash(&usecase, HasherConfig::new().length(64))?.len(), 64);
+        assert_eq!(hash(&usecase, HasherConfig::new().length(8))?.len(), 8);
+        usecase.clean()?;
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_config() -> Result<(), E> {
+        let usecase = UseCase::unnamed(2, 2, 2, &[])?;
+        for config in [
+            HasherConfig::new().key([1u8; 16]),
+            HasherConfig::new()
+                .key([1u8; 32])
+                .context("fshasher test v1"),
+            HasherConfig::new().length(0),
+        ] {
+            assert!(matches!(
+                hash(&usecase, config),
+                Err(E::InvalidHasherConfig(_))
+            ));
+        }
+        usecase.clean()?;
+        Ok(())
+    }
+
+    #[test]
+    fn secret_key() -> Result<(), E> {
+        let key = [0xabu8; 32];
+        let config = HasherConfig::new().key(key);
+        let debug = format!("{config:?}");
+        assert!(debug.contains("key: Some(<redacted>)"));
+        assert!(!debug.contains("171"));
+        let opt = Options::new().hasher_config(config.clone());
+        assert!(!format!("{opt:?}").contains("171"));
+        // The fingerprint is neither the key nor the plain hash of the key and its debug output
+        let fingerprint = config.fingerprint();
+        assert_eq!(fingerprint.len(), 16);
+        assert!(!blake3::hash(&key).to_hex().starts_with(&fingerprint));
+        assert!(!blake3::hash(debug.as_bytes())
+            .to_hex()
+            .starts_with(&fingerprint));
+        assert_ne!(
+            fingerprint,
+            HasherConfig::new().key([0xacu8; 32]).fingerprint()
+        );
+        assert_eq!(fingerprint, HasherConfig::new().key(key).fingerprint());
+        Ok(())
+    }
+
+    #[cfg(feature = "tracking")]
+    #[test]
+    fn tracking_key() -> Result<(), E> {
+        let usecase = UseCase::unnamed(1, 1, 0, &[])?;
+        let plain = Options::from(&usecase.root)?.hash();
+        assert_eq!(
+            plain,
+            Options::from(&usecase.root)?
+                .hasher_config(HasherConfig::new())
+                .hash()
+        );
+        let keyed = Options::from(&usecase.root)?
+            .hasher_config(HasherConfig::new().key([0xabu8; 32]))
+            .hash();
+        assert_ne!(plain, keyed);
+        assert!(!String::from_utf8_lossy(&keyed).contains("171"));
+        assert_ne!(
+            keyed,
+            Options::from(&usecase.root)?
+                .hasher_config(HasherConfig::new().key([0xacu8; 32]))
+                .hash()
+        );
+        assert_ne!(
+            plain,
+            Options::from(&usecase.root)?
+                .hasher_config(HasherConfig::new().context("fshasher test"))
+                .hash()
+        );
+        usecase.clean()?;
+        Ok(())
+    }
 }
@@ -0,0 +1,181 @@
+mod error;
+
+use super::Hasher;
+use error::E;
+
+/// Hasher based on `crc32c` crate (CRC-32C, Castagnoli). It's a checksum, which is suitable for
+/// change detection, but not for integrity checks against tampering.
+///
+/// The checksum is stored in big-endian byte order.
+pub struct Crc32c {
+    crc: Option<u32>,
+    hash: Option<Vec<u8>>,
+}
+
+impl Default for Crc32c {
+    /// Creates a default instance of `Crc32c` hasher.
+    fn default() -> Self {
+        Crc32c {
+            crc: Some(0),
+            hash: None,
+        }
+    }
+}
+
+impl Crc32c {
+    /// Creates a new instance of `Crc32c` hasher.
+    pub fn new() -> Self {
+        Crc32c {
+            crc: Some(0),
+            hash: None,
+        }
+    }
+}
+
+impl Hasher for Crc32c {
+    type Error = E;
+
+    /// Creates a new instance of `Crc32c` hasher.
+    fn new() -> Self
+    where
+        Self: Sized,
+    {
+        Self::new()
+    }
+
+    /// Returns the computed hash.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(&[u8])` containing the hash bytes if hashing is finished.
+    /// - `Err(E)` if the hash is not yet finalized.
+    fn hash(&self) -> Result<&[u8], E> {
+        Ok(self.hash.as_ref().ok_or(E::NotFinished)?)
+    }
+
+    /// Absorbs input data into the hasher.
+    ///
+    /// # Parameters
+    ///
+    /// - `data`: A slice of bytes to be hashed.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` on success.
+    /// - `Err(E)` on error.
+    fn absorb(&mut self, data: &[u8]) -> Result<(), E> {
+        if let Some(crc) = self.crc.as_mut() {
+            *crc = crc32c::crc32c_append(*crc, data);
+        }
+        Ok(())
+    }
+
+    /// Finalizes the hash computation and stores the result.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` on success.
+    /// - `Err(E)` on error.
+    fn finish(&mut self) -> Result<(), E> {
+        let Some(crc) = self.crc.take() else {
+            return Err(E::AlreadyFinished);
+        };
+        self.hash = Some(crc.to_be_bytes().to_vec());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        hasher, reader,
+        test::{usecase::*, utils},
+        ReadingStrategy, E,
+    };
+
+    #[test]
+    fn correction_buffering() -> Result<(), E> {
+        let usecase = UseCase::unnamed(2, 2, 2, &[])?;
+        utils::compare_same_dest::<hasher::crc32c::Crc32c, reader::buffering::Buffering>(
+            &usecase, None,
+        )?;
+        usecase.clean()?;
+        Ok(())
+    }
+
+    #[test]
+    fn changes_buffering() -> Result<(), E> {
+        let usecase = UseCase::unnamed(2, 2, 2, &[])?;
+        utils::check_for_changes::<hasher::crc32c::Crc32c, reader::buffering::Buffering>(
+            &usecase, None,
+        )?;
+        usecase.clean()?;
+        Ok(())
+    }
+
+    #[test]
+    fn correction_complete() -> Result<(), E> {
+        let usecase = UseCase::unnamed(2, 2, 2, &[])?;
+        utils::compare_same_dest::<hasher::crc32c::Crc32c, reader::buffering::Buffering>(
+            &usecase,
+            Some(ReadingStrategy::Complete),
+        )?;
+        usecase.clean()?;
+        Ok(())
+    }
+
+    #[test]
+    fn changes_complete() -> Result<(), E> {
+        let usecase = UseCase::unnamed(2, 2, 2, &[])?;
+        utils::check_for_changes::<hasher::crc32c::Crc32c, reader::buffering::Buffering>(
+            &usecase,
+            Some(ReadingStrategy::Complete),
+        )?;
+        usecase.clean()?;
+        Ok(())
+    }
+
+    #[test]
+    fn correction_mapped() -> Result<(), E> {
+        let usecase = UseCase::unnamed(2, 2, 2, &[])?;
+        utils::compare_same_dest::<hasher::crc32c::Crc32c, reader::mapping::Mapping>(
+            &usecase,
+            Some(ReadingStrategy::MemoryMapped),
+        )?;
+        usecase.clean()?;
+        Ok(())
+    }
+
+    #[test]
+    fn changes_mapped() -> Result<(), E> {
+        let usecase = UseCase::unnamed(2, 2, 2, &[])?;
+        utils::check_for_changes::<hasher::crc32c::Crc32c, reader::mapping::Mapping>(
+            &usecase,
+            Some(ReadingStrategy::MemoryMapped),
+        )?;
+        usecase.clean()?;
+        Ok(())
+    }
+
+    #[test]
+    fn known_answers() {
+        use crate::hasher::Hasher;
+
+        // The check value of CRC-32C (Castagnoli) in the big-endian order
+        for (data, expected) in [
+            (b"".as_slice(), "00000000"),
+            (b"123456789".as_slice(), "e3069283"),
+        ] {
+            let mut hasher = hasher::crc32c::Crc32c::new();
+            hasher.absorb(data).unwrap();
+            hasher.finish().unwrap();
+            let hash: String = hasher
+                .hash()
+                .unwrap()
+                .iter()
+                .map(|b| format!("{b:02x}"))
+                .collect();
+            assert_eq!(hash, expected);
+        }
+    }
+}
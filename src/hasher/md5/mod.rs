@@ -0,0 +1,175 @@
+mod error;
+
+use super::Hasher;
+use error::E;
+use md5::{Digest, Md5 as Origin};
+
+/// Hasher based on `md-5` crate.
+pub struct Md5 {
+    hasher: Option<Origin>,
+    hash: Option<Vec<u8>>,
+}
+
+impl Default for Md5 {
+    /// Creates a default instance of `Md5` hasher.
+    fn default() -> Self {
+        Md5 {
+            hasher: Some(Origin::new()),
+            hash: None,
+        }
+    }
+}
+
+impl Md5 {
+    /// Creates a new instance of `Md5` hasher.
+    pub fn new() -> Self {
+        Md5 {
+            hasher: Some(Origin::new()),
+            hash: None,
+        }
+    }
+}
+
+impl Hasher for Md5 {
+    type Error = E;
+
+    /// Creates a new instance of `Md5` hasher.
+    fn new() -> Self
+    where
+        Self: Sized,
+    {
+        Self::new()
+    }
+
+    /// Returns the computed hash.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(&[u8])` containing the hash bytes if hashing is finished.
+    /// - `Err(E)` if the hash is not yet finalized.
+    fn hash(&self) -> Result<&[u8], E> {
+        Ok(self.hash.as_ref().ok_or(E::NotFinished)?)
+    }
+
+    /// Absorbs input data into the hasher.
+    ///
+    /// # Parameters
+    ///
+    /// - `data`: A slice of bytes to be hashed.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` on success.
+    /// - `Err(E)` on error.
+    fn absorb(&mut self, data: &[u8]) -> Result<(), E> {
+        if let Some(h) = self.hasher.as_mut() {
+            h.update(data)
+        }
+        Ok(())
+    }
+
+    /// Finalizes the hash computation and stores the result.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` on success.
+    /// - `Err(E)` on error.
+    fn finish(&mut self) -> Result<(), E> {
+        let Some(hasher) = self.hasher.take() else {
+            return Err(E::AlreadyFinished);
+        };
+        self.hash = Some(hasher.finalize().to_vec());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        hasher, reader,
+        test::{usecase::*, utils},
+        ReadingStrategy, E,
+    };
+
+    #[test]
+    fn correction_buffering() -> Result<(), E> {
+        let usecase = UseCase::unnamed(2, 2, 2, &[])?;
+        utils::compare_same_dest::<hasher::md5::Md5, reader::buffering::Buffering>(&usecase, None)?;
+        usecase.clean()?;
+        Ok(())
+    }
+
+    #[test]
+    fn changes_buffering() -> Result<(), E> {
+        let usecase = UseCase::unnamed(2, 2, 2, &[])?;
+        utils::check_for_changes::<hasher::md5::Md5, reader::buffering::Buffering>(&usecase, None)?;
+        usecase.clean()?;
+        Ok(())
+    }
+
+    #[test]
+    fn correction_complete() -> Result<(), E> {
+        let usecase = UseCase::unnamed(2, 2, 2, &[])?;
+        utils::compare_same_dest::<hasher::md5::Md5, reader::buffering::Buffering>(
+            &usecase,
+            Some(ReadingStrategy::Complete),
+        )?;
+        usecase.clean()?;
+        Ok(())
+    }
+
+    #[test]
+    fn changes_complete() -> Result<(), E> {
+        let usecase = UseCase::unnamed(2, 2, 2, &[])?;
+        utils::check_for_changes::<hasher::md5::Md5, reader::buffering::Buffering>(
+            &usecase,
+            Some(ReadingStrategy::Complete),
+        )?;
+        usecase.clean()?;
+        Ok(())
+    }
+
+    #[test]
+    fn correction_mapped() -> Result<(), E> {
+        let usecase = UseCase::unnamed(2, 2, 2, &[])?;
+        utils::compare_same_dest::<hasher::md5::Md5, reader::mapping::Mapping>(
+            &usecase,
+            Some(ReadingStrategy::MemoryMapped),
+        )?;
+        usecase.clean()?;
+        Ok(())
+    }
+
+    #[test]
+    fn changes_mapped() -> Result<(), E> {
+        let usecase = UseCase::unnamed(2, 2, 2, &[])?;
+        utils::check_for_changes::<hasher::md5::Md5, reader::mapping::Mapping>(
+            &usecase,
+            Some(ReadingStrategy::MemoryMapped),
+        )?;
+        usecase.clean()?;
+        Ok(())
+    }
+
+    #[test]
+    fn known_answers() {
+        use crate::hasher::Hasher;
+
+        // MD5 test vectors (RFC 1321)
+        for (data, expected) in [
+            (b"".as_slice(), "d41d8cd98f00b204e9800998ecf8427e"),
+            (b"abc".as_slice(), "900150983cd24fb0d6963f7d28e17f72"),
+        ] {
+            let mut hasher = hasher::md5::Md5::new();
+            hasher.absorb(data).unwrap();
+            hasher.finish().unwrap();
+            let hash: String = hasher
+                .hash()
+                .unwrap()
+                .iter()
+                .map(|b| format!("{b:02x}"))
+                .collect();
+            assert_eq!(hash, expected);
+        }
+    }
+}
@@ -1,11 +1,138 @@
 pub mod blake;
+#[cfg(feature = "use_crc32c")]
+pub mod crc32c;
+#[cfg(feature = "use_md5")]
+pub mod md5;
+#[cfg(feature = "use_sha1")]
+pub mod sha1;
 #[cfg(feature = "use_sha2")]
 pub mod sha256;
 #[cfg(feature = "use_sha2")]
 pub mod sha512;
+#[cfg(feature = "use_xxh3")]
+pub mod xxh3_128;
+#[cfg(feature = "use_xxh3")]
+pub mod xxh3_64;
 
-use crate::walker;
-use std::error;
+use crate::walker::{self, E};
+use std::{any::type_name, error, fmt};
+
+/// Configuration of a hasher, which is carried by `Options` and used to construct hashers with
+/// `Hasher::configured()`. It allows keyed (salted) and namespaced hashing, so hashes cannot be
+/// reproduced without knowing the configuration.
+///
+/// Which settings are supported depends on the hasher. By default, hashers support only an empty
+/// configuration; `blake::Blake` supports all settings (keyed mode, derive-key mode and extended
+/// output).
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct HasherConfig {
+    /// A secret key for keyed hashing (MAC). For `blake::Blake`, the key should be 32 bytes long.
+    pub key: Option<Vec<u8>>,
+    /// A context string for derive-key mode. It should be hardcoded, globally unique and
+    /// application-specific, for example, "example.com 2024-01-01 artifact signatures v1".
+    pub context: Option<String>,
+    /// The length of the output hash in bytes, if the hasher supports extendable output.
+    pub length: Option<usize>,
+}
+
+impl HasherConfig {
+    /// Creates an empty configuration.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets a secret key for keyed hashing.
+    ///
+    /// # Parameters
+    ///
+    /// - `key`: The key.
+    ///
+    /// # Returns
+    ///
+    /// - A `HasherConfig` instance.
+    pub fn key<K: AsRef<[u8]>>(mut self, key: K) -> Self {
+        self.key = Some(key.as_ref().to_vec());
+        self
+    }
+
+    /// Sets a context string for derive-key mode.
+    ///
+    /// # Parameters
+    ///
+    /// - `context`: The context string.
+    ///
+    /// # Returns
+    ///
+    /// - A `HasherConfig` instance.
+    pub fn context<S: AsRef<str>>(mut self, context: S) -> Self {
+        self.context = Some(context.as_ref().to_owned());
+        self
+    }
+
+    /// Sets the length of the output hash in bytes.
+    ///
+    /// # Parameters
+    ///
+    /// - `length`: The length of the hash.
+    ///
+    /// # Returns
+    ///
+    /// - A `HasherConfig` instance.
+    pub fn length(mut self, length: usize) -> Self {
+        self.length = Some(length);
+        self
+    }
+
+    /// Returns `true` if no settings are defined.
+    pub fn is_empty(&self) -> bool {
+        self.key.is_none() && self.context.is_none() && self.length.is_none()
+    }
+
+    /// Returns a fingerprint of the configuration, which can be used to distinguish hashes made
+    /// with different configurations. The key isn't hashed as data: it's used as the key of a MAC
+    /// over a fixed message, so the fingerprint doesn't contain the key or a plain hash of it.
+    pub(crate) fn fingerprint(&self) -> String {
+        if self.is_empty() {
+            return String::new();
+        }
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(format!("{:?}:{:?}", self.context, self.length).as_bytes());
+        if let Some(key) = self.key.as_ref() {
+            // Keys of other lengths are rejected by hashers, but the fingerprint shouldn't depend
+            // on it
+            let key: [u8; blake3::KEY_LEN] = key.as_slice().try_into().unwrap_or_else(|_| {
+                blake3::derive_key("fshasher hasher config fingerprint v1", key)
+            });
+            hasher.update(blake3::keyed_hash(&key, b"fshasher-cache").as_bytes());
+        }
+        hasher
+            .finalize()
+            .to_hex()
+            .as_str()
+            .chars()
+            .take(16)
+            .collect()
+    }
+}
+
+impl fmt::Debug for HasherConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HasherConfig")
+            .field("key", &self.key.as_ref().map(|_| Redacted))
+            .field("context", &self.context)
+            .field("length", &self.length)
+            .finish()
+    }
+}
+
+/// Placeholder of the secret key in the output of `Debug` for `HasherConfig`.
+struct Redacted;
+
+impl fmt::Debug for Redacted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<redacted>")
+    }
+}
 
 /// A trait that defines the behavior of a hasher, which is used to process and compute hashes.
 /// Implementers of this trait must also implement `Send` and `Sync`.
@@ -34,6 +161,31 @@ pub trait Hasher: Send + Sync {
     where
         Self: Sized;
 
+    /// Creates an instance of the hasher with the given configuration. `Walker` uses this method
+    /// instead of `new()` to create a hasher for each file and for the summary hash.
+    ///
+    /// The default implementation accepts only an empty configuration; hashers, which support
+    /// keyed hashing, derive-key mode or extended output, should override it.
+    ///
+    /// # Parameters
+    ///
+    /// - `config`: The configuration of the hasher.
+    ///
+    /// # Returns
+    ///
+    /// - `Result<Self, E>`: An instance of the hasher or `E::UnsupportedHasherConfig` if the
+    ///   configuration isn't supported by the hasher.
+    fn configured(config: &HasherConfig) -> Result<Self, E>
+    where
+        Self: Sized,
+    {
+        if config.is_empty() {
+            Ok(Self::new())
+        } else {
+            Err(E::UnsupportedHasherConfig(type_name::<Self>().to_owned()))
+        }
+    }
+
     /// Absorbs data into the hasher. This method processes the input data and updates the hasher
     /// state. This method might be called multiple times during the reading of a file.
     ///
@@ -0,0 +1,182 @@
+mod error;
+
+use super::Hasher;
+use error::E;
+use sha1::{Digest, Sha1 as Origin};
+
+/// Hasher based on `sha1` crate.
+pub struct Sha1 {
+    hasher: Option<Origin>,
+    hash: Option<Vec<u8>>,
+}
+
+impl Default for Sha1 {
+    /// Creates a default instance of `Sha1` hasher.
+    fn default() -> Self {
+        Sha1 {
+            hasher: Some(Origin::new()),
+            hash: None,
+        }
+    }
+}
+
+impl Sha1 {
+    /// Creates a new instance of `Sha1` hasher.
+    pub fn new() -> Self {
+        Sha1 {
+            hasher: Some(Origin::new()),
+            hash: None,
+        }
+    }
+}
+
+impl Hasher for Sha1 {
+    type Error = E;
+
+    /// Creates a new instance of `Sha1` hasher.
+    fn new() -> Self
+    where
+        Self: Sized,
+    {
+        Self::new()
+    }
+
+    /// Returns the computed hash.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(&[u8])` containing the hash bytes if hashing is finished.
+    /// - `Err(E)` if the hash is not yet finalized.
+    fn hash(&self) -> Result<&[u8], E> {
+        Ok(self.hash.as_ref().ok_or(E::NotFinished)?)
+    }
+
+    /// Absorbs input data into the hasher.
+    ///
+    /// # Parameters
+    ///
+    /// - `data`: A slice of bytes to be hashed.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` on success.
+    /// - `Err(E)` on error.
+    fn absorb(&mut self, data: &[u8]) -> Result<(), E> {
+        if let Some(h) = self.hasher.as_mut() {
+            h.update(data)
+        }
+        Ok(())
+    }
+
+    /// Finalizes the hash computation and stores the result.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` on success.
+    /// - `Err(E)` on error.
+    fn finish(&mut self) -> Result<(), E> {
+        let Some(hasher) = self.hasher.take() else {
+            return Err(E::AlreadyFinished);
+        };
+        self.hash = Some(hasher.finalize().to_vec());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        hasher, reader,
+        test::{usecase::*, utils},
+        ReadingStrategy, E,
+    };
+
+    #[test]
+    fn correction_buffering() -> Result<(), E> {
+        let usecase = UseCase::unnamed(2, 2, 2, &[])?;
+        utils::compare_same_dest::<hasher::sha1::Sha1, reader::buffering::Buffering>(
+            &usecase, None,
+        )?;
+        usecase.clean()?;
+        Ok(())
+    }
+
+    #[test]
+    fn changes_buffering() -> Result<(), E> {
+        let usecase = UseCase::unnamed(2, 2, 2, &[])?;
+        utils::check_for_changes::<hasher::sha1::Sha1, reader::buffering::Buffering>(
+            &usecase, None,
+        )?;
+        usecase.clean()?;
+        Ok(())
+    }
+
+    #[test]
+    fn correction_complete() -> Result<(), E> {
+        let usecase = UseCase::unnamed(2, 2, 2, &[])?;
+        utils::compare_same_dest::<hasher::sha1::Sha1, reader::buffering::Buffering>(
+            &usecase,
+            Some(ReadingStrategy::Complete),
+        )?;
+        usecase.clean()?;
+        Ok(())
+    }
+
+    #[test]
+    fn changes_complete() -> Result<(), E> {
+        let usecase = UseCase::unnamed(2, 2, 2, &[])?;
+        utils::check_for_changes::<hasher::sha1::Sha1, reader::buffering::Buffering>(
+            &usecase,
+            Some(ReadingStrategy::Complete),
+        )?;
+        usecase.clean()?;
+        Ok(())
+    }
+
+    #[test]
+    fn correction_mapped() -> Result<(), E> {
+        let usecase = UseCase::unnamed(2, 2, 2, &[])?;
+        utils::compare_same_dest::<hasher::sha1::Sha1, reader::mapping::Mapping>(
+            &usecase,
+            Some(ReadingStrategy::MemoryMapped),
+        )?;
+        usecase.clean()?;
+        Ok(())
+    }
+
+    #[test]
+    fn changes_mapped() -> Result<(), E> {
+        let usecase = UseCase::unnamed(2, 2, 2, &[])?;
+        utils::check_for_changes::<hasher::sha1::Sha1, reader::mapping::Mapping>(
+            &usecase,
+            Some(ReadingStrategy::MemoryMapped),
+        )?;
+        usecase.clean()?;
+        Ok(())
+    }
+
+    #[test]
+    fn known_answers() {
+        use crate::hasher::Hasher;
+
+        // SHA-1 test vectors (FIPS 180)
+        for (data, expected) in [
+            (b"".as_slice(), "da39a3ee5e6b4b0d3255bfef95601890afd80709"),
+            (
+                b"abc".as_slice(),
+                "a9993e364706816aba3e25717850c26c9cd0d89d",
+            ),
+        ] {
+            let mut hasher = hasher::sha1::Sha1::new();
+            hasher.absorb(data).unwrap();
+            hasher.finish().unwrap();
+            let hash: String = hasher
+                .hash()
+                .unwrap()
+                .iter()
+                .map(|b| format!("{b:02x}"))
+                .collect();
+            assert_eq!(hash, expected);
+        }
+    }
+}
@@ -86,9 +86,10 @@ impl Hasher for Sha256 {
 #[cfg(test)]
 mod test {
     use crate::{
-        hasher, reader,
+        hasher::{self, HasherConfig},
+        reader,
         test::{usecase::*, utils},
-        ReadingStrategy, E,
+        Options, ReadingStrategy, E,
     };
 
     #[test]
@@ -154,4 +155,20 @@ mod test {
         usecase.clean()?;
         Ok(())
     }
+
+    #[test]
+    fn unsupported_config() -> Result<(), E> {
+        let usecase = UseCase::unnamed(2, 2, 2, &[])?;
+        let mut walker = Options::from(&usecase.root)?
+            .hasher_config(HasherConfig::new().key([1u8; 32]))
+            .walker()?;
+        assert!(matches!(
+            walker
+                .collect()?
+                .hash::<hasher::sha256::Sha256, reader::buffering::Buffering>(),
+            Err(E::UnsupportedHasherConfig(_))
+        ));
+        usecase.clean()?;
+        Ok(())
+    }
 }
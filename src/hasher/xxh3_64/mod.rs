@@ -0,0 +1,182 @@
+mod error;
+
+use super::Hasher;
+use error::E;
+use xxhash_rust::xxh3::Xxh3 as Origin;
+
+/// Hasher based on `xxhash-rust` crate (XXH3, 64-bit). It's a fast non-cryptographic hash,
+/// which is suitable for change detection, but not for integrity checks against tampering.
+///
+/// The hash is stored in big-endian byte order (the same as `xxhsum` prints it).
+pub struct Xxh3_64 {
+    hasher: Option<Origin>,
+    hash: Option<Vec<u8>>,
+}
+
+impl Default for Xxh3_64 {
+    /// Creates a default instance of `Xxh3_64` hasher.
+    fn default() -> Self {
+        Xxh3_64 {
+            hasher: Some(Origin::new()),
+            hash: None,
+        }
+    }
+}
+
+impl Xxh3_64 {
+    /// Creates a new instance of `Xxh3_64` hasher.
+    pub fn new() -> Self {
+        Xxh3_64 {
+            hasher: Some(Origin::new()),
+            hash: None,
+        }
+    }
+}
+
+impl Hasher for Xxh3_64 {
+    type Error = E;
+
+    /// Creates a new instance of `Xxh3_64` hasher.
+    fn new() -> Self
+    where
+        Self: Sized,
+    {
+        Self::new()
+    }
+
+    /// Returns the computed hash.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(&[u8])` containing the hash bytes if hashing is finished.
+    /// - `Err(E)` if the hash is not yet finalized.
+    fn hash(&self) -> Result<&[u8], E> {
+        Ok(self.hash.as_ref().ok_or(E::NotFinished)?)
+    }
+
+    /// Absorbs input data into the hasher.
+    ///
+    /// # Parameters
+    ///
+    /// - `data`: A slice of bytes to be hashed.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` on success.
+    /// - `Err(E)` on error.
+    fn absorb(&mut self, data: &[u8]) -> Result<(), E> {
+        if let Some(h) = self.hasher.as_mut() {
+            h.update(data)
+        }
+        Ok(())
+    }
+
+    /// Finalizes the hash computation and stores the result.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` on success.
+    /// - `Err(E)` on error.
+    fn finish(&mut self) -> Result<(), E> {
+        let Some(hasher) = self.hasher.take() else {
+            return Err(E::AlreadyFinished);
+        };
+        self.hash = Some(hasher.digest().to_be_bytes().to_vec());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        hasher, reader,
+        test::{usecase::*, utils},
+        ReadingStrategy, E,
+    };
+
+    #[test]
+    fn correction_buffering() -> Result<(), E> {
+        let usecase = UseCase::unnamed(2, 2, 2, &[])?;
+        utils::compare_same_dest::<hasher::xxh3_64::Xxh3_64, reader::buffering::Buffering>(
+            &usecase, None,
+        )?;
+        usecase.clean()?;
+        Ok(())
+    }
+
+    #[test]
+    fn changes_buffering() -> Result<(), E> {
+        let usecase = UseCase::unnamed(2, 2, 2, &[])?;
+        utils::check_for_changes::<hasher::xxh3_64::Xxh3_64, reader::buffering::Buffering>(
+            &usecase, None,
+        )?;
+        usecase.clean()?;
+        Ok(())
+    }
+
+    #[test]
+    fn correction_complete() -> Result<(), E> {
+        let usecase = UseCase::unnamed(2, 2, 2, &[])?;
+        utils::compare_same_dest::<hasher::xxh3_64::Xxh3_64, reader::buffering::Buffering>(
+            &usecase,
+            Some(ReadingStrategy::Complete),
+        )?;
+        usecase.clean()?;
+        Ok(())
+    }
+
+    #[test]
+    fn changes_complete() -> Result<(), E> {
+        let usecase = UseCase::unnamed(2, 2, 2, &[])?;
+        utils::check_for_changes::<hasher::xxh3_64::Xxh3_64, reader::buffering::Buffering>(
+            &usecase,
+            Some(ReadingStrategy::Complete),
+        )?;
+        usecase.clean()?;
+        Ok(())
+    }
+
+    #[test]
+    fn correction_mapped() -> Result<(), E> {
+        let usecase = UseCase::unnamed(2, 2, 2, &[])?;
+        utils::compare_same_dest::<hasher::xxh3_64::Xxh3_64, reader::mapping::Mapping>(
+            &usecase,
+            Some(ReadingStrategy::MemoryMapped),
+        )?;
+        usecase.clean()?;
+        Ok(())
+    }
+
+    #[test]
+    fn changes_mapped() -> Result<(), E> {
+        let usecase = UseCase::unnamed(2, 2, 2, &[])?;
+        utils::check_for_changes::<hasher::xxh3_64::Xxh3_64, reader::mapping::Mapping>(
+            &usecase,
+            Some(ReadingStrategy::MemoryMapped),
+        )?;
+        usecase.clean()?;
+        Ok(())
+    }
+
+    #[test]
+    fn known_answers() {
+        use crate::hasher::Hasher;
+
+        // Outputs of `xxhsum -H3` in the big-endian (canonical) order
+        for (data, expected) in [
+            (b"".as_slice(), "2d06800538d394c2"),
+            (b"abc".as_slice(), "78af5f94892f3950"),
+        ] {
+            let mut hasher = hasher::xxh3_64::Xxh3_64::new();
+            hasher.absorb(data).unwrap();
+            hasher.finish().unwrap();
+            let hash: String = hasher
+                .hash()
+                .unwrap()
+                .iter()
+                .map(|b| format!("{b:02x}"))
+                .collect();
+            assert_eq!(hash, expected);
+        }
+    }
+}
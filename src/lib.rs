@@ -10,10 +10,15 @@ pub(crate) mod test;
 pub mod walker;
 
 pub use breaker::Breaker;
-pub use collector::{collect, Tolerance};
-pub use entry::{ContextFile, Entry, Filter, PatternFilter};
+pub use collector::{collect, Explanation, Reason, SymlinkPolicy, Tolerance};
+pub use entry::{ContextFile, Entry, Filter, GitAware, PathFilter, PatternFilter};
 pub use hasher::Hasher;
 pub use reader::Reader;
+#[cfg(feature = "tokio")]
+pub use walker::AsyncWalker;
 #[cfg(feature = "tracking")]
 pub use walker::Tracking;
-pub use walker::{JobType, Options, Progress, ReadingStrategy, Tick, Walker, E};
+pub use walker::{
+    Changes, HasherKind, JobType, MetaField, Options, Progress, ReaderKind, ReadingStrategy,
+    SummaryMode, Tick, Walker, E,
+};
@@ -76,19 +76,16 @@ impl Read for Md {
                 .modified()?
                 .duration_since(UNIX_EPOCH)
                 .map(|d| d.as_nanos())
-                .map_err(|err| {
-                    io::Error::new(io::ErrorKind::Other, format!("SystemTimeError: {err}"))
-                })?;
+                .map_err(|err| io::Error::other(format!("SystemTimeError: {err}")))?;
             let as_bytes = [
                 modified.to_be_bytes().as_ref(),
                 md.len().to_be_bytes().as_ref(),
             ]
             .concat();
             if as_bytes.len() > buffer.len() {
-                Err(io::Error::new(
-                    io::ErrorKind::Other,
-                    String::from("Md reader needs at least 255 bytes buffer"),
-                ))
+                Err(io::Error::other(String::from(
+                    "Md reader needs at least 255 bytes buffer",
+                )))
             } else {
                 buffer[..as_bytes.len()].copy_from_slice(&as_bytes);
                 Ok(as_bytes.len())
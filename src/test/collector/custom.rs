@@ -0,0 +1,113 @@
+use crate::*;
+use std::{
+    fs::Metadata,
+    path::{Path, PathBuf},
+};
+use test::usecase::*;
+
+fn collect_sorted(entry: &Entry) -> Result<Vec<PathBuf>, E> {
+    let (mut collected, _) =
+        collector::collect(&None, entry, &Breaker::new(), &Tolerance::LogErrors, &None)?;
+    collected.sort();
+    Ok(collected)
+}
+
+struct Registry {
+    generated: Vec<PathBuf>,
+}
+
+impl PathFilter for Registry {
+    fn accept(&self, path: &Path, _md: &Metadata) -> bool {
+        !self.generated.iter().any(|p| p == path)
+    }
+}
+
+#[test]
+fn trait_impl() -> Result<(), E> {
+    let usecase = UseCase::unnamed(2, 4, 2, &[])?;
+    let generated: Vec<PathBuf> = usecase.files.iter().step_by(3).cloned().collect();
+    let entry = Entry::from(&usecase.root)?.path_filter(
+        "registry",
+        Registry {
+            generated: generated.clone(),
+        },
+    );
+    let collected = collect_sorted(&entry)?;
+    assert!(!collected.is_empty());
+    assert_eq!(collected.len(), usecase.files.len() - generated.len());
+    assert!(!collected.iter().any(|p| generated.contains(p)));
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn closure_on_folders() -> Result<(), E> {
+    let usecase = UseCase::folders(&["keep", "skip"], 3, 1, &[])?;
+    let entry = Entry::from(&usecase.root)?.path_filter("no-skip", |path: &Path, md: &Metadata| {
+        !(md.is_dir() && path.file_name().is_some_and(|name| name == "skip"))
+    });
+    let collected = collect_sorted(&entry)?;
+    let mut expected: Vec<PathBuf> = usecase
+        .files
+        .iter()
+        .filter(|p| {
+            !p.strip_prefix(&usecase.root)
+                .unwrap()
+                .components()
+                .any(|c| c.as_os_str() == "skip")
+        })
+        .cloned()
+        .collect();
+    expected.sort();
+    assert!(!expected.is_empty());
+    assert_eq!(collected, expected);
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn global() -> Result<(), E> {
+    let usecase = UseCase::unnamed(2, 4, 2, &[])?;
+    let mut walker = Options::from(&usecase.root)?
+        .path_filter("nothing", |_: &Path, md: &Metadata| md.is_dir())
+        .walker()?;
+    walker.collect()?;
+    assert!(walker.paths.is_empty());
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn same_identity_replaced() -> Result<(), E> {
+    let usecase = UseCase::unnamed(2, 4, 2, &[])?;
+    let entry = Entry::from(&usecase.root)?
+        .path_filter("filter", |_: &Path, md: &Metadata| md.is_dir())
+        .path_filter("filter", |_: &Path, _: &Metadata| true);
+    assert_eq!(entry.custom.len(), 1);
+    assert_eq!(collect_sorted(&entry)?.len(), usecase.files.len());
+    usecase.clean()?;
+    Ok(())
+}
+
+#[cfg(feature = "tracking")]
+#[test]
+fn tracking_key() -> Result<(), E> {
+    let usecase = UseCase::unnamed(1, 1, 0, &[])?;
+    let plain = Options::from(&usecase.root)?.hash();
+    let a = Options::from(&usecase.root)?
+        .path_filter("a", |_: &Path, _: &Metadata| true)
+        .hash();
+    let b = Options::from(&usecase.root)?
+        .path_filter("b", |_: &Path, _: &Metadata| true)
+        .hash();
+    assert_ne!(plain, a);
+    assert_ne!(a, b);
+    assert_eq!(
+        a,
+        Options::from(&usecase.root)?
+            .path_filter("a", |_: &Path, _: &Metadata| false)
+            .hash()
+    );
+    usecase.clean()?;
+    Ok(())
+}
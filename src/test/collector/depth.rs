@@ -0,0 +1,86 @@
+use crate::test::usecase::*;
+use crate::*;
+use std::path::{Path, PathBuf};
+
+fn depth(root: &Path, path: &Path) -> usize {
+    path.strip_prefix(root)
+        .map(|rel| rel.components().count())
+        .unwrap_or_default()
+}
+
+fn collect_sorted(entry: &Entry) -> Result<Vec<PathBuf>, E> {
+    let (mut collected, _) =
+        collector::collect(&None, entry, &Breaker::new(), &Tolerance::LogErrors, &None)?;
+    collected.sort();
+    Ok(collected)
+}
+
+fn expected<F: Fn(usize) -> bool>(usecase: &UseCase, check: F) -> Vec<PathBuf> {
+    let mut expected: Vec<PathBuf> = usecase
+        .files
+        .iter()
+        .filter(|p| check(depth(&usecase.root, p)))
+        .cloned()
+        .collect();
+    expected.sort();
+    expected
+}
+
+#[test]
+fn max_depth() -> Result<(), E> {
+    let usecase = UseCase::unnamed(2, 3, 3, &[])?;
+    for max in 0..6 {
+        let collected = collect_sorted(&Entry::from(&usecase.root)?.max_depth(max))?;
+        assert_eq!(collected, expected(&usecase, |d| d <= max));
+    }
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn min_depth() -> Result<(), E> {
+    let usecase = UseCase::unnamed(2, 3, 3, &[])?;
+    for min in 0..6 {
+        let collected = collect_sorted(&Entry::from(&usecase.root)?.min_depth(min))?;
+        assert_eq!(collected, expected(&usecase, |d| d >= min));
+    }
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn depth_range() -> Result<(), E> {
+    let usecase = UseCase::unnamed(2, 3, 3, &[])?;
+    let collected = collect_sorted(&Entry::from(&usecase.root)?.min_depth(3).max_depth(3))?;
+    assert!(!collected.is_empty());
+    assert_eq!(collected, expected(&usecase, |d| d == 3));
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn global_max_depth() -> Result<(), E> {
+    let usecase = UseCase::unnamed(2, 3, 3, &[])?;
+    let mut walker = Options::from(&usecase.root)?.walker()?;
+    walker.collect()?;
+    assert_eq!(walker.paths.len(), usecase.files.len());
+    let mut opt = Options::from(&usecase.root)?;
+    opt.global = Entry::new().max_depth(2);
+    let mut walker = opt.walker()?;
+    walker.collect()?;
+    let mut collected: Vec<PathBuf> = walker.paths.iter().map(|(p, _)| p.to_owned()).collect();
+    collected.sort();
+    assert_eq!(collected, expected(&usecase, |d| d <= 2));
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn one_file_system() -> Result<(), E> {
+    let usecase = UseCase::unnamed(2, 3, 3, &[])?;
+    // All files are on the same file system with the entry
+    let collected = collect_sorted(&Entry::from(&usecase.root)?.one_file_system(true))?;
+    assert_eq!(collected, expected(&usecase, |_| true));
+    usecase.clean()?;
+    Ok(())
+}
@@ -0,0 +1,242 @@
+use crate::*;
+use std::{
+    fs::{create_dir_all, write, Metadata},
+    path::Path,
+};
+use test::usecase::*;
+
+fn create<P: AsRef<Path>>(root: P, rel: &str, content: &str) -> Result<(), E> {
+    let path = root.as_ref().join(rel);
+    if let Some(parent) = path.parent() {
+        create_dir_all(parent)?;
+    }
+    write(path, content)?;
+    Ok(())
+}
+
+#[test]
+fn filters() -> Result<(), E> {
+    let usecase = UseCaseEmpty::gen()?;
+    let root = &usecase.root;
+    for file in ["a.rs", "b.txt", "target/c.rs", "src/d.rs", "src/e.lock"] {
+        create(root, file, file)?;
+    }
+    let entry = Entry::from(root)?
+        .include(Filter::Files("*.rs"))?
+        .exclude(Filter::Folders("*/target"))?
+        .path_filter("no-locks", |path: &Path, _md: &Metadata| {
+            path.extension().map_or(true, |ext| ext != "lock")
+        });
+    let explanation = entry.explain(root.join("a.rs"))?;
+    assert!(explanation.accepted);
+    assert_eq!(explanation.reason, Reason::Include(String::from("*.rs")));
+    let explanation = entry.explain(root.join("b.txt"))?;
+    assert!(!explanation.accepted);
+    assert_eq!(explanation.reason, Reason::NotIncluded);
+    // The decision is made for the parent folder
+    let explanation = entry.explain(root.join("target").join("c.rs"))?;
+    assert!(!explanation.accepted);
+    assert_eq!(explanation.subject, root.join("target"));
+    assert_eq!(
+        explanation.reason,
+        Reason::Exclude(String::from("*/target"))
+    );
+    assert!(entry.explain(root.join("src").join("d.rs"))?.accepted);
+    assert_eq!(
+        entry.explain(root.join("src").join("e.lock"))?.reason,
+        Reason::Custom(String::from("no-locks"))
+    );
+    let entry = Entry::from(root)?.pattern(PatternFilter::Accept("*.txt"))?;
+    let explanation = entry.explain(root.join("b.txt"))?;
+    assert!(explanation.accepted);
+    assert_eq!(explanation.reason, Reason::Pattern(String::from("*.txt")));
+    assert_eq!(entry.explain(root.join("a.rs"))?.reason, Reason::NoPattern);
+    let entry = Entry::from(root)?.max_depth(1);
+    assert_eq!(
+        entry.explain(root.join("src").join("d.rs"))?.reason,
+        Reason::Depth
+    );
+    assert_eq!(entry.explain(root.join("a.rs"))?.reason, Reason::Default);
+    assert_eq!(
+        entry.explain(root.join("missing.rs"))?.reason,
+        Reason::Unavailable
+    );
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn context() -> Result<(), E> {
+    let usecase = UseCaseEmpty::gen()?;
+    let root = &usecase.root;
+    create(root, ".ignore", "# Comment\n*.log\n\nbuild/\n")?;
+    create(root, "sub/.ignore", "!keep.log\n")?;
+    for file in ["a.log", "build/b.txt", "sub/keep.log", "sub/c.txt"] {
+        create(root, file, file)?;
+    }
+    let entry = Entry::from(root)?.context(ContextFile::Ignore(".ignore"));
+    let explanation = entry.explain(root.join("a.log"))?;
+    assert!(!explanation.accepted);
+    assert_eq!(
+        explanation.reason,
+        Reason::Context {
+            file: root.join(".ignore"),
+            line: 2,
+            rule: String::from("*.log"),
+        }
+    );
+    let explanation = entry.explain(root.join("build").join("b.txt"))?;
+    assert!(!explanation.accepted);
+    assert_eq!(explanation.subject, root.join("build"));
+    assert_eq!(
+        explanation.reason,
+        Reason::Context {
+            file: root.join(".ignore"),
+            line: 4,
+            rule: String::from("build/"),
+        }
+    );
+    // Re-included by a negative rule
+    let explanation = entry.explain(root.join("sub").join("keep.log"))?;
+    assert!(explanation.accepted);
+    assert_eq!(
+        explanation.reason,
+        Reason::Context {
+            file: root.join("sub").join(".ignore"),
+            line: 1,
+            rule: String::from("!keep.log"),
+        }
+    );
+    assert_eq!(
+        entry.explain(root.join("sub").join("c.txt"))?.reason,
+        Reason::Default
+    );
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn walker() -> Result<(), E> {
+    let usecase = UseCaseEmpty::gen()?;
+    let other = UseCaseEmpty::gen()?;
+    let root = &usecase.root;
+    create(root, ".ignore", "*.log\n")?;
+    for file in [
+        "a.txt",
+        "b.log",
+        "c.tmp",
+        "gen/d.txt",
+        "gen/e.txt",
+        "sub/f.txt",
+    ] {
+        create(root, file, file)?;
+    }
+    create(&other.root, "g.log", "g")?;
+    let mut walker = Options::new()
+        .entry(
+            Entry::from(root)?
+                .context(ContextFile::Ignore(".ignore"))
+                .exclude(Filter::Folders("*/gen"))?,
+        )?
+        .exclude(Filter::Files("*.tmp"))?
+        .file(other.root.join("g.log"))?
+        .record_skipped(true)
+        .walker()?;
+    walker.collect()?;
+    let skipped: Vec<(&Path, &Reason, bool)> = walker
+        .skipped
+        .iter()
+        .map(|ex| (ex.path.strip_prefix(root).unwrap(), &ex.reason, ex.global))
+        .collect();
+    assert_eq!(
+        skipped,
+        vec![
+            (
+                Path::new("b.log"),
+                &Reason::Context {
+                    file: root.join(".ignore"),
+                    line: 1,
+                    rule: String::from("*.log"),
+                },
+                false
+            ),
+            (
+                Path::new("c.tmp"),
+                &Reason::Exclude(String::from("*.tmp")),
+                true
+            ),
+            // The content of the skipped folder isn't listed
+            (
+                Path::new("gen"),
+                &Reason::Exclude(String::from("*/gen")),
+                false
+            ),
+        ]
+    );
+    // Explanations match the results of collecting
+    for (path, _) in walker.iter() {
+        assert!(walker.explain(path)?.accepted);
+    }
+    for explanation in walker.skipped.iter() {
+        assert_eq!(&walker.explain(&explanation.path)?, explanation);
+    }
+    let explanation = walker.explain(other.root.join("g.log"))?;
+    assert!(explanation.accepted);
+    assert_eq!(explanation.reason, Reason::File);
+    let explanation = walker.explain(other.root.join("h.txt"))?;
+    assert!(!explanation.accepted);
+    assert_eq!(explanation.reason, Reason::OutsideEntry);
+    // Skipped paths aren't recorded by default
+    let mut walker = Options::new().entry(Entry::from(root)?)?.walker()?;
+    walker.collect()?;
+    assert!(walker.skipped.is_empty());
+    usecase.clean()?;
+    other.clean()?;
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn symlinks() -> Result<(), E> {
+    use std::os::unix::fs::symlink;
+    let usecase = UseCaseEmpty::gen()?;
+    let root = &usecase.root;
+    create(root, "sub/a.txt", "a")?;
+    symlink(root.join("sub"), root.join("link"))?;
+    symlink(root, root.join("sub").join("loop"))?;
+    let skipped = |policy: SymlinkPolicy| -> Result<Vec<(String, Reason)>, E> {
+        let mut walker = Options::new()
+            .entry(Entry::from(root)?)?
+            .symlinks(policy)
+            .record_skipped(true)
+            .walker()?;
+        walker.collect()?;
+        Ok(walker
+            .skipped
+            .iter()
+            .map(|ex| {
+                (
+                    ex.path.strip_prefix(root).unwrap().display().to_string(),
+                    ex.reason.clone(),
+                )
+            })
+            .collect())
+    };
+    assert_eq!(
+        skipped(SymlinkPolicy::Follow)?,
+        vec![
+            (String::from("link/loop"), Reason::SymlinkLoop),
+            (String::from("sub/loop"), Reason::SymlinkLoop)
+        ]
+    );
+    assert_eq!(
+        skipped(SymlinkPolicy::Skip)?,
+        vec![
+            (String::from("link"), Reason::Symlink),
+            (String::from("sub/loop"), Reason::Symlink)
+        ]
+    );
+    assert!(skipped(SymlinkPolicy::HashTarget)?.is_empty());
+    usecase.clean()?;
+    Ok(())
+}
@@ -376,3 +376,139 @@ fn patterns_cmb() -> Result<(), E> {
     usecase.clean()?;
     Ok(())
 }
+
+#[test]
+fn global_exclude() -> Result<(), E> {
+    let usecase = UseCase::folders(
+        &["aaa", "bbb", "exclude_ccc", "ddd_exclude"],
+        9,
+        2,
+        &["aaa", "bbb", "ccc"],
+    )?;
+    let breaker = Breaker::new();
+    let entry = Entry::from(&usecase.root)?;
+    let global = Entry::new()
+        .exclude(Filter::Folders("*exclude*"))
+        .expect("filter is set");
+    let a = collector::collect_entry(
+        &None,
+        &entry,
+        &global,
+        &breaker,
+        &Tolerance::LogErrors,
+        &None,
+        &SymlinkPolicy::default(),
+    )?;
+    assert!(!a.0.is_empty());
+    assert!(!a.0.iter().any(|p| p.to_string_lossy().contains("exclude")));
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn global_and_entry_excludes_combined() -> Result<(), E> {
+    let usecase = UseCase::unnamed(3, 9, 2, &["aaa", "bbb", "ccc"])?;
+    let breaker = Breaker::new();
+    let entry = Entry::from(&usecase.root)?
+        .exclude(Filter::Files("*.aaa"))
+        .expect("filter is set");
+    let global = Entry::new()
+        .exclude(Filter::Files("*.bbb"))
+        .expect("filter is set");
+    let a = collector::collect_entry(
+        &None,
+        &entry,
+        &global,
+        &breaker,
+        &Tolerance::LogErrors,
+        &None,
+        &SymlinkPolicy::default(),
+    )?;
+    assert!(!a.0.is_empty());
+    assert!(a
+        .0
+        .iter()
+        .all(|p| p.extension().unwrap().to_str().unwrap() == "ccc"));
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn global_and_entry_includes_both_required() -> Result<(), E> {
+    let usecase = UseCase::folders(
+        &["aaa", "bbb", "include_ccc", "ddd_include"],
+        9,
+        2,
+        &["aaa", "bbb", "ccc"],
+    )?;
+    let breaker = Breaker::new();
+    let entry = Entry::from(&usecase.root)?
+        .include(Filter::Folders("*include*"))
+        .expect("filter is set");
+    let global = Entry::new()
+        .include(Filter::Files("*.aaa"))
+        .expect("filter is set");
+    let a = collector::collect_entry(
+        &None,
+        &entry,
+        &global,
+        &breaker,
+        &Tolerance::LogErrors,
+        &None,
+        &SymlinkPolicy::default(),
+    )?;
+    assert!(!a.0.is_empty());
+    assert!(a.0.iter().all(|p| {
+        p.parent().unwrap().to_string_lossy().contains("include")
+            && p.extension().unwrap().to_str().unwrap() == "aaa"
+    }));
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn global_exclude_overrides_entry_include() -> Result<(), E> {
+    let usecase = UseCase::unnamed(3, 9, 2, &["aaa", "bbb", "ccc"])?;
+    let breaker = Breaker::new();
+    let entry = Entry::from(&usecase.root)?
+        .include(Filter::Files("*.aaa"))
+        .expect("filter is set");
+    let global = Entry::new()
+        .exclude(Filter::Files("*.aaa"))
+        .expect("filter is set");
+    let a = collector::collect_entry(
+        &None,
+        &entry,
+        &global,
+        &breaker,
+        &Tolerance::LogErrors,
+        &None,
+        &SymlinkPolicy::default(),
+    )?;
+    assert!(a.0.is_empty());
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn global_filters_in_walker() -> Result<(), E> {
+    let usecase = UseCase::unnamed(3, 9, 2, &["aaa", "bbb", "ccc"])?;
+    let mut walker = Options::from(&usecase.root)?
+        .include(Filter::Files("*.ccc"))?
+        .exclude(Filter::Folders("never_matched"))?
+        .walker()?;
+    walker.collect()?;
+    assert_eq!(
+        walker.count(),
+        usecase
+            .files
+            .iter()
+            .filter(|p| p.extension().unwrap().to_str().unwrap() == "ccc")
+            .count()
+    );
+    assert!(walker
+        .iter()
+        .all(|(p, _)| p.extension().unwrap().to_str().unwrap() == "ccc"));
+    usecase.clean()?;
+    Ok(())
+}
@@ -0,0 +1,238 @@
+use std::{
+    fs::{create_dir_all, write},
+    path::Path,
+};
+
+use crate::{test::usecase::*, Entry, GitAware, Options, E};
+
+pub(crate) const REGULAR: u32 = 0o100644;
+const SYMLINK: u32 = 0o120000;
+const GITLINK: u32 = 0o160000;
+
+fn create<P: AsRef<Path>>(root: P, rel: &str, content: &str) -> Result<(), E> {
+    let path = root.as_ref().join(rel);
+    if let Some(parent) = path.parent() {
+        create_dir_all(parent)?;
+    }
+    write(path, content)?;
+    Ok(())
+}
+
+fn collect(entry: Entry) -> Result<Vec<String>, E> {
+    let root = entry.entry.clone();
+    let mut walker = Options::new().entry(entry)?.walker()?;
+    walker.collect()?;
+    let mut paths: Vec<String> = walker
+        .iter()
+        .map(|(p, _)| {
+            p.strip_prefix(&root)
+                .unwrap()
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().to_string())
+                .collect::<Vec<String>>()
+                .join("/")
+        })
+        .collect();
+    paths.sort();
+    Ok(paths)
+}
+
+/// Encodes a number in the way of git's `encode_varint()`.
+fn varint(mut value: usize) -> Vec<u8> {
+    let mut bytes = vec![(value & 127) as u8];
+    value >>= 7;
+    while value > 0 {
+        value -= 1;
+        bytes.insert(0, 128 | (value & 127) as u8);
+        value >>= 7;
+    }
+    bytes
+}
+
+/// Creates the content of an index file with the given entries (mode, stage, path).
+pub(crate) fn index(version: u32, entries: &[(u32, u16, &str)]) -> Vec<u8> {
+    let mut content = b"DIRC".to_vec();
+    content.extend_from_slice(&version.to_be_bytes());
+    content.extend_from_slice(&(entries.len() as u32).to_be_bytes());
+    let mut previous = "";
+    for (mode, stage, path) in entries.iter() {
+        let start = content.len();
+        // ctime, mtime, dev, ino
+        content.extend_from_slice(&[0; 24]);
+        content.extend_from_slice(&mode.to_be_bytes());
+        // uid, gid, size, object id
+        content.extend_from_slice(&[0; 12 + 20]);
+        let flags = (stage << 12) | path.len().min(0xfff) as u16;
+        content.extend_from_slice(&flags.to_be_bytes());
+        if version == 4 {
+            let common = previous
+                .bytes()
+                .zip(path.bytes())
+                .take_while(|(a, b)| a == b)
+                .count();
+            content.extend_from_slice(&varint(previous.len() - common));
+            content.extend_from_slice(&path.as_bytes()[common..]);
+            content.push(0);
+        } else {
+            content.extend_from_slice(path.as_bytes());
+            let len = (content.len() - start + 8) & !7;
+            content.resize(start + len, 0);
+        }
+        previous = path;
+    }
+    // Checksum
+    content.extend_from_slice(&[0; 20]);
+    content
+}
+
+#[test]
+fn ignore() -> Result<(), E> {
+    let usecase = UseCaseEmpty::gen()?;
+    let global = UseCaseEmpty::gen()?;
+    let root = &usecase.root;
+    create(root, ".git/info/exclude", "*.tmp\n")?;
+    let excludes = global.root.join("ignore");
+    create(&global.root, "ignore", "*.bak\n")?;
+    create(
+        root,
+        ".git/config",
+        &format!("[core]\n\texcludesFile = \"{}\"\n", excludes.display()),
+    )?;
+    create(root, ".gitignore", "sub/gen/\n*.log\n")?;
+    create(root, "sub/.gitignore", "!keep.log\n")?;
+    for file in [
+        "a.txt",
+        "sub/a.txt",
+        "sub/a.log",
+        "sub/keep.log",
+        "sub/b.tmp",
+        "sub/c.bak",
+        "sub/deep/d.txt",
+        "sub/deep/e.tmp",
+        "sub/gen/x.txt",
+    ] {
+        create(root, file, file)?;
+    }
+    // Rules of the parent folder are applied to the nested entry
+    assert_eq!(
+        collect(Entry::from(root.join("sub"))?.git(GitAware::Ignore))?,
+        vec![".gitignore", "a.txt", "deep/d.txt", "keep.log"]
+    );
+    // The git folder isn't collected
+    assert_eq!(
+        collect(Entry::from(root)?.git(GitAware::Ignore))?,
+        vec![
+            ".gitignore",
+            "a.txt",
+            "sub/.gitignore",
+            "sub/a.txt",
+            "sub/deep/d.txt",
+            "sub/keep.log"
+        ]
+    );
+    usecase.clean()?;
+    global.clean()?;
+    Ok(())
+}
+
+#[test]
+fn tracked() -> Result<(), E> {
+    for version in [2, 3, 4] {
+        let usecase = UseCaseEmpty::gen()?;
+        let root = &usecase.root;
+        for file in [
+            "a.txt",
+            "b.txt",
+            "untracked.txt",
+            "src/lib.rs",
+            "src/nested/mod.rs",
+            "src/nested/untracked.rs",
+            "ignored/c.txt",
+            "sub/.keep",
+        ] {
+            create(root, file, file)?;
+        }
+        create(root, ".gitignore", "ignored/\n")?;
+        create_dir_all(root.join(".git"))?;
+        write(
+            root.join(".git").join("index"),
+            index(
+                version,
+                &[
+                    // A conflict: the file is listed for each stage
+                    (REGULAR, 1, "a.txt"),
+                    (REGULAR, 2, "a.txt"),
+                    (REGULAR, 3, "a.txt"),
+                    (SYMLINK, 0, "b.txt"),
+                    (REGULAR, 0, "ignored/c.txt"),
+                    (REGULAR, 0, "removed.txt"),
+                    (REGULAR, 0, "src/lib.rs"),
+                    (REGULAR, 0, "src/nested/mod.rs"),
+                    (GITLINK, 0, "sub"),
+                ],
+            ),
+        )?;
+        // Tracked files are collected even if they match ignore rules
+        assert_eq!(
+            collect(Entry::from(root)?.git(GitAware::Tracked))?,
+            vec![
+                "a.txt",
+                "b.txt",
+                "ignored/c.txt",
+                "src/lib.rs",
+                "src/nested/mod.rs"
+            ]
+        );
+        assert_eq!(
+            collect(Entry::from(root.join("src"))?.git(GitAware::Tracked))?,
+            vec!["lib.rs", "nested/mod.rs"]
+        );
+        usecase.clean()?;
+    }
+    Ok(())
+}
+
+#[test]
+fn worktree() -> Result<(), E> {
+    let usecase = UseCaseEmpty::gen()?;
+    let gitdir = UseCaseEmpty::gen()?;
+    let root = &usecase.root;
+    create(root, "a.txt", "a")?;
+    create(root, "b.txt", "b")?;
+    // The working tree refers to the git folder with a ".git" file
+    create(
+        root,
+        ".git",
+        &format!("gitdir: {}\n", gitdir.root.display()),
+    )?;
+    write(
+        gitdir.root.join("index"),
+        index(2, &[(REGULAR, 0, "a.txt")]),
+    )?;
+    assert_eq!(
+        collect(Entry::from(root)?.git(GitAware::Tracked))?,
+        vec!["a.txt"]
+    );
+    usecase.clean()?;
+    gitdir.clean()?;
+    Ok(())
+}
+
+#[test]
+fn errors() -> Result<(), E> {
+    let usecase = UseCaseEmpty::gen()?;
+    create(&usecase.root, "a.txt", "a")?;
+    // Not a repository
+    let mut walker = Options::new()
+        .entry(Entry::from(&usecase.root)?.git(GitAware::Tracked))?
+        .walker()?;
+    assert!(walker.collect().is_err());
+    // Broken index
+    create(&usecase.root, ".git/index", "DIRC")?;
+    let mut walker = Options::new()
+        .entry(Entry::from(&usecase.root)?.git(GitAware::Tracked))?
+        .walker()?;
+    assert!(walker.collect().is_err());
+    usecase.clean()?;
+    Ok(())
+}
@@ -0,0 +1,304 @@
+use std::{fs::create_dir_all, path::Path};
+
+use crate::{
+    test::{usecase::*, utils::create_text_file},
+    ContextFile, Entry, Options, SymlinkPolicy, E,
+};
+
+/// A tree of files (and symlinks as pairs of a link and its target) with `.gitignore` files and the
+/// list of files, which git considers as not ignored
+/// (`git ls-files -o --exclude-per-directory=.gitignore`).
+struct Case {
+    name: &'static str,
+    rules: &'static [(&'static str, &'static str)],
+    files: &'static [&'static str],
+    links: &'static [(&'static str, &'static str)],
+    expected: &'static [&'static str],
+}
+
+/// Cases are based on git's tests of ignore rules (t0008) and the documentation of `.gitignore`.
+const CASES: &[Case] = &[
+    Case {
+        name: "anchored",
+        rules: &[(".gitignore", "/build\n/sub/out.txt\n")],
+        files: &[
+            "build/a.txt",
+            "sub/build/a.txt",
+            "sub/out.txt",
+            "out.txt",
+            "a/sub/out.txt",
+        ],
+        links: &[],
+        expected: &[".gitignore", "a/sub/out.txt", "out.txt", "sub/build/a.txt"],
+    },
+    Case {
+        name: "dir_only",
+        rules: &[(".gitignore", "target/\n")],
+        files: &[
+            "target/a.txt",
+            "sub/target/b.txt",
+            "other/target",
+            "keep.txt",
+        ],
+        links: &[],
+        expected: &[".gitignore", "keep.txt", "other/target"],
+    },
+    Case {
+        name: "double_star",
+        rules: &[("sub/.gitignore", "**/gen\na/**/b.txt\n")],
+        files: &[
+            "gen/a.txt",
+            "sub/gen/a.txt",
+            "sub/x/gen/a.txt",
+            "sub/a/b.txt",
+            "sub/a/x/y/b.txt",
+            "a/b.txt",
+            "sub/x/a/b.txt",
+        ],
+        links: &[],
+        expected: &["a/b.txt", "gen/a.txt", "sub/.gitignore", "sub/x/a/b.txt"],
+    },
+    Case {
+        name: "escaped",
+        rules: &[(".gitignore", "\\#notes\n\\!important\n#comment\n\n  \n")],
+        files: &["#notes", "!important", "comment", "#comment", "notes"],
+        links: &[],
+        expected: &["#comment", ".gitignore", "comment", "notes"],
+    },
+    Case {
+        name: "trailing_spaces",
+        rules: &[(".gitignore", "name.txt   \n")],
+        files: &["name.txt", "other.txt"],
+        links: &[],
+        expected: &[".gitignore", "other.txt"],
+    },
+    Case {
+        name: "last_match_wins",
+        rules: &[(".gitignore", "*.log\n!keep.log\n")],
+        files: &["a.log", "keep.log", "sub/b.log", "sub/keep.log", "a.txt"],
+        links: &[],
+        expected: &[".gitignore", "a.txt", "keep.log", "sub/keep.log"],
+    },
+    Case {
+        name: "negation_order",
+        rules: &[(".gitignore", "!keep.log\n*.log\n")],
+        files: &["a.log", "keep.log", "a.txt"],
+        links: &[],
+        expected: &[".gitignore", "a.txt"],
+    },
+    Case {
+        name: "deeper_wins",
+        rules: &[
+            (".gitignore", "*.log\n"),
+            ("sub/.gitignore", "!*.log\n"),
+            ("sub/deep/.gitignore", "b.log\n"),
+        ],
+        files: &[
+            "a.log",
+            "sub/a.log",
+            "sub/deep/a.log",
+            "sub/deep/b.log",
+            "sub/b.log",
+        ],
+        links: &[],
+        expected: &[
+            ".gitignore",
+            "sub/.gitignore",
+            "sub/a.log",
+            "sub/b.log",
+            "sub/deep/.gitignore",
+            "sub/deep/a.log",
+        ],
+    },
+    Case {
+        name: "excluded_parent",
+        rules: &[(".gitignore", "logs/\n!logs/a.log\n")],
+        files: &["logs/a.log", "logs/b.log", "a.log"],
+        links: &[],
+        expected: &[".gitignore", "a.log"],
+    },
+    Case {
+        name: "middle_slash",
+        rules: &[("sub/.gitignore", "doc/frotz\n")],
+        files: &[
+            "sub/doc/frotz",
+            "sub/a/doc/frotz",
+            "doc/frotz",
+            "sub/doc/frotz2",
+        ],
+        links: &[],
+        expected: &[
+            "doc/frotz",
+            "sub/.gitignore",
+            "sub/a/doc/frotz",
+            "sub/doc/frotz2",
+        ],
+    },
+    Case {
+        name: "folder_content",
+        rules: &[(".gitignore", "foo/*\n!foo/keep\n")],
+        files: &[
+            "foo/bar",
+            "foo/keep",
+            "foo/baz/qux",
+            "foo/keep2",
+            "bar/foo/x",
+        ],
+        links: &[],
+        expected: &[".gitignore", "bar/foo/x", "foo/keep"],
+    },
+    Case {
+        name: "whitelist",
+        rules: &[(".gitignore", "*\n!*/\n!*.txt\n")],
+        files: &["a.txt", "a.rs", "sub/b.txt", "sub/b.rs", "sub/deep/c.txt"],
+        links: &[],
+        expected: &["a.txt", "sub/b.txt", "sub/deep/c.txt"],
+    },
+    Case {
+        name: "relative_base",
+        rules: &[("sub/.gitignore", "/a.txt\n*.tmp\n")],
+        files: &[
+            "a.txt",
+            "sub/a.txt",
+            "sub/x/a.txt",
+            "b.tmp",
+            "sub/b.tmp",
+            "sub/x/c.tmp",
+        ],
+        links: &[],
+        expected: &["a.txt", "b.tmp", "sub/.gitignore", "sub/x/a.txt"],
+    },
+    Case {
+        name: "crlf",
+        rules: &[(".gitignore", "a.txt\r\n*.tmp\r\n")],
+        files: &["a.txt", "b.tmp", "c.txt"],
+        links: &[],
+        expected: &[".gitignore", "c.txt"],
+    },
+    Case {
+        name: "classes",
+        rules: &[(".gitignore", "[[:digit:]]*.txt\nfile[!a-c].txt\n")],
+        files: &["1.txt", "a1.txt", "filea.txt", "filed.txt", "file1.txt"],
+        links: &[],
+        expected: &[".gitignore", "a1.txt", "filea.txt"],
+    },
+];
+
+/// Cases with symlinks and with names, which cannot be created on Windows (trailing spaces).
+#[cfg(unix)]
+const UNIX_CASES: &[Case] = &[
+    Case {
+        name: "escaped_space",
+        rules: &[(".gitignore", "space\\ \n")],
+        files: &["space ", "space"],
+        links: &[],
+        expected: &[".gitignore", "space"],
+    },
+    Case {
+        name: "dir_only_symlink",
+        rules: &[(".gitignore", "lnk/\ndir/\n")],
+        files: &["dir/a.txt", "keep.txt"],
+        links: &[("lnk", "dir")],
+        expected: &[".gitignore", "keep.txt", "lnk"],
+    },
+];
+
+fn create<P: AsRef<Path>>(root: P, rel: &str, content: &str) -> Result<(), E> {
+    let path = root.as_ref().join(rel);
+    if let Some(parent) = path.parent() {
+        create_dir_all(parent)?;
+    }
+    create_text_file(path, content)?;
+    Ok(())
+}
+
+fn collect(case: &Case, context: ContextFile<&str>) -> Result<Vec<String>, E> {
+    let usecase = UseCaseEmpty::gen()?;
+    for file in case.files.iter() {
+        create(&usecase.root, file, file)?;
+    }
+    for (file, rules) in case.rules.iter() {
+        create(&usecase.root, file, rules)?;
+    }
+    #[cfg(unix)]
+    for (link, target) in case.links.iter() {
+        std::os::unix::fs::symlink(target, usecase.root.join(link))?;
+    }
+    #[cfg(not(unix))]
+    assert!(case.links.is_empty(), "case: {}", case.name);
+    // Git keeps symlinks as links, so they aren't followed
+    let entry = Entry::from(&usecase.root)?.context(context);
+    let mut walker = Options::new()
+        .entry(entry)?
+        .symlinks(SymlinkPolicy::HashTarget)
+        .walker()?;
+    walker.collect()?;
+    let mut paths: Vec<String> = walker
+        .iter()
+        .map(|(p, _)| {
+            p.strip_prefix(&usecase.root)
+                .unwrap()
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().to_string())
+                .collect::<Vec<String>>()
+                .join("/")
+        })
+        .collect();
+    paths.sort();
+    usecase.clean()?;
+    Ok(paths)
+}
+
+#[test]
+fn corpus() -> Result<(), E> {
+    for case in CASES.iter() {
+        assert_eq!(
+            collect(case, ContextFile::Ignore(".gitignore"))?,
+            case.expected,
+            "case: {}",
+            case.name
+        );
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn unix_corpus() -> Result<(), E> {
+    for case in UNIX_CASES.iter() {
+        assert_eq!(
+            collect(case, ContextFile::Ignore(".gitignore"))?,
+            case.expected,
+            "case: {}",
+            case.name
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn accept() -> Result<(), E> {
+    // Accept rules are applied to files only, the last matching rule wins
+    let case = Case {
+        name: "accept",
+        rules: &[
+            (".accept", "*.txt\n!skip.txt\n"),
+            ("sub/.accept", "skip.txt\n/*.rs\n"),
+        ],
+        files: &[
+            "a.txt",
+            "skip.txt",
+            "a.rs",
+            "sub/skip.txt",
+            "sub/b.rs",
+            "sub/deep/c.rs",
+        ],
+        links: &[],
+        expected: &["a.txt", "sub/b.rs", "sub/skip.txt"],
+    };
+    assert_eq!(
+        collect(&case, ContextFile::Accept(".accept"))?,
+        case.expected
+    );
+    Ok(())
+}
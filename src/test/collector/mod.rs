@@ -1,4 +1,11 @@
+mod custom;
+mod depth;
+mod explain;
 mod filters;
+pub(crate) mod git;
+mod gitignore;
+mod predicates;
+mod wildmatch;
 
 use walker::Progress;
 
@@ -0,0 +1,92 @@
+use crate::*;
+use std::{
+    fs::{create_dir, File},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+use test::{usecase::*, utils};
+
+fn collect_sorted(entry: &Entry) -> Result<Vec<PathBuf>, E> {
+    let (mut collected, _) =
+        collector::collect(&None, entry, &Breaker::new(), &Tolerance::LogErrors, &None)?;
+    collected.sort();
+    Ok(collected)
+}
+
+fn file(path: &Path, size: usize) -> Result<PathBuf, E> {
+    utils::create_text_file(path, "x".repeat(size))?;
+    Ok(path.to_path_buf())
+}
+
+#[test]
+fn size() -> Result<(), E> {
+    let usecase = UseCaseEmpty::gen()?;
+    let small = file(&usecase.root.join("small.txt"), 10)?;
+    let medium = file(&usecase.root.join("medium.txt"), 100)?;
+    let large = file(&usecase.root.join("large.bin"), 1000)?;
+    let entry = Entry::from(&usecase.root)?.exclude(Filter::<&str>::LargerThan(100))?;
+    assert_eq!(collect_sorted(&entry)?, vec![medium.clone(), small.clone()]);
+    let entry = Entry::from(&usecase.root)?.exclude(Filter::<&str>::SmallerThan(100))?;
+    assert_eq!(collect_sorted(&entry)?, vec![large.clone(), medium.clone()]);
+    let entry = Entry::from(&usecase.root)?
+        .include(Filter::<&str>::LargerThan(10))?
+        .include(Filter::<&str>::SmallerThan(1000))?;
+    // Includes are combined with "or"
+    assert_eq!(collect_sorted(&entry)?, vec![large, medium, small]);
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn modified_after() -> Result<(), E> {
+    let usecase = UseCaseEmpty::gen()?;
+    let old = file(&usecase.root.join("old.txt"), 10)?;
+    let new = file(&usecase.root.join("new.txt"), 10)?;
+    let now = SystemTime::now();
+    File::options()
+        .write(true)
+        .open(&old)?
+        .set_modified(now - Duration::from_secs(3600))?;
+    let since = now - Duration::from_secs(60);
+    let entry = Entry::from(&usecase.root)?.include(Filter::<&str>::ModifiedAfter(since))?;
+    assert_eq!(collect_sorted(&entry)?, vec![new.clone()]);
+    let entry = Entry::from(&usecase.root)?.exclude(Filter::<&str>::ModifiedAfter(since))?;
+    assert_eq!(collect_sorted(&entry)?, vec![old]);
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn hidden() -> Result<(), E> {
+    let usecase = UseCaseEmpty::gen()?;
+    let visible = file(&usecase.root.join("visible.txt"), 10)?;
+    file(&usecase.root.join(".hidden"), 10)?;
+    create_dir(usecase.root.join(".cache"))?;
+    file(&usecase.root.join(".cache").join("visible.txt"), 10)?;
+    let entry = Entry::from(&usecase.root)?.exclude(Filter::<&str>::Hidden)?;
+    // Files in hidden folders are excluded as well
+    assert_eq!(collect_sorted(&entry)?, vec![visible]);
+    let entry = Entry::from(&usecase.root)?;
+    assert_eq!(collect_sorted(&entry)?.len(), 3);
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn extension() -> Result<(), E> {
+    let usecase = UseCaseEmpty::gen()?;
+    let rs = file(&usecase.root.join("main.rs"), 10)?;
+    let toml = file(&usecase.root.join("Cargo.TOML"), 10)?;
+    let tmp = file(&usecase.root.join("build.tmp"), 10)?;
+    create_dir(usecase.root.join("folder.rs"))?;
+    let nested = file(&usecase.root.join("folder.rs").join("nested.txt"), 10)?;
+    let entry = Entry::from(&usecase.root)?.include(Filter::Extension(vec!["rs", ".toml"]))?;
+    // Extensions are applied to files only
+    assert_eq!(collect_sorted(&entry)?, vec![toml.clone(), rs.clone()]);
+    let entry = Entry::from(&usecase.root)?.exclude(Filter::Extension(vec!["tmp"]))?;
+    assert_eq!(collect_sorted(&entry)?, vec![toml, nested, rs]);
+    let accepted: entry::FilterAccepted = Filter::Extension(vec!["TMP"]).try_into()?;
+    assert_eq!(accepted.filtered(&tmp), Some(true));
+    usecase.clean()?;
+    Ok(())
+}
@@ -0,0 +1,162 @@
+use crate::collector::wildmatch::wildmatch;
+
+/// Cases of git's wildmatch tests (t3070) for the mode used by ignore files (`WM_PATHNAME`):
+/// `(matched, text, pattern)`. Expectations are verified with `git check-ignore` on anchored
+/// patterns.
+const CASES: &[(bool, &str, &str)] = &[
+    (true, "", ""),
+    (true, "foo", "foo"),
+    (false, "foo", "bar"),
+    (true, "foo", "???"),
+    (false, "foo", "??"),
+    (true, "foo", "*"),
+    (true, "foo", "f*"),
+    (false, "foo", "*f"),
+    (true, "foo", "*foo*"),
+    (true, "foobar", "*ob*a*r*"),
+    (true, "aaaaaaabababab", "*ab"),
+    (true, "foo*", "foo\\*"),
+    (false, "foobar", "foo\\*bar"),
+    (true, "f\\oo", "f\\\\oo"),
+    (true, "ball", "*[al]?"),
+    (false, "ten", "[ten]"),
+    (true, "ten", "**[!te]"),
+    (false, "ten", "**[!ten]"),
+    (true, "ten", "t[a-g]n"),
+    (false, "ten", "t[!a-g]n"),
+    (true, "ton", "t[!a-g]n"),
+    (true, "ton", "t[^a-g]n"),
+    (true, "a]b", "a[]]b"),
+    (true, "a-b", "a[]-]b"),
+    (true, "a]b", "a[]-]b"),
+    (false, "aab", "a[]-]b"),
+    (true, "aab", "a[]a-]b"),
+    (true, "]", "]"),
+    (false, "foo/baz/bar", "foo*bar"),
+    (false, "foo/baz/bar", "foo**bar"),
+    (true, "foobazbar", "foo**bar"),
+    (true, "foo/baz/bar", "foo/**/bar"),
+    (true, "foo/baz/bar", "foo/**/**/bar"),
+    (true, "foo/b/a/z/bar", "foo/**/bar"),
+    (true, "foo/b/a/z/bar", "foo/**/**/bar"),
+    (true, "foo/bar", "foo/**/bar"),
+    (true, "foo/bar", "foo/**/**/bar"),
+    (false, "foo/bar", "foo?bar"),
+    (false, "foo/bar", "foo[/]bar"),
+    (false, "foo/bar", "foo[^a-z]bar"),
+    (false, "foo/bar", "f[^eiu][^eiu][^eiu][^eiu][^eiu]r"),
+    (true, "foo-bar", "f[^eiu][^eiu][^eiu][^eiu][^eiu]r"),
+    (true, "foo", "**/foo"),
+    (true, "XXX/foo", "**/foo"),
+    (true, "bar/baz/foo", "**/foo"),
+    (false, "bar/baz/foo", "*/foo"),
+    (false, "foo/bar/baz", "**/bar*"),
+    (true, "deep/foo/bar/baz", "**/bar/*"),
+    (false, "deep/foo/bar", "**/bar/*"),
+    (false, "foo/bar/baz", "**/bar**"),
+    (true, "foo/bar/baz/x", "*/bar/**"),
+    (false, "deep/foo/bar/baz/x", "*/bar/**"),
+    (true, "deep/foo/bar/baz/x", "**/bar/*/*"),
+    (false, "acrt", "a[c-c]st"),
+    (true, "acrt", "a[c-c]rt"),
+    (false, "]", "[!]-]"),
+    (true, "a", "[!]-]"),
+    (false, "\\", "\\"),
+    (false, "foo", "*/bar"),
+    (true, "foo/bar", "foo/*"),
+    (false, "foo/bba/arr", "foo/*"),
+    (true, "foo/bba/arr", "foo/**"),
+    (false, "foo/bba/arr", "foo*"),
+    (false, "foo/bba/arr", "foo**"),
+    (false, "foo/bba/arr", "foo/*arr"),
+    (false, "foo/bba/arr", "foo/**arr"),
+    (false, "foo/bba/arr", "foo/*z"),
+    (false, "foo/bba/arr", "foo/**z"),
+    (true, "ab/cXd/efXg/hi", "**/*X*/**/*i"),
+    (true, "ab/cXd/efXg/hi", "*/*X*/*/*i"),
+    (false, "ab/cXd/efXg/hi", "*Xg*i"),
+    (true, "-", "[-]"),
+    (true, "-", "[,-.]"),
+    (false, "+", "[,-.]"),
+    (false, "-.]", "[,-.]"),
+    (true, "1", "[\\1-\\3]"),
+    (true, "3", "[\\1-\\3]"),
+    (false, "4", "[\\1-\\3]"),
+    (true, "\\", "[[-\\]]"),
+    (true, "[", "[[-\\]]"),
+    (true, "]", "[[-\\]]"),
+    (false, "-", "[[-\\]]"),
+    (true, "-adobe-courier-bold-o-normal--12-120-75-75-m-70-iso8859-1", "-*-*-*-*-*-*-12-*-*-*-m-*-*-*"),
+    (false, "-adobe-courier-bold-o-normal--12-120-75-75-X-70-iso8859-1", "-*-*-*-*-*-*-12-*-*-*-m-*-*-*"),
+    (false, "-adobe-courier-bold-o-normal--12-120-75-75-/-70-iso8859-1", "-*-*-*-*-*-*-12-*-*-*-m-*-*-*"),
+    (true, "abcd/abcdefg/abcdefghijk/abcdefghijklmnop.txt", "**/*a*b*g*n*t"),
+    (false, "abcd/abcdefg/abcdefghijk/abcdefghijklmnop.txtz", "**/*a*b*g*n*t"),
+    (false, "foo", "*/*/*"),
+    (false, "foo/bar", "*/*/*"),
+    (true, "foo/bba/arr", "*/*/*"),
+    (false, "foo/bb/aa/rr", "*/*/*"),
+    (true, "foo/bb/aa/rr", "**/**/**"),
+    (true, "abcXdefXghi", "*X*i"),
+    (false, "ab/cXd/efXg/hi", "*X*i"),
+    (true, "a1B", "[[:alpha:]][[:digit:]][[:upper:]]"),
+    (false, "a", "[[:digit:][:upper:][:space:]]"),
+    (true, "A", "[[:digit:][:upper:][:space:]]"),
+    (true, "1", "[[:digit:][:upper:][:space:]]"),
+    (false, "1", "[[:digit:][:upper:][:spaci:]]"),
+    (true, " ", "[[:digit:][:upper:][:space:]]"),
+    (true, "5", "[[:xdigit:]]"),
+    (true, "f", "[[:xdigit:]]"),
+    (true, "D", "[[:xdigit:]]"),
+    (true, "_", "[[:alnum:][:alpha:][:blank:][:cntrl:][:digit:][:graph:][:lower:][:print:][:punct:][:space:][:upper:][:xdigit:]]"),
+    (true, "5", "[a-c[:digit:]x-z]"),
+    (true, "b", "[a-c[:digit:]x-z]"),
+    (true, "y", "[a-c[:digit:]x-z]"),
+    (false, "q", "[a-c[:digit:]x-z]"),
+    (true, "]", "[\\\\-^]"),
+    (false, "[", "[\\\\-^]"),
+    (false, "\\", "[\\]"),
+    (true, "\\", "[\\\\]"),
+    (false, "\\", "[!\\\\]"),
+    (true, "G", "[A-\\\\]"),
+    (false, "aaabbb", "b*a"),
+    (false, "aabcaa", "*ba*"),
+    (true, ",", "[,]"),
+    (true, ",", "[\\\\,]"),
+    (true, "\\", "[\\\\,]"),
+    (true, "+", "[!,-.]"),
+    (true, "a", "[\\a-\\c]"),
+    (false, "{", "[a-\\\\]"),
+    (true, ":", "[[:]"),
+    (false, ":", "[[::]]"),
+    (false, ":", "[[:::]]"),
+    (true, "[ab]", "\\[ab]"),
+    (true, "[ab]", "[[]ab]"),
+    (true, "[ab]", "[[:]ab]"),
+    (true, "[ab]", "[\\[:]ab]"),
+    (true, "?a?b", "\\??\\?b"),
+    (true, "abc", "\\a\\b\\c"),
+    (true, "foo/bar/baz/to", "**/t[o]"),
+    (false, "foo.o", "foo.[!o]"),
+    (true, "foo.c", "foo.[!o]"),
+    (false, "deep/foo/bar/baz/", "**/bar/*"),
+    (true, "deep/foo/bar/baz/", "**/bar/**"),
+    (true, "deep/foo/bar/", "**/bar/**"),
+    (false, ".", "[[:digit:][:upper:][:space:]]"),
+    (true, ".", "[[:digit:][:punct:][:space:]]"),
+    (
+        true,
+        ".",
+        "[^[:alnum:][:alpha:][:blank:][:cntrl:][:digit:][:lower:][:space:][:upper:][:xdigit:]]",
+    ),
+];
+
+#[test]
+fn corpus() {
+    let failed: Vec<&(bool, &str, &str)> = CASES
+        .iter()
+        .filter(|(matched, text, pattern)| {
+            wildmatch(pattern.as_bytes(), text.as_bytes()) != *matched
+        })
+        .collect();
+    assert!(failed.is_empty(), "{failed:?}");
+}
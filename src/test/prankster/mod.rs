@@ -1,8 +1,11 @@
-use std::{env::temp_dir, thread};
+#[cfg(unix)]
+mod symlinks;
+
+use std::thread;
 
 use crate::{
-    collector::Tolerance, entry::Entry, hasher, reader, test::usecase::*, Options, ReadingStrategy,
-    E,
+    collector::Tolerance, entry::Entry, hasher, reader, test::usecase::*, HasherKind, Options,
+    ReaderKind, ReadingStrategy, E,
 };
 
 #[test]
@@ -60,12 +63,24 @@ fn bad_options_no_threads() -> Result<(), E> {
     let usecase = UseCase::unnamed(2, 2, 2, &[])?;
     let opt = Options {
         entries: vec![Entry::from(&usecase.root)?],
+        files: Vec::new(),
         threads: Some(0),
         tolerance: Tolerance::LogErrors,
         progress: None,
         reading_strategy: ReadingStrategy::Buffer,
+        symlinks: Default::default(),
+        record_skipped: false,
+        paths_in_summary: false,
+        summary: Default::default(),
+        cache: false,
+        hasher: HasherKind::default(),
+        reader: ReaderKind::default(),
+        hasher_config: Default::default(),
+        metadata: Vec::new(),
+        hashers: Vec::new(),
         global: Entry::new(),
-        storage: temp_dir(),
+        #[cfg(feature = "tracking")]
+        storage: std::env::temp_dir(),
     };
     let mut walker = opt.walker()?;
     assert!(walker.collect().is_err());
@@ -82,12 +97,24 @@ fn bad_options_too_many_threads() -> Result<(), E> {
     let usecase = UseCase::unnamed(2, 2, 2, &[])?;
     let opt = Options {
         entries: vec![Entry::from(&usecase.root)?],
+        files: Vec::new(),
         threads: Some(10000),
         tolerance: Tolerance::LogErrors,
         progress: None,
         reading_strategy: ReadingStrategy::Buffer,
+        symlinks: Default::default(),
+        record_skipped: false,
+        paths_in_summary: false,
+        summary: Default::default(),
+        cache: false,
+        hasher: HasherKind::default(),
+        reader: ReaderKind::default(),
+        hasher_config: Default::default(),
+        metadata: Vec::new(),
+        hashers: Vec::new(),
         global: Entry::new(),
-        storage: temp_dir(),
+        #[cfg(feature = "tracking")]
+        storage: std::env::temp_dir(),
     };
     let mut walker = opt.walker()?;
     assert!(walker.collect().is_err());
@@ -0,0 +1,162 @@
+use std::{
+    fs::{create_dir, remove_file},
+    os::unix::fs::symlink,
+    path::{Path, PathBuf},
+};
+
+use crate::{hasher, reader, test::usecase::*, test::utils, Options, SymlinkPolicy, E};
+
+/// Creates "a/file.txt" and "b/file.txt" in the root folder.
+fn structure(root: &Path) -> Result<Vec<PathBuf>, E> {
+    let mut files = Vec::new();
+    for folder in ["a", "b"] {
+        create_dir(root.join(folder))?;
+        let file = root.join(folder).join("file.txt");
+        utils::create_text_file(&file, folder)?;
+        files.push(file);
+    }
+    Ok(files)
+}
+
+fn collect(root: &Path, policy: SymlinkPolicy) -> Result<(Vec<PathBuf>, Vec<u8>), E> {
+    let mut walker = Options::from(root)?.symlinks(policy).walker()?;
+    let hash = walker
+        .collect()?
+        .hash::<hasher::blake::Blake, reader::buffering::Buffering>()?
+        .to_vec();
+    let mut paths: Vec<PathBuf> = walker
+        .iter()
+        .filter(|(_, h)| matches!(h, Some(Ok(_))))
+        .map(|(p, _)| p.to_owned())
+        .collect();
+    paths.sort();
+    Ok((paths, hash))
+}
+
+#[test]
+fn loop_to_ancestor() -> Result<(), E> {
+    let usecase = UseCaseEmpty::gen()?;
+    let files = structure(&usecase.root)?;
+    symlink(&usecase.root, usecase.root.join("a").join("root"))?;
+    symlink("..", usecase.root.join("b").join("up"))?;
+    symlink(".", usecase.root.join("b").join("self"))?;
+    let (paths, _) = collect(&usecase.root, SymlinkPolicy::Follow)?;
+    assert_eq!(paths, files);
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn mutual_loop() -> Result<(), E> {
+    let usecase = UseCaseEmpty::gen()?;
+    let files = structure(&usecase.root)?;
+    symlink("../b", usecase.root.join("a").join("to_b"))?;
+    symlink("../a", usecase.root.join("b").join("to_a"))?;
+    let (paths, _) = collect(&usecase.root, SymlinkPolicy::Follow)?;
+    // Each folder is reached once via a symlink; the next step would be a loop
+    assert!(paths.contains(&files[0]));
+    assert!(paths.contains(&files[1]));
+    assert!(paths.contains(&usecase.root.join("a").join("to_b").join("file.txt")));
+    assert!(paths.contains(&usecase.root.join("b").join("to_a").join("file.txt")));
+    assert_eq!(paths.len(), 4);
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn relative_target() -> Result<(), E> {
+    let usecase = UseCaseEmpty::gen()?;
+    structure(&usecase.root)?;
+    let link = usecase.root.join("a").join("link.txt");
+    symlink("../b/file.txt", &link)?;
+    let (paths, _) = collect(&usecase.root, SymlinkPolicy::Follow)?;
+    assert!(paths.contains(&link));
+    assert_eq!(paths.len(), 3);
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn skip() -> Result<(), E> {
+    let usecase = UseCaseEmpty::gen()?;
+    let files = structure(&usecase.root)?;
+    symlink("../b/file.txt", usecase.root.join("a").join("link.txt"))?;
+    symlink("../b", usecase.root.join("a").join("b"))?;
+    let (paths, _) = collect(&usecase.root, SymlinkPolicy::Skip)?;
+    assert_eq!(paths, files);
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn hash_target() -> Result<(), E> {
+    let usecase = UseCaseEmpty::gen()?;
+    structure(&usecase.root)?;
+    utils::create_text_file(usecase.root.join("b").join("copy.txt"), "b")?;
+    let link = usecase.root.join("a").join("link.txt");
+    symlink("../b/file.txt", &link)?;
+    symlink("../b", usecase.root.join("a").join("b"))?;
+    let broken = usecase.root.join("a").join("broken.txt");
+    symlink("../nowhere.txt", &broken)?;
+    let (paths, hash) = collect(&usecase.root, SymlinkPolicy::HashTarget)?;
+    // Links to files, folders and broken links are collected as files
+    assert!(paths.contains(&link));
+    assert!(paths.contains(&broken));
+    assert!(paths.contains(&usecase.root.join("a").join("b")));
+    assert_eq!(paths.len(), 6);
+    // Content is the same, but the target string is different
+    remove_file(&link)?;
+    symlink("../b/copy.txt", &link)?;
+    assert_ne!(hash, collect(&usecase.root, SymlinkPolicy::HashTarget)?.1);
+    assert_eq!(
+        collect(&usecase.root, SymlinkPolicy::HashTarget)?.1,
+        collect(&usecase.root, SymlinkPolicy::HashTarget)?.1
+    );
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn within_entry() -> Result<(), E> {
+    let usecase = UseCaseEmpty::gen()?;
+    let outside = UseCaseEmpty::gen()?;
+    structure(&usecase.root)?;
+    utils::create_text_file(outside.root.join("outside.txt"), "outside")?;
+    symlink(&outside.root, usecase.root.join("a").join("outside"))?;
+    let inside = usecase.root.join("a").join("link.txt");
+    symlink("../b/file.txt", &inside)?;
+    let (paths, _) = collect(&usecase.root, SymlinkPolicy::WithinEntry)?;
+    assert!(paths.contains(&inside));
+    assert_eq!(paths.len(), 3);
+    let (paths, _) = collect(&usecase.root, SymlinkPolicy::Follow)?;
+    assert!(paths.contains(&usecase.root.join("a").join("outside").join("outside.txt")));
+    assert_eq!(paths.len(), 4);
+    usecase.clean()?;
+    outside.clean()?;
+    Ok(())
+}
+
+#[cfg(feature = "tracking")]
+#[test]
+fn tracking_key() -> Result<(), E> {
+    let usecase = UseCaseEmpty::gen()?;
+    let plain = Options::from(&usecase.root)?.hash();
+    // The default policy doesn't change the key, so previous records stay valid
+    assert_eq!(
+        plain,
+        Options::from(&usecase.root)?
+            .symlinks(SymlinkPolicy::Follow)
+            .hash()
+    );
+    let skip = Options::from(&usecase.root)?
+        .symlinks(SymlinkPolicy::Skip)
+        .hash();
+    let target = Options::from(&usecase.root)?
+        .symlinks(SymlinkPolicy::HashTarget)
+        .hash();
+    assert_ne!(plain, skip);
+    assert_ne!(plain, target);
+    assert_ne!(skip, target);
+    usecase.clean()?;
+    Ok(())
+}
@@ -138,8 +138,7 @@ impl UseCase {
 
     pub fn change(&self, count: usize) -> Result<(), io::Error> {
         if self.files.is_empty() {
-            return Err(io::Error::new(
-                io::ErrorKind::Other,
+            return Err(io::Error::other(
                 "No files has been created. Cannot change a state",
             ));
         }
@@ -162,16 +161,12 @@ impl UseCase {
 
     pub fn remove(&self, count: usize) -> Result<(), io::Error> {
         if self.files.is_empty() {
-            return Err(io::Error::new(
-                io::ErrorKind::Other,
+            return Err(io::Error::other(
                 "No files has been created. Cannot change a state",
             ));
         }
         if count > self.files.len() - 1 {
-            return Err(io::Error::new(
-                io::ErrorKind::Other,
-                "Cannot remove more files than created",
-            ));
+            return Err(io::Error::other("Cannot remove more files than created"));
         }
         let s = rand::thread_rng().gen_range(0..self.files.len() - 1 - count);
         for i in s..(s + count) {
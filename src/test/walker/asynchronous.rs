@@ -0,0 +1,178 @@
+use std::time::Duration;
+
+use crate::{hasher, reader, test::usecase::*, JobType, Options, Tick, E};
+
+fn summary(usecase: &UseCase) -> Result<Vec<u8>, E> {
+    let mut walker = Options::from(&usecase.root)?.walker()?;
+    Ok(walker
+        .collect()?
+        .hash::<hasher::blake::Blake, reader::buffering::Buffering>()?
+        .to_vec())
+}
+
+#[tokio::test]
+async fn hashing() -> Result<(), E> {
+    let usecase = UseCase::unnamed(2, 4, 2, &[])?;
+    let mut walker = Options::from(&usecase.root)?.walker()?.into_async();
+    let hash = walker
+        .collect()
+        .await?
+        .hash::<hasher::blake::Blake, reader::buffering::Buffering>()
+        .await?;
+    assert_eq!(hash, summary(&usecase)?);
+    assert_eq!(walker.walker().await.count(), usecase.files.len());
+    usecase.clean()?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn progress() -> Result<(), E> {
+    let usecase = UseCase::unnamed(5, 10, 3, &[])?;
+    let mut walker = Options::from(&usecase.root)?
+        .progress(10)
+        .walker()?
+        .into_async();
+    let mut rx_progress = walker.progress().unwrap();
+    assert!(walker.progress().is_none());
+    let handle = tokio::spawn(async move {
+        let mut collecting = false;
+        let mut hashing = false;
+        while let Some(tick) = rx_progress.recv().await {
+            match tick.job {
+                JobType::Collecting => collecting = true,
+                JobType::Hashing => hashing = true,
+            }
+        }
+        (collecting, hashing)
+    });
+    walker
+        .collect()
+        .await?
+        .hash::<hasher::blake::Blake, reader::buffering::Buffering>()
+        .await?;
+    // Channel is closed as soon as walker is dropped
+    drop(walker);
+    let (collecting, hashing) = handle.await.expect("progress task is finished");
+    assert!(collecting);
+    assert!(hashing);
+    usecase.clean()?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn cancellation_on_drop() -> Result<(), E> {
+    let usecase = UseCase::unnamed(5, 10, 3, &[])?;
+    let mut walker = Options::from(&usecase.root)?.walker()?.into_async();
+    walker.collect().await?;
+    // Future is dropped right after the first poll
+    assert!(tokio::time::timeout(
+        Duration::ZERO,
+        walker.hash::<hasher::blake::Blake, reader::buffering::Buffering>()
+    )
+    .await
+    .is_err());
+    assert!(walker.breaker().is_aborted());
+    // Walker is available again as soon as the aborted operation is done
+    let hash = walker
+        .collect()
+        .await?
+        .hash::<hasher::blake::Blake, reader::buffering::Buffering>()
+        .await?;
+    assert_eq!(hash, summary(&usecase)?);
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn single_blocking_thread() -> Result<(), E> {
+    let usecase = UseCase::unnamed(2, 4, 2, &[])?;
+    // Delivering of progress doesn't occupy a blocking thread, so one is enough for operations
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .max_blocking_threads(1)
+        .enable_time()
+        .build()?;
+    let hash = runtime.block_on(async {
+        let mut walker = Options::from(&usecase.root)?
+            .progress(10)
+            .walker()?
+            .into_async();
+        let mut progress = walker.progress().unwrap();
+        let ticks = tokio::spawn(async move {
+            let mut count = 0;
+            while progress.recv().await.is_some() {
+                count += 1;
+            }
+            count
+        });
+        let hash = tokio::time::timeout(Duration::from_secs(10), async {
+            walker
+                .collect()
+                .await?
+                .hash::<hasher::blake::Blake, reader::buffering::Buffering>()
+                .await
+        })
+        .await
+        .expect("Operations aren't blocked")?;
+        drop(walker);
+        assert!(ticks.await.expect("progress task is finished") > 0);
+        Ok::<Vec<u8>, E>(hash)
+    })?;
+    assert_eq!(hash, summary(&usecase)?);
+    usecase.clean()?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn bounded_progress() -> Result<(), E> {
+    let usecase = UseCase::unnamed(5, 10, 3, &[])?;
+    let mut walker = Options::from(&usecase.root)?
+        .progress(1)
+        .walker()?
+        .into_async();
+    let mut rx_progress = walker.progress().unwrap();
+    let handle = tokio::spawn(async move {
+        let mut last: Option<Tick> = None;
+        while let Some(tick) = rx_progress.recv().await {
+            // Slow receiver: the walker waits instead of dropping ticks
+            tokio::time::sleep(Duration::from_millis(1)).await;
+            last = Some(tick);
+        }
+        last
+    });
+    walker
+        .collect()
+        .await?
+        .hash::<hasher::blake::Blake, reader::buffering::Buffering>()
+        .await?;
+    drop(walker);
+    let last = handle
+        .await
+        .expect("progress task is finished")
+        .expect("ticks are received");
+    assert!(matches!(last.job, JobType::Hashing));
+    assert_eq!(last.done, last.total);
+    usecase.clean()?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn untaken_progress() -> Result<(), E> {
+    let usecase = UseCase::unnamed(5, 10, 3, &[])?;
+    let mut walker = Options::from(&usecase.root)?
+        .progress(1)
+        .walker()?
+        .into_async();
+    // Nobody receives ticks, but operations aren't blocked
+    let hash = tokio::time::timeout(Duration::from_secs(10), async {
+        walker
+            .collect()
+            .await?
+            .hash::<hasher::blake::Blake, reader::buffering::Buffering>()
+            .await
+    })
+    .await
+    .expect("Operations aren't blocked")?;
+    assert_eq!(hash, summary(&usecase)?);
+    usecase.clean()?;
+    Ok(())
+}
@@ -0,0 +1,176 @@
+use std::{
+    fs::{metadata, read, write, File, OpenOptions},
+    io::Write,
+    path::Path,
+    time::{Duration, SystemTime},
+};
+
+use crate::{
+    hasher::{self, blake::Blake, HasherConfig},
+    reader::{self, buffering::Buffering},
+    test::usecase::*,
+    walker::cache::Cache,
+    Options, E,
+};
+
+/// Moves the modification time of files an hour back, so they aren't considered as modified during
+/// hashing.
+fn backdate(files: &[impl AsRef<Path>]) -> Result<(), E> {
+    let modified = SystemTime::now() - Duration::from_secs(3600);
+    for file in files {
+        File::options()
+            .write(true)
+            .open(file)?
+            .set_modified(modified)?;
+    }
+    Ok(())
+}
+
+/// Overwrites the content of the file in place, keeping its size and modification time. Such a
+/// change can be detected by the status change time only.
+fn overwrite_silently(path: &Path) -> Result<(), E> {
+    let modified = metadata(path)?.modified()?;
+    let content = read(path)?;
+    let mut file = OpenOptions::new().write(true).open(path)?;
+    file.write_all(&vec![b'#'; content.len()])?;
+    file.flush()?;
+    file.set_modified(modified)?;
+    Ok(())
+}
+
+fn summary(opt: Options) -> Result<Vec<u8>, E> {
+    let mut walker = opt.walker()?;
+    Ok(walker
+        .collect()?
+        .hash::<hasher::blake::Blake, reader::buffering::Buffering>()?
+        .to_vec())
+}
+
+#[test]
+fn unchanged_files_are_not_read() -> Result<(), E> {
+    let usecase = UseCase::unnamed(2, 4, 2, &[])?;
+    backdate(&usecase.files)?;
+    let path = &usecase.files[0];
+    let mut cache = Cache::new::<Blake, Buffering>(&HasherConfig::default());
+    cache.set(path, &metadata(path)?, b"cached");
+    cache.rotate();
+    // Cached hash is used, because metadata of the file is the same
+    assert_eq!(cache.get(path, &metadata(path)?), Some(b"cached".to_vec()));
+    // The record is kept for the next hashing
+    cache.rotate();
+    assert_eq!(cache.get(path, &metadata(path)?), Some(b"cached".to_vec()));
+    usecase.clean()?;
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn silent_changes_are_detected() -> Result<(), E> {
+    let usecase = UseCase::unnamed(2, 4, 2, &[])?;
+    backdate(&usecase.files)?;
+    let mut walker = Options::from(&usecase.root)?.cache(true).walker()?;
+    let before = walker
+        .collect()?
+        .hash::<hasher::blake::Blake, reader::buffering::Buffering>()?
+        .to_vec();
+    overwrite_silently(&usecase.files[0])?;
+    // Size and modification time are the same, but the status change time isn't
+    let after = walker
+        .collect()?
+        .hash::<hasher::blake::Blake, reader::buffering::Buffering>()?
+        .to_vec();
+    assert_ne!(before, after);
+    assert_eq!(after, summary(Options::from(&usecase.root)?)?);
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn racy_files_are_hashed_again() -> Result<(), E> {
+    let usecase = UseCase::unnamed(2, 4, 2, &[])?;
+    let path = &usecase.files[0];
+    let mut cache = Cache::new::<Blake, Buffering>(&HasherConfig::default());
+    // The file is modified after the records have been started, so it could be modified again
+    // without changing its metadata
+    write(path, b"racy")?;
+    cache.set(path, &metadata(path)?, b"cached");
+    cache.rotate();
+    assert_eq!(cache.get(path, &metadata(path)?), None);
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn changed_files_are_hashed() -> Result<(), E> {
+    let usecase = UseCase::unnamed(2, 4, 2, &[])?;
+    let mut walker = Options::from(&usecase.root)?.cache(true).walker()?;
+    let before = walker
+        .collect()?
+        .hash::<hasher::blake::Blake, reader::buffering::Buffering>()?
+        .to_vec();
+    usecase.change(3)?;
+    let after = walker
+        .collect()?
+        .hash::<hasher::blake::Blake, reader::buffering::Buffering>()?
+        .to_vec();
+    assert_ne!(before, after);
+    assert_eq!(after, summary(Options::from(&usecase.root)?)?);
+    usecase.remove(2)?;
+    let after = walker
+        .collect()?
+        .hash::<hasher::blake::Blake, reader::buffering::Buffering>()?
+        .to_vec();
+    assert_eq!(after, summary(Options::from(&usecase.root)?)?);
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn hasher_change_resets_cache() -> Result<(), E> {
+    let usecase = UseCase::unnamed(2, 4, 2, &[])?;
+    let mut walker = Options::from(&usecase.root)?.cache(true).walker()?;
+    walker
+        .collect()?
+        .hash::<hasher::blake::Blake, reader::buffering::Buffering>()?;
+    let md = walker
+        .collect()?
+        .hash::<hasher::blake::Blake, reader::md::Md>()?
+        .to_vec();
+    let mut expected = Options::from(&usecase.root)?.walker()?;
+    assert_eq!(
+        md,
+        expected
+            .collect()?
+            .hash::<hasher::blake::Blake, reader::md::Md>()?
+    );
+    usecase.clean()?;
+    Ok(())
+}
+
+#[cfg(all(feature = "tracking", unix))]
+#[test]
+fn persistent() -> Result<(), E> {
+    use std::{env::temp_dir, fs::remove_dir_all};
+    use uuid::Uuid;
+
+    let usecase = UseCase::unnamed(2, 4, 2, &[])?;
+    backdate(&usecase.files)?;
+    let storage = temp_dir().join(Uuid::new_v4().to_string());
+    let path = &usecase.files[0];
+    let mut cache = Cache::new::<Blake, Buffering>(&HasherConfig::default());
+    cache.set(path, &metadata(path)?, b"cached");
+    cache.rotate();
+    cache.save(&storage, "test")?;
+    // New cache takes records from the storage
+    let cache = Cache::load::<Blake, Buffering>(&HasherConfig::default(), &storage, "test")?;
+    assert_eq!(cache.get(path, &metadata(path)?), Some(b"cached".to_vec()));
+    // Stored records don't hide silent changes
+    let before = summary(Options::from(&usecase.root)?.storage(&storage)?.cache(true))?;
+    overwrite_silently(path)?;
+    let after = summary(Options::from(&usecase.root)?.storage(&storage)?.cache(true))?;
+    assert_ne!(before, after);
+    assert_eq!(after, summary(Options::from(&usecase.root)?)?);
+    usecase.clean()?;
+    remove_dir_all(&storage)?;
+    Ok(())
+}
@@ -374,10 +374,8 @@ fn ignore_root() -> Result<(), E> {
         walker.count(),
         UseCase::expectation(folders as usize, files as usize, (deep + 1) as usize)
     );
-    create_text_file(
-        usecase.root.join(".ignore"),
-        format!("**/{}/**/*", usecase.root.display()),
-    )?;
+    // Rules are relative to the folder of the context file
+    create_text_file(usecase.root.join(".ignore"), "*")?;
     let entry = Entry::new()
         .entry(&usecase.root)?
         .context(ContextFile::Ignore(".ignore"));
@@ -0,0 +1,106 @@
+use std::path::PathBuf;
+
+use crate::{
+    hasher, reader,
+    test::{usecase::*, utils},
+    Filter, Options, E,
+};
+
+#[test]
+fn along_with_entry() -> Result<(), E> {
+    let usecase = UseCase::unnamed(2, 4, 2, &[])?;
+    let extra = UseCaseEmpty::gen()?;
+    let file = extra.root.join("Cargo.lock");
+    utils::create_text_file(&file, "lock")?;
+    let mut walker = Options::from(&usecase.root)?.walker()?;
+    let without = walker
+        .collect()?
+        .hash::<hasher::blake::Blake, reader::buffering::Buffering>()?
+        .to_vec();
+    let mut walker = Options::from(&usecase.root)?.file(&file)?.walker()?;
+    let with = walker
+        .collect()?
+        .hash::<hasher::blake::Blake, reader::buffering::Buffering>()?
+        .to_vec();
+    assert_ne!(without, with);
+    assert_eq!(walker.count(), usecase.files.len() + 1);
+    assert!(walker
+        .iter()
+        .any(|(p, h)| p == &file && matches!(h, Some(Ok(_)))));
+    usecase.clean()?;
+    extra.clean()?;
+    Ok(())
+}
+
+#[test]
+fn hashed_once() -> Result<(), E> {
+    let usecase = UseCase::unnamed(2, 4, 2, &[])?;
+    let mut walker = Options::from(&usecase.root)?.walker()?;
+    let expected = walker
+        .collect()?
+        .hash::<hasher::blake::Blake, reader::buffering::Buffering>()?
+        .to_vec();
+    let mut walker = Options::from(&usecase.root)?
+        .files(usecase.files.iter().take(3))?
+        .walker()?;
+    let hash = walker
+        .collect()?
+        .hash::<hasher::blake::Blake, reader::buffering::Buffering>()?
+        .to_vec();
+    assert_eq!(walker.count(), usecase.files.len());
+    assert_eq!(expected, hash);
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn files_only() -> Result<(), E> {
+    let usecase = UseCase::unnamed(2, 4, 2, &[])?;
+    let mut walker = Options::new().files(usecase.files.iter())?.walker()?;
+    let hash = walker
+        .collect()?
+        .hash::<hasher::blake::Blake, reader::buffering::Buffering>()?
+        .to_vec();
+    assert!(!hash.is_empty());
+    assert_eq!(walker.count(), usecase.files.len());
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn not_filtered() -> Result<(), E> {
+    let usecase = UseCase::unnamed(2, 4, 2, &["aaa", "bbb"])?;
+    let excluded: Vec<&PathBuf> = usecase
+        .files
+        .iter()
+        .filter(|p| p.extension().is_some_and(|ext| ext == "aaa"))
+        .collect();
+    let mut walker = Options::from(&usecase.root)?
+        .exclude(Filter::Files("*.aaa"))?
+        .file(excluded[0])?
+        .walker()?;
+    walker.collect()?;
+    assert!(walker.iter().any(|(p, _)| p == excluded[0]));
+    assert_eq!(walker.count(), usecase.files.len() - excluded.len() + 1);
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn invalid() -> Result<(), E> {
+    let usecase = UseCase::unnamed(1, 2, 0, &[])?;
+    assert!(matches!(
+        Options::new().file("Cargo.toml"),
+        Err(E::RelativePathAsEntry(_))
+    ));
+    assert!(matches!(
+        Options::new().file(&usecase.root),
+        Err(E::OnlyFileAsEntry(_))
+    ));
+    assert!(matches!(
+        Options::new().file(usecase.root.join("missing.file")),
+        Err(E::OnlyFileAsEntry(_))
+    ));
+    usecase.clean()?;
+    Ok(())
+}
@@ -0,0 +1,109 @@
+use crate::{
+    hasher, reader,
+    test::usecase::*,
+    walker::{HasherKind, ReaderKind},
+    Options, E,
+};
+
+#[test]
+fn same_as_generic() -> Result<(), E> {
+    let usecase = UseCase::unnamed(2, 4, 2, &[])?;
+    let mut walker = Options::from(&usecase.root)?.walker()?;
+    let expected = walker
+        .collect()?
+        .hash::<hasher::blake::Blake, reader::mapping::Mapping>()?
+        .to_vec();
+    let mut walker = Options::from(&usecase.root)?
+        .hasher("blake".parse()?)
+        .reader("mapping".parse()?)
+        .walker()?;
+    assert_eq!(walker.collect()?.hash_dyn()?, expected);
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn custom_hasher() -> Result<(), E> {
+    let usecase = UseCase::unnamed(2, 4, 2, &[])?;
+    let mut walker = Options::from(&usecase.root)?.walker()?;
+    let expected = walker
+        .collect()?
+        .hash::<hasher::blake::Blake, reader::buffering::Buffering>()?
+        .to_vec();
+    let mut walker = Options::from(&usecase.root)?
+        .register_hasher::<hasher::blake::Blake, _>("my-hasher")
+        .hasher("my-hasher".parse()?)
+        .walker()?;
+    assert_eq!(walker.collect()?.hash_dyn()?, expected);
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn unknown_hasher() -> Result<(), E> {
+    let usecase = UseCase::unnamed(1, 2, 0, &[])?;
+    let mut walker = Options::from(&usecase.root)?
+        .hasher(HasherKind::Custom(String::from("unregistered")))
+        .walker()?;
+    assert!(matches!(
+        walker.collect()?.hash_dyn(),
+        Err(E::UnknownHasher(name)) if name == "unregistered"
+    ));
+    assert!(matches!(
+        "unknown".parse::<ReaderKind>(),
+        Err(E::UnknownReader(_))
+    ));
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn names() -> Result<(), E> {
+    let names: &[&str] = &[
+        "blake",
+        #[cfg(feature = "use_sha2")]
+        "sha256",
+        #[cfg(feature = "use_sha2")]
+        "sha512",
+        #[cfg(feature = "use_sha1")]
+        "sha1",
+        #[cfg(feature = "use_md5")]
+        "md5",
+        #[cfg(feature = "use_xxh3")]
+        "xxh3-64",
+        #[cfg(feature = "use_xxh3")]
+        "xxh3-128",
+        #[cfg(feature = "use_crc32c")]
+        "crc32c",
+    ];
+    for name in names.iter() {
+        let kind: HasherKind = name.parse()?;
+        assert!(!matches!(kind, HasherKind::Custom(_)));
+        assert_eq!(&kind.to_string(), name);
+    }
+    for name in ["buffering", "mapping", "md"] {
+        assert_eq!(name.parse::<ReaderKind>()?.to_string(), name);
+    }
+    Ok(())
+}
+
+#[test]
+fn disabled_feature() -> Result<(), E> {
+    let parsed = "sha256".parse::<HasherKind>();
+    #[cfg(feature = "use_sha2")]
+    assert_eq!(parsed?, HasherKind::Sha256);
+    #[cfg(not(feature = "use_sha2"))]
+    assert!(matches!(
+        parsed,
+        Err(E::DisabledHasher(name, feature)) if name == "sha256" && feature == "use_sha2"
+    ));
+    let parsed = "xxh3-64".parse::<HasherKind>();
+    #[cfg(feature = "use_xxh3")]
+    assert_eq!(parsed?, HasherKind::Xxh3_64);
+    #[cfg(not(feature = "use_xxh3"))]
+    assert!(matches!(
+        parsed,
+        Err(E::DisabledHasher(name, feature)) if name == "xxh3-64" && feature == "use_xxh3"
+    ));
+    Ok(())
+}
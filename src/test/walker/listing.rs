@@ -0,0 +1,227 @@
+use std::{fs::create_dir, path::Path};
+
+use crate::{
+    hasher, reader,
+    test::{usecase::*, utils},
+    walker::{FileKey, Listed, Origin},
+    Entry, Filter, Options, E,
+};
+
+#[test]
+fn sorted() -> Result<(), E> {
+    let first = UseCaseEmpty::gen()?;
+    let second = UseCaseEmpty::gen()?;
+    let extra = UseCaseEmpty::gen()?;
+    create_dir(first.root.join("a"))?;
+    // Byte-wise order: uppercase letters go before lowercase ones, "-" goes before "/"
+    for (root, rel, content) in [
+        (&first.root, "b.txt", "bb"),
+        (&first.root, "a/z.txt", "z"),
+        (&first.root, "a-b.txt", "ab"),
+        (&first.root, "B.txt", "B"),
+        (&first.root, "skip.log", "log"),
+        (&second.root, "c.txt", "ccc"),
+    ] {
+        utils::create_text_file(root.join(rel), content)?;
+    }
+    let file = extra.root.join("file.bin");
+    utils::create_text_file(&file, "file")?;
+    let mut walker = Options::new()
+        .file(&file)?
+        .entry(Entry::from(&second.root)?)?
+        .entry(Entry::from(&first.root)?)?
+        .exclude(Filter::Files("*.log"))?
+        .walker()?;
+    let listed = walker.list()?;
+    assert_eq!(
+        listed,
+        vec![
+            Listed {
+                key: FileKey {
+                    origin: Origin::Entry(0),
+                    relative: b"c.txt".to_vec(),
+                },
+                path: second.root.join("c.txt"),
+                size: Some(3),
+            },
+            Listed {
+                key: FileKey {
+                    origin: Origin::Entry(1),
+                    relative: b"B.txt".to_vec(),
+                },
+                path: first.root.join("B.txt"),
+                size: Some(1),
+            },
+            Listed {
+                key: FileKey {
+                    origin: Origin::Entry(1),
+                    relative: b"a-b.txt".to_vec(),
+                },
+                path: first.root.join("a-b.txt"),
+                size: Some(2),
+            },
+            Listed {
+                key: FileKey {
+                    origin: Origin::Entry(1),
+                    relative: b"a/z.txt".to_vec(),
+                },
+                path: first.root.join("a").join("z.txt"),
+                size: Some(1),
+            },
+            Listed {
+                key: FileKey {
+                    origin: Origin::Entry(1),
+                    relative: b"b.txt".to_vec(),
+                },
+                path: first.root.join("b.txt"),
+                size: Some(2),
+            },
+            Listed {
+                key: FileKey {
+                    origin: Origin::File(0),
+                    relative: b"file.bin".to_vec(),
+                },
+                path: file.clone(),
+                size: Some(4),
+            },
+        ]
+    );
+    // The order doesn't depend on the order of collecting
+    for _ in 0..5 {
+        assert_eq!(walker.list()?, listed);
+    }
+    first.clean()?;
+    second.clean()?;
+    extra.clean()?;
+    Ok(())
+}
+
+#[test]
+fn hash_after_list() -> Result<(), E> {
+    let usecase = UseCase::unnamed(2, 4, 2, &[])?;
+    let mut walker = Options::from(&usecase.root)?.walker()?;
+    let listed = walker.list()?;
+    assert_eq!(listed.len(), usecase.files.len());
+    assert!(listed.iter().all(|file| usecase.files.contains(&file.path)));
+    // Files aren't hashed by listing
+    assert!(walker.iter().all(|(_, state)| state.is_none()));
+    let hash = walker
+        .hash::<hasher::blake::Blake, reader::buffering::Buffering>()?
+        .to_vec();
+    let mut walker = Options::from(&usecase.root)?.walker()?;
+    let expected = walker
+        .collect()?
+        .hash::<hasher::blake::Blake, reader::buffering::Buffering>()?
+        .to_vec();
+    assert_eq!(hash, expected);
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn portable() -> Result<(), E> {
+    let first = UseCaseEmpty::gen()?;
+    let second = UseCaseEmpty::gen()?;
+    for root in [&first.root, &second.root] {
+        create_dir(root.join("sub"))?;
+        for (rel, content) in [("b.txt", "b"), ("a.txt", "a"), ("sub/c.txt", "c")] {
+            utils::create_text_file(root.join(rel), content)?;
+        }
+    }
+    let results = |root: &Path| -> Result<Vec<(FileKey, Vec<u8>)>, E> {
+        let mut walker = Options::new()
+            .entry(Entry::from(root)?)?
+            // Nested entry: files of "sub" are listed once, relative to "sub"
+            .entry(Entry::from(root.join("sub"))?)?
+            .walker()?;
+        walker
+            .collect()?
+            .hash::<hasher::blake::Blake, reader::buffering::Buffering>()?;
+        assert_eq!(
+            walker.key(root.join("sub").join("c.txt")),
+            Some(FileKey {
+                origin: Origin::Entry(1),
+                relative: b"c.txt".to_vec(),
+            })
+        );
+        let mut results = Vec::new();
+        for (key, state) in walker.results()? {
+            let Some(Ok(hash)) = state else {
+                panic!("File {key} should be hashed");
+            };
+            results.push((key, hash.to_owned()));
+        }
+        Ok(results)
+    };
+    let a = results(&first.root)?;
+    let b = results(&second.root)?;
+    assert_eq!(
+        a.iter()
+            .map(|(key, _)| key.to_string())
+            .collect::<Vec<String>>(),
+        vec!["0:a.txt", "0:b.txt", "1:c.txt"]
+    );
+    // Results of two copies of the same tree in different folders are equal
+    assert_eq!(a, b);
+    first.clean()?;
+    second.clean()?;
+    Ok(())
+}
+
+#[test]
+fn files_with_same_name() -> Result<(), E> {
+    let first = UseCaseEmpty::gen()?;
+    let second = UseCaseEmpty::gen()?;
+    let a = first.root.join("x.txt");
+    let b = second.root.join("x.txt");
+    utils::create_text_file(&a, "a")?;
+    utils::create_text_file(&b, "b")?;
+    let mut walker = Options::new().file(&a)?.file(&b)?.walker()?;
+    walker
+        .collect()?
+        .hash::<hasher::blake::Blake, reader::buffering::Buffering>()?;
+    let results = walker.results()?;
+    assert_eq!(walker.iter().count(), 2);
+    assert_eq!(
+        results
+            .keys()
+            .map(|key| key.to_string())
+            .collect::<Vec<String>>(),
+        vec!["f0:x.txt", "f1:x.txt"]
+    );
+    assert_eq!(
+        walker.key(&b),
+        Some(FileKey {
+            origin: Origin::File(1),
+            relative: b"x.txt".to_vec(),
+        })
+    );
+    first.clean()?;
+    second.clean()?;
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn non_utf8_names() -> Result<(), E> {
+    use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+    let usecase = UseCaseEmpty::gen()?;
+    // Both names are lossily converted to "a\u{FFFD}"
+    for name in [b"a\xff".as_slice(), b"a\xfe".as_slice(), b"a~".as_slice()] {
+        utils::create_text_file(usecase.root.join(OsStr::from_bytes(name)), "a")?;
+    }
+    let mut walker = Options::from(&usecase.root)?.walker()?;
+    let listed: Vec<Vec<u8>> = walker
+        .list()?
+        .into_iter()
+        .map(|file| file.key.relative)
+        .collect();
+    assert_eq!(
+        listed,
+        vec![b"a~".to_vec(), b"a\xfe".to_vec(), b"a\xff".to_vec()]
+    );
+    walker.hash::<hasher::blake::Blake, reader::buffering::Buffering>()?;
+    assert_eq!(walker.results()?.len(), 3);
+    usecase.clean()?;
+    Ok(())
+}
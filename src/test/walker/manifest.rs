@@ -0,0 +1,159 @@
+use std::{
+    env::temp_dir,
+    fs::{read_to_string, remove_file},
+};
+use uuid::Uuid;
+
+use crate::{
+    hasher, reader,
+    test::{usecase::*, utils::create_text_file},
+    walker::{
+        manifest::{from_hex, to_hex},
+        Manifest, ManifestFormat,
+    },
+    Options, Walker, E,
+};
+
+fn walk(usecase: &UseCase) -> Result<Walker, E> {
+    let mut walker = Options::from(&usecase.root)?.walker()?;
+    walker
+        .collect()?
+        .hash::<hasher::blake::Blake, reader::buffering::Buffering>()?;
+    Ok(walker)
+}
+
+#[test]
+fn hex() {
+    assert_eq!(to_hex(&[0, 15, 255]), "000fff");
+    assert_eq!(from_hex("000fFF"), Some(vec![0, 15, 255]));
+    assert_eq!(from_hex("0"), None);
+    assert_eq!(from_hex("zz"), None);
+}
+
+#[test]
+fn text() -> Result<(), E> {
+    let usecase = UseCase::unnamed(2, 4, 2, &[])?;
+    let walker = walk(&usecase)?;
+    let filename = temp_dir().join(format!("{}.b3", Uuid::new_v4()));
+    walker.manifest().save(&filename, ManifestFormat::Text)?;
+    let manifest = Manifest::load(&filename)?;
+    assert_eq!(manifest.files.len(), usecase.files.len());
+    assert!(manifest.compare(&walk(&usecase)?).is_same());
+    usecase.change(1)?;
+    let changes = manifest.compare(&walk(&usecase)?);
+    assert_eq!(changes.modified.len(), 1);
+    remove_file(filename)?;
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn text_relative_paths() -> Result<(), E> {
+    let usecase = UseCase::unnamed(2, 4, 2, &[])?;
+    let walker = walk(&usecase)?;
+    let filename = usecase.root.join("checksums.b3");
+    walker.manifest().save(&filename, ManifestFormat::Text)?;
+    let content = read_to_string(&filename)?;
+    assert!(!content.contains(&usecase.root.to_string_lossy().to_string()));
+    let manifest = Manifest::load(&filename)?;
+    for path in usecase.files.iter() {
+        assert!(manifest.get(path).is_some());
+    }
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn text_sha256sum_compatibility() -> Result<(), E> {
+    let usecase = UseCaseEmpty::gen()?;
+    let filename = usecase.root.join("checksums.sha256");
+    create_text_file(
+        &filename,
+        "0011  a.txt\n\
+         2233 *b.bin\n\
+         \\4455  c\\\\d\\nx.txt\n\
+         \n",
+    )?;
+    let manifest = Manifest::load(&filename)?;
+    assert_eq!(manifest.files.len(), 3);
+    assert_eq!(
+        manifest.get(usecase.root.join("a.txt")),
+        Some(&[0x00, 0x11][..])
+    );
+    assert_eq!(
+        manifest.get(usecase.root.join("b.bin")),
+        Some(&[0x22, 0x33][..])
+    );
+    assert_eq!(
+        manifest.get(usecase.root.join("c\\d\nx.txt")),
+        Some(&[0x44, 0x55][..])
+    );
+    create_text_file(&filename, "0011 a.txt\n")?;
+    assert!(Manifest::load(&filename).is_err());
+    usecase.clean()?;
+    Ok(())
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn json() -> Result<(), E> {
+    let usecase = UseCase::unnamed(2, 4, 2, &[])?;
+    let walker = walk(&usecase)?;
+    let filename = temp_dir().join(format!("{}.json", Uuid::new_v4()));
+    walker.manifest().save(&filename, ManifestFormat::Json)?;
+    let manifest = Manifest::load(&filename)?;
+    assert_eq!(manifest.files, walker.manifest().files);
+    usecase.remove(1)?;
+    let changes = manifest.compare(&walk(&usecase)?);
+    assert_eq!(changes.removed.len(), 1);
+    remove_file(filename)?;
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn text_escaping() -> Result<(), E> {
+    let usecase = UseCaseEmpty::gen()?;
+    let filename = usecase.root.join("checksums.b3");
+    let manifest = Manifest {
+        files: vec![
+            (usecase.root.join("a\\b\nc\rd.txt"), vec![0x00, 0x11]),
+            (usecase.root.join("e.txt"), vec![0x22, 0x33]),
+        ],
+    };
+    manifest.save(&filename, ManifestFormat::Text)?;
+    // Escaping is the same as GNU coreutils use
+    assert_eq!(
+        read_to_string(&filename)?,
+        "\\0011  a\\\\b\\nc\\rd.txt\n2233  e.txt\n"
+    );
+    assert_eq!(Manifest::load(&filename)?.files, manifest.files);
+    usecase.clean()?;
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn non_utf8_paths() -> Result<(), E> {
+    use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+    let usecase = UseCaseEmpty::gen()?;
+    let filename = usecase.root.join("checksums.b3");
+    let path = usecase.root.join(OsStr::from_bytes(b"invalid\xff.txt"));
+    let manifest = Manifest {
+        files: vec![(path.clone(), vec![0x00])],
+    };
+    // The path isn't rewritten lossily
+    assert!(matches!(
+        manifest.save(&filename, ManifestFormat::Text),
+        Err(E::NonUtf8Path(invalid)) if invalid == path
+    ));
+    assert!(!filename.exists());
+    #[cfg(feature = "serde")]
+    assert!(matches!(
+        manifest.save(&filename, ManifestFormat::Json),
+        Err(E::NonUtf8Path(_))
+    ));
+    usecase.clean()?;
+    Ok(())
+}
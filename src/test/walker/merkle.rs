@@ -0,0 +1,168 @@
+use std::{
+    fs::{create_dir_all, rename, write},
+    path::Path,
+};
+
+use crate::{
+    hasher, reader,
+    test::{usecase::*, utils},
+    walker::Walker,
+    Entry, Options, SummaryMode, E,
+};
+
+fn walker(root: &Path) -> Result<Walker, E> {
+    let mut walker = Options::from(root)?.summary(SummaryMode::Merkle).walker()?;
+    walker
+        .collect()?
+        .hash::<hasher::blake::Blake, reader::buffering::Buffering>()?;
+    Ok(walker)
+}
+
+fn fill(root: &Path) -> Result<(), E> {
+    for folder in ["a/x", "a/y", "b/x"] {
+        create_dir_all(root.join(folder))?;
+        utils::create_text_file(root.join(folder).join("f.txt"), "content")?;
+        utils::create_text_file(root.join(folder).join("g.txt"), folder)?;
+    }
+    Ok(())
+}
+
+#[test]
+fn entry_hash_is_summary() -> Result<(), E> {
+    let usecase = UseCase::unnamed(3, 4, 2, &[])?;
+    let mut walker = Options::from(&usecase.root)?
+        .summary(SummaryMode::Merkle)
+        .walker()?;
+    let summary = walker
+        .collect()?
+        .hash::<hasher::blake::Blake, reader::buffering::Buffering>()?
+        .to_vec();
+    assert!(!summary.is_empty());
+    assert_eq!(walker.dir_hash(&usecase.root), Some(summary.as_slice()));
+    for file in usecase.files.iter() {
+        let Some(parent) = file.parent() else {
+            continue;
+        };
+        assert!(walker.dir_hash(parent).is_some());
+    }
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn localized_changes() -> Result<(), E> {
+    let usecase = UseCaseEmpty::gen()?;
+    let root = &usecase.root;
+    fill(root)?;
+    let before = walker(root)?;
+    write(root.join("a/x/f.txt"), "changed")?;
+    let after = walker(root)?;
+    for changed in ["", "a", "a/x"] {
+        assert_ne!(
+            before.dir_hash(root.join(changed)),
+            after.dir_hash(root.join(changed))
+        );
+    }
+    for unchanged in ["a/y", "b", "b/x"] {
+        assert!(after.dir_hash(root.join(unchanged)).is_some());
+        assert_eq!(
+            before.dir_hash(root.join(unchanged)),
+            after.dir_hash(root.join(unchanged))
+        );
+    }
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn names_are_included() -> Result<(), E> {
+    let usecase = UseCaseEmpty::gen()?;
+    let root = &usecase.root;
+    fill(root)?;
+    let before = walker(root)?;
+    rename(root.join("a/y/f.txt"), root.join("a/y/h.txt"))?;
+    let after = walker(root)?;
+    assert_ne!(
+        before.dir_hash(root.join("a/y")),
+        after.dir_hash(root.join("a/y"))
+    );
+    assert_eq!(
+        before.dir_hash(root.join("b")),
+        after.dir_hash(root.join("b"))
+    );
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn flat_mode() -> Result<(), E> {
+    let usecase = UseCaseEmpty::gen()?;
+    fill(&usecase.root)?;
+    let mut walker = Options::from(&usecase.root)?.walker()?;
+    walker
+        .collect()?
+        .hash::<hasher::blake::Blake, reader::buffering::Buffering>()?;
+    assert!(walker.dir_hash(&usecase.root).is_none());
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn nested_entries() -> Result<(), E> {
+    let usecase = UseCaseEmpty::gen()?;
+    let other = UseCaseEmpty::gen()?;
+    let root = &usecase.root;
+    fill(root)?;
+    let file = other.root.join("file.txt");
+    utils::create_text_file(&file, "file")?;
+    let single = walker(root)?;
+    let mut nested = Options::new()
+        .entry(Entry::from(root)?)?
+        .entry(Entry::from(root.join("a"))?)?
+        .file(&file)?
+        .summary(SummaryMode::Merkle)
+        .walker()?;
+    nested
+        .collect()?
+        .hash::<hasher::blake::Blake, reader::buffering::Buffering>()?;
+    // Hashes of folders don't depend on entries: the outer entry covers the inner one
+    for folder in ["", "a", "a/x", "a/y", "b", "b/x"] {
+        assert!(single.dir_hash(root.join(folder)).is_some());
+        assert_eq!(
+            single.dir_hash(root.join(folder)),
+            nested.dir_hash(root.join(folder))
+        );
+    }
+    // A file added explicitly belongs to its folder only
+    assert!(nested.dir_hash(&other.root).is_some());
+    assert!(other
+        .root
+        .ancestors()
+        .skip(1)
+        .all(|folder| nested.dir_hash(folder).is_none()));
+    usecase.clean()?;
+    other.clean()?;
+    Ok(())
+}
+
+#[cfg(feature = "tracking")]
+#[test]
+fn tracking_key() -> Result<(), E> {
+    let usecase = UseCase::unnamed(1, 1, 0, &[])?;
+    let plain = Options::from(&usecase.root)?.hash();
+    // The default mode doesn't change the key, so previous records stay valid
+    assert_eq!(
+        plain,
+        Options::from(&usecase.root)?
+            .summary(SummaryMode::Flat)
+            .hash()
+    );
+    assert_ne!(
+        plain,
+        Options::from(&usecase.root)?
+            .summary(SummaryMode::Merkle)
+            .hash()
+    );
+    usecase.clean()?;
+    Ok(())
+}
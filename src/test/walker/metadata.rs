@@ -0,0 +1,156 @@
+use std::{
+    fs::{metadata, remove_file, set_permissions},
+    os::unix::fs::{symlink, PermissionsExt},
+    path::Path,
+};
+
+use crate::{
+    hasher, reader,
+    test::{usecase::*, utils},
+    MetaField, Options, E,
+};
+
+fn hash(root: &Path, fields: &[MetaField]) -> Result<Vec<u8>, E> {
+    let mut opt = Options::from(root)?;
+    for field in fields.iter() {
+        opt = opt.metadata(*field);
+    }
+    let mut walker = opt.walker()?;
+    Ok(walker
+        .collect()?
+        .hash::<hasher::blake::Blake, reader::buffering::Buffering>()?
+        .to_vec())
+}
+
+fn chmod(path: &Path, mode: u32) -> Result<(), E> {
+    let mut permissions = metadata(path)?.permissions();
+    permissions.set_mode(mode);
+    set_permissions(path, permissions)?;
+    Ok(())
+}
+
+#[test]
+fn mode() -> Result<(), E> {
+    let usecase = UseCase::unnamed(2, 4, 2, &[])?;
+    chmod(&usecase.files[0], 0o644)?;
+    let without = hash(&usecase.root, &[])?;
+    let with = hash(&usecase.root, &[MetaField::Mode])?;
+    assert_ne!(without, with);
+    chmod(&usecase.files[0], 0o755)?;
+    // Content isn't changed, so only the hash with mixed mode is changed
+    assert_eq!(without, hash(&usecase.root, &[])?);
+    assert_ne!(with, hash(&usecase.root, &[MetaField::Mode])?);
+    chmod(&usecase.files[0], 0o644)?;
+    assert_eq!(with, hash(&usecase.root, &[MetaField::Mode])?);
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn symlink_target() -> Result<(), E> {
+    let usecase = UseCaseEmpty::gen()?;
+    let a = usecase.root.join("a.txt");
+    let b = usecase.root.join("b.txt");
+    let link = usecase.root.join("link");
+    utils::create_text_file(&a, "same")?;
+    utils::create_text_file(&b, "same")?;
+    symlink(&a, &link)?;
+    let without = hash(&usecase.root, &[])?;
+    let with = hash(&usecase.root, &[MetaField::SymlinkTarget])?;
+    assert_ne!(without, with);
+    remove_file(&link)?;
+    symlink(&b, &link)?;
+    assert_eq!(without, hash(&usecase.root, &[])?);
+    assert_ne!(with, hash(&usecase.root, &[MetaField::SymlinkTarget])?);
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn order_of_fields() -> Result<(), E> {
+    let usecase = UseCase::unnamed(2, 4, 2, &[])?;
+    assert_eq!(
+        hash(&usecase.root, &[MetaField::Mode, MetaField::Owner])?,
+        hash(
+            &usecase.root,
+            &[MetaField::Owner, MetaField::Mode, MetaField::Owner]
+        )?
+    );
+    let opt = Options::new()
+        .metadata(MetaField::SymlinkTarget)
+        .metadata(MetaField::Mode)
+        .metadata(MetaField::Mode);
+    assert_eq!(
+        opt.metadata,
+        vec![MetaField::Mode, MetaField::SymlinkTarget]
+    );
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn cache_keeps_contents() -> Result<(), E> {
+    let usecase = UseCase::unnamed(2, 4, 2, &[])?;
+    chmod(&usecase.files[0], 0o644)?;
+    let mut walker = Options::from(&usecase.root)?
+        .cache(true)
+        .metadata(MetaField::Mode)
+        .walker()?;
+    let before = walker
+        .collect()?
+        .hash::<hasher::blake::Blake, reader::buffering::Buffering>()?
+        .to_vec();
+    chmod(&usecase.files[0], 0o755)?;
+    let after = walker
+        .collect()?
+        .hash::<hasher::blake::Blake, reader::buffering::Buffering>()?
+        .to_vec();
+    assert_ne!(before, after);
+    usecase.clean()?;
+    Ok(())
+}
+
+#[cfg(feature = "xattrs")]
+#[test]
+fn xattrs() -> Result<(), E> {
+    let usecase = UseCase::unnamed(2, 4, 2, &[])?;
+    let with = hash(&usecase.root, &[MetaField::Xattrs])?;
+    // Extended attributes might be not supported by the file system of temporary folder
+    if xattr::set(&usecase.files[0], "user.fshasher", b"value").is_ok() {
+        assert_ne!(with, hash(&usecase.root, &[MetaField::Xattrs])?);
+        xattr::remove(&usecase.files[0], "user.fshasher")?;
+        assert_eq!(with, hash(&usecase.root, &[MetaField::Xattrs])?);
+    }
+    usecase.clean()?;
+    Ok(())
+}
+
+#[cfg(feature = "tracking")]
+#[test]
+fn tracking_key() -> Result<(), E> {
+    let usecase = UseCase::unnamed(1, 1, 0, &[])?;
+    let plain = Options::from(&usecase.root)?.hash();
+    let mode = Options::from(&usecase.root)?
+        .metadata(MetaField::Mode)
+        .hash();
+    assert_ne!(plain, mode);
+    // The order of fields doesn't matter
+    assert_eq!(
+        Options::from(&usecase.root)?
+            .metadata(MetaField::Owner)
+            .metadata(MetaField::Mode)
+            .hash(),
+        Options::from(&usecase.root)?
+            .metadata(MetaField::Mode)
+            .metadata(MetaField::Owner)
+            .hash()
+    );
+    assert_ne!(
+        mode,
+        Options::from(&usecase.root)?
+            .metadata(MetaField::Owner)
+            .hash()
+    );
+    usecase.clean()?;
+    Ok(())
+}
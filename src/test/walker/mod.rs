@@ -1,8 +1,23 @@
+#[cfg(feature = "tokio")]
+mod asynchronous;
+mod cache;
 mod cancellation;
 mod changed_dest;
 mod context;
+mod files;
+mod kind;
+mod listing;
+mod manifest;
+mod merkle;
+#[cfg(unix)]
+mod metadata;
 mod progress;
 mod stratagies;
+mod stream;
+mod summary;
+mod verify;
+#[cfg(all(feature = "watcher", target_os = "linux"))]
+mod watcher;
 use std::env::temp_dir;
 
 use crate::{
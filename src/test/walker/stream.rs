@@ -0,0 +1,93 @@
+use std::{collections::HashSet, fs::remove_file, path::PathBuf};
+
+use crate::{collector::Tolerance, hasher, reader, test::usecase::*, Options, E};
+
+#[test]
+fn same_as_hash() -> Result<(), E> {
+    let usecase = UseCase::unnamed(3, 5, 2, &[])?;
+    let mut walker = Options::from(&usecase.root)?.walker()?;
+    let expected = walker
+        .collect()?
+        .hash::<hasher::blake::Blake, reader::buffering::Buffering>()?
+        .to_vec();
+    walker.collect()?;
+    let mut stream = walker.hash_stream::<hasher::blake::Blake, reader::buffering::Buffering>()?;
+    let mut streamed: HashSet<PathBuf> = HashSet::new();
+    for (path, result) in stream.by_ref() {
+        assert!(result.is_ok());
+        // Each file is yielded only once
+        assert!(streamed.insert(path));
+    }
+    assert_eq!(stream.finish()?, expected);
+    assert_eq!(streamed.len(), usecase.files.len());
+    // Results are stored in Walker as well
+    assert_eq!(walker.iter().count(), usecase.files.len());
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn errors_streamed() -> Result<(), E> {
+    let usecase = UseCase::unnamed(2, 4, 2, &[])?;
+    let mut walker = Options::from(&usecase.root)?
+        .tolerance(Tolerance::LogErrors)
+        .walker()?;
+    walker.collect()?;
+    let removed = &usecase.files[0];
+    remove_file(removed)?;
+    let mut stream = walker.hash_stream::<hasher::blake::Blake, reader::buffering::Buffering>()?;
+    let failed: Vec<PathBuf> = stream
+        .by_ref()
+        .filter_map(|(path, result)| result.is_err().then_some(path))
+        .collect();
+    assert!(!stream.finish()?.is_empty());
+    assert_eq!(failed, vec![removed.to_owned()]);
+    // Errors are moved into the stream
+    assert!(!walker.iter().any(|(p, _)| p == removed));
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn stop_on_errors() -> Result<(), E> {
+    let usecase = UseCase::unnamed(2, 4, 2, &[])?;
+    let mut walker = Options::from(&usecase.root)?
+        .tolerance(Tolerance::StopOnErrors)
+        .walker()?;
+    walker.collect()?;
+    remove_file(&usecase.files[0])?;
+    let stream = walker.hash_stream::<hasher::blake::Blake, reader::buffering::Buffering>()?;
+    assert!(stream.finish().is_err());
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn drop_before_end() -> Result<(), E> {
+    let usecase = UseCase::unnamed(3, 5, 2, &[])?;
+    let mut walker = Options::from(&usecase.root)?.walker()?;
+    walker.collect()?;
+    let mut stream = walker.hash_stream::<hasher::blake::Blake, reader::buffering::Buffering>()?;
+    assert!(stream.next().is_some());
+    drop(stream);
+    // Walker is still usable after dropping the stream
+    let hash = walker
+        .collect()?
+        .hash::<hasher::blake::Blake, reader::buffering::Buffering>()?
+        .to_vec();
+    assert!(!hash.is_empty());
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn empty() -> Result<(), E> {
+    let usecase = UseCase::unnamed(1, 0, 0, &[])?;
+    let mut walker = Options::from(&usecase.root)?.walker()?;
+    walker.collect()?;
+    let mut stream = walker.hash_stream::<hasher::blake::Blake, reader::buffering::Buffering>()?;
+    assert!(stream.next().is_none());
+    assert!(stream.finish()?.is_empty());
+    usecase.clean()?;
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use std::fs::{create_dir, rename};
+
+use crate::{hasher, reader, test::usecase::*, Options, E};
+
+fn summary(usecase: &UseCase, paths_in_summary: bool) -> Result<Vec<u8>, E> {
+    let mut walker = Options::from(&usecase.root)?
+        .paths_in_summary(paths_in_summary)
+        .walker()?;
+    Ok(walker
+        .collect()?
+        .hash::<hasher::blake::Blake, reader::buffering::Buffering>()?
+        .to_vec())
+}
+
+#[test]
+fn renaming() -> Result<(), E> {
+    let usecase = UseCase::unnamed(2, 4, 2, &[])?;
+    let before = summary(&usecase, true)?;
+    assert_eq!(before, summary(&usecase, true)?);
+    let file = &usecase.files[0];
+    rename(file, file.with_file_name("renamed.file"))?;
+    assert_ne!(before, summary(&usecase, true)?);
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn moving() -> Result<(), E> {
+    let usecase = UseCase::unnamed(2, 4, 2, &[])?;
+    let before = summary(&usecase, true)?;
+    let file = &usecase.files[0];
+    let folder = usecase.root.join("moved");
+    create_dir(&folder)?;
+    rename(file, folder.join(file.file_name().unwrap()))?;
+    assert_ne!(before, summary(&usecase, true)?);
+    usecase.clean()?;
+    Ok(())
+}
+
+#[cfg(feature = "tracking")]
+#[test]
+fn tracking_key() -> Result<(), E> {
+    let usecase = UseCase::unnamed(1, 1, 0, &[])?;
+    let plain = Options::from(&usecase.root)?.hash();
+    // The default value doesn't change the key, so previous records stay valid
+    assert_eq!(
+        plain,
+        Options::from(&usecase.root)?.paths_in_summary(false).hash()
+    );
+    assert_ne!(
+        plain,
+        Options::from(&usecase.root)?.paths_in_summary(true).hash()
+    );
+    usecase.clean()?;
+    Ok(())
+}
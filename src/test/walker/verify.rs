@@ -0,0 +1,157 @@
+use std::fs::{remove_file, write};
+
+use crate::{
+    collector::Tolerance,
+    hasher, reader,
+    test::{usecase::*, utils},
+    walker::{ManifestFormat, Verdict},
+    Options, E,
+};
+
+fn save_manifest(usecase: &UseCase) -> Result<std::path::PathBuf, E> {
+    let mut walker = Options::from(&usecase.root)?.walker()?;
+    walker
+        .collect()?
+        .hash::<hasher::blake::Blake, reader::buffering::Buffering>()?;
+    let manifest = usecase.root.join("checksums.b3");
+    walker.manifest().save(&manifest, ManifestFormat::Text)?;
+    Ok(manifest)
+}
+
+#[test]
+fn same_dest() -> Result<(), E> {
+    let usecase = UseCase::unnamed(2, 4, 2, &[])?;
+    let manifest = save_manifest(&usecase)?;
+    let mut walker = Options::from(&usecase.root)?.walker()?;
+    let verification =
+        walker.verify::<hasher::blake::Blake, reader::buffering::Buffering, _>(&manifest)?;
+    assert!(verification.is_ok());
+    assert_eq!(verification.files.len(), usecase.files.len());
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn verdicts() -> Result<(), E> {
+    let usecase = UseCase::unnamed(2, 4, 2, &[])?;
+    let manifest = save_manifest(&usecase)?;
+    let changed = &usecase.files[0];
+    let removed = &usecase.files[1];
+    let added = usecase.root.join("added.file");
+    write(changed, "changed content")?;
+    remove_file(removed)?;
+    utils::create_text_file(&added, "added")?;
+    let mut walker = Options::from(&usecase.root)?.walker()?;
+    let verification =
+        walker.verify::<hasher::blake::Blake, reader::buffering::Buffering, _>(&manifest)?;
+    assert!(!verification.is_ok());
+    assert_eq!(verification.failed().count(), 3);
+    for (path, verdict) in verification.files.iter() {
+        if path == changed {
+            assert!(matches!(verdict, Verdict::Mismatch { .. }));
+        } else if path == removed {
+            assert!(matches!(verdict, Verdict::Missing));
+        } else if path == &added {
+            assert!(matches!(verdict, Verdict::Unexpected));
+        } else {
+            assert!(verdict.is_ok());
+        }
+    }
+    // Manifest file itself isn't reported
+    assert!(!verification.files.iter().any(|(p, _)| p == &manifest));
+    // Only listed files have been hashed
+    assert!(!walker.iter().any(|(p, _)| p == &added));
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn filters_applied_to_unexpected() -> Result<(), E> {
+    let usecase = UseCase::unnamed(2, 4, 2, &[])?;
+    let manifest = save_manifest(&usecase)?;
+    utils::create_text_file(usecase.root.join("added.tmp"), "added")?;
+    let mut walker = Options::from(&usecase.root)?
+        .exclude(crate::Filter::Files("*.tmp"))?
+        .walker()?;
+    let verification =
+        walker.verify::<hasher::blake::Blake, reader::buffering::Buffering, _>(&manifest)?;
+    assert!(verification.is_ok());
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn missing_no_tolerance() -> Result<(), E> {
+    let usecase = UseCase::unnamed(2, 4, 2, &[])?;
+    let manifest = save_manifest(&usecase)?;
+    remove_file(&usecase.files[0])?;
+    let mut walker = Options::from(&usecase.root)?
+        .tolerance(Tolerance::StopOnErrors)
+        .walker()?;
+    // Missing files are verdicts, not errors
+    let verification =
+        walker.verify::<hasher::blake::Blake, reader::buffering::Buffering, _>(&manifest)?;
+    assert_eq!(verification.failed().count(), 1);
+    usecase.clean()?;
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn dangling_symlink() -> Result<(), E> {
+    use std::os::unix::fs::symlink;
+
+    let usecase = UseCase::unnamed(2, 4, 2, &[])?;
+    let target = &usecase.files[0];
+    let link = usecase.root.join("link");
+    symlink(target, &link)?;
+    let manifest = save_manifest(&usecase)?;
+    remove_file(target)?;
+    let mut walker = Options::from(&usecase.root)?.walker()?;
+    let verification =
+        walker.verify::<hasher::blake::Blake, reader::buffering::Buffering, _>(&manifest)?;
+    assert_eq!(verification.failed().count(), 2);
+    for (path, verdict) in verification.failed() {
+        if path == &link {
+            // The symlink is still present, but its target cannot be read
+            assert!(matches!(verdict, Verdict::Unreadable(_)));
+        } else {
+            assert_eq!(path, target);
+            assert!(matches!(verdict, Verdict::Missing));
+        }
+    }
+    usecase.clean()?;
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn unreadable_folder() -> Result<(), E> {
+    use std::fs::{create_dir, read_dir, set_permissions, Permissions};
+    use std::os::unix::fs::PermissionsExt;
+
+    let usecase = UseCase::unnamed(2, 4, 2, &[])?;
+    let locked = usecase.root.join("locked");
+    create_dir(&locked)?;
+    utils::create_text_file(locked.join("file.txt"), "locked")?;
+    let manifest = save_manifest(&usecase)?;
+    set_permissions(&locked, Permissions::from_mode(0o000))?;
+    // Permissions are ignored with privileges (for example, for root)
+    if read_dir(&locked).is_ok() {
+        set_permissions(&locked, Permissions::from_mode(0o755))?;
+        usecase.clean()?;
+        return Ok(());
+    }
+    let mut walker = Options::from(&usecase.root)?.walker()?;
+    let verification =
+        walker.verify::<hasher::blake::Blake, reader::buffering::Buffering, _>(&manifest)?;
+    set_permissions(&locked, Permissions::from_mode(0o755))?;
+    assert_eq!(verification.failed().count(), 2);
+    for (path, verdict) in verification.failed() {
+        // The folder cannot be collected and the listed file cannot be hashed
+        assert!(path.starts_with(&locked));
+        assert!(matches!(verdict, Verdict::Unreadable(_)));
+    }
+    usecase.clean()?;
+    Ok(())
+}
@@ -0,0 +1,222 @@
+use std::{
+    fs::{create_dir, create_dir_all, remove_file, rename, write},
+    path::PathBuf,
+    sync::mpsc::Receiver,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    hasher, reader,
+    test::{
+        collector::git::{index, REGULAR},
+        usecase::*,
+        utils::create_text_file,
+    },
+    walker::{WatchEvent, Watcher},
+    Changes, ContextFile, Entry, Filter, GitAware, Options, E,
+};
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+fn watch(opt: Options) -> Result<(Watcher, Receiver<WatchEvent>), E> {
+    let mut watcher = opt
+        .walker()?
+        .watch::<hasher::blake::Blake, reader::buffering::Buffering>()?;
+    let events = watcher.events().expect("Channel is available");
+    Ok((watcher, events))
+}
+
+fn summary(opt: Options) -> Result<Vec<u8>, E> {
+    let mut walker = opt.walker()?;
+    Ok(walker
+        .collect()?
+        .hash::<hasher::blake::Blake, reader::buffering::Buffering>()?
+        .to_vec())
+}
+
+/// Receives events until the hash reported by watcher is equal to the expected one. Returns all
+/// received changes.
+fn wait_for(events: &Receiver<WatchEvent>, expected: &[u8]) -> Result<Changes, E> {
+    let started = Instant::now();
+    let mut changes = Changes::default();
+    while started.elapsed() < TIMEOUT {
+        let Ok(event) = events.recv_timeout(TIMEOUT) else {
+            break;
+        };
+        match event {
+            WatchEvent::Changed {
+                changes: mut next,
+                hash,
+            } => {
+                changes.added.append(&mut next.added);
+                changes.removed.append(&mut next.removed);
+                changes.modified.append(&mut next.modified);
+                if hash == expected {
+                    return Ok(changes);
+                }
+            }
+            WatchEvent::Error(err) => return Err(err),
+        }
+    }
+    panic!("Watcher hasn't reported expected hash");
+}
+
+#[test]
+fn initial_hash() -> Result<(), E> {
+    let usecase = UseCase::unnamed(2, 4, 2, &[])?;
+    let (watcher, _events) = watch(Options::from(&usecase.root)?)?;
+    assert_eq!(watcher.hash()?, summary(Options::from(&usecase.root)?)?);
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn modified_added_removed() -> Result<(), E> {
+    let usecase = UseCase::unnamed(2, 4, 2, &[])?;
+    let (watcher, events) = watch(Options::from(&usecase.root)?)?;
+    let modified = &usecase.files[0];
+    create_text_file(modified, "modified")?;
+    let expected = summary(Options::from(&usecase.root)?)?;
+    let changes = wait_for(&events, &expected)?;
+    assert_eq!(changes.modified, vec![modified.to_owned()]);
+    let removed = &usecase.files[1];
+    remove_file(removed)?;
+    let expected = summary(Options::from(&usecase.root)?)?;
+    let changes = wait_for(&events, &expected)?;
+    assert_eq!(changes.removed, vec![removed.to_owned()]);
+    // Files in new folders are considered as well
+    let folder = usecase.root.join("new_folder");
+    create_dir(&folder)?;
+    let added = folder.join("added.file");
+    create_text_file(&added, "added")?;
+    let expected = summary(Options::from(&usecase.root)?)?;
+    let changes = wait_for(&events, &expected)?;
+    assert_eq!(changes.added, vec![added]);
+    assert_eq!(watcher.hash()?, expected);
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn filtered_files_ignored() -> Result<(), E> {
+    let usecase = UseCase::unnamed(2, 4, 2, &[])?;
+    let opt =
+        || -> Result<Options, E> { Options::from(&usecase.root)?.exclude(Filter::Files("*.tmp")) };
+    let (watcher, events) = watch(opt()?)?;
+    let before = watcher.hash()?;
+    create_text_file(usecase.root.join("ignored.tmp"), "ignored")?;
+    let accepted = usecase.root.join("accepted.file");
+    create_text_file(&accepted, "accepted")?;
+    let expected = summary(opt()?)?;
+    assert_ne!(before, expected);
+    let changes = wait_for(&events, &expected)?;
+    assert_eq!(changes.added, vec![accepted]);
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn context_file_changed() -> Result<(), E> {
+    let usecase = UseCase::folders(&["aaa", "bbb"], 4, 1, &[])?;
+    let opt = || -> Result<Options, E> {
+        Options::new().entry(
+            Entry::new()
+                .entry(&usecase.root)?
+                .context(ContextFile::Ignore(".ignore")),
+        )
+    };
+    let (_watcher, events) = watch(opt()?)?;
+    create_text_file(usecase.root.join(".ignore"), "**/aaa")?;
+    let expected = summary(opt()?)?;
+    let changes = wait_for(&events, &expected)?;
+    assert!(!changes.removed.is_empty());
+    assert!(changes
+        .removed
+        .iter()
+        .all(|p: &PathBuf| p.to_string_lossy().contains("aaa")));
+    // .ignore file itself
+    assert_eq!(changes.added, vec![usecase.root.join(".ignore")]);
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn stop() -> Result<(), E> {
+    let usecase = UseCase::unnamed(2, 4, 2, &[])?;
+    let (mut watcher, events) = watch(Options::from(&usecase.root)?)?;
+    assert!(watcher.is_running());
+    watcher.stop();
+    assert!(!watcher.is_running());
+    create_text_file(&usecase.files[0], "modified")?;
+    assert!(events.recv_timeout(Duration::from_millis(200)).is_err());
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn nested_context_rules() -> Result<(), E> {
+    let usecase = UseCase::folders(&["aaa", "bbb"], 4, 1, &[])?;
+    create_text_file(usecase.root.join("aaa").join(".ignore"), "*.log")?;
+    let opt = || -> Result<Options, E> {
+        Options::new().entry(Entry::from(&usecase.root)?.context(ContextFile::Ignore(".ignore")))
+    };
+    let (_watcher, events) = watch(opt()?)?;
+    // Rules are applied to touched files without collecting paths again
+    for _ in 0..2 {
+        create_text_file(usecase.root.join("aaa").join("ignored.log"), "ignored")?;
+        create_text_file(usecase.root.join("bbb").join("accepted.log"), "accepted")?;
+    }
+    let expected = summary(opt()?)?;
+    let changes = wait_for(&events, &expected)?;
+    assert_eq!(
+        changes.added,
+        vec![usecase.root.join("bbb").join("accepted.log")]
+    );
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn git_index_changed() -> Result<(), E> {
+    let usecase = UseCaseEmpty::gen()?;
+    let root = &usecase.root;
+    create_text_file(root.join("a.txt"), "a")?;
+    create_text_file(root.join("b.txt"), "b")?;
+    create_dir_all(root.join(".git"))?;
+    write(
+        root.join(".git").join("index"),
+        index(2, &[(REGULAR, 0, "a.txt")]),
+    )?;
+    let opt = || -> Result<Options, E> {
+        Options::new().entry(Entry::from(root)?.git(GitAware::Tracked))
+    };
+    let (watcher, events) = watch(opt()?)?;
+    let before = watcher.hash()?;
+    // As "git add" does: the new index is written into a lock file and renamed
+    let lock = root.join(".git").join("index.lock");
+    write(
+        &lock,
+        index(2, &[(REGULAR, 0, "a.txt"), (REGULAR, 0, "b.txt")]),
+    )?;
+    rename(&lock, root.join(".git").join("index"))?;
+    let expected = summary(opt()?)?;
+    assert_ne!(before, expected);
+    let changes = wait_for(&events, &expected)?;
+    assert_eq!(changes.added, vec![root.join("b.txt")]);
+    usecase.clean()?;
+    Ok(())
+}
+
+#[test]
+fn idle_stop() -> Result<(), E> {
+    let usecase = UseCase::unnamed(2, 4, 2, &[])?;
+    let (mut watcher, _events) = watch(Options::from(&usecase.root)?)?;
+    // Watcher waits for events without a timeout, but stops right away
+    std::thread::sleep(Duration::from_millis(100));
+    let started = Instant::now();
+    watcher.stop();
+    assert!(started.elapsed() < Duration::from_secs(1));
+    assert!(!watcher.is_running());
+    usecase.clean()?;
+    Ok(())
+}
@@ -0,0 +1,217 @@
+use super::{progress::Tx, Tick, Walker, E};
+use crate::{breaker::Breaker, Hasher, Reader};
+use std::sync::Arc;
+use tokio::{
+    sync::{
+        mpsc::{channel, Receiver, Sender},
+        Mutex, MutexGuard, Semaphore,
+    },
+    task,
+};
+
+/// Aborts the operation with `Breaker`, if the future of the operation is dropped before the
+/// operation is done.
+struct AbortOnDrop(Option<Breaker>);
+
+impl AbortOnDrop {
+    fn disarm(mut self) {
+        self.0 = None;
+    }
+}
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        if let Some(breaker) = self.0.take() {
+            breaker.abort();
+        }
+    }
+}
+
+/// Async wrapper around `Walker`. Available with feature "tokio".
+///
+/// Collecting and hashing are done in tokio's blocking threads, so the async runtime isn't
+/// blocked. If the future of `collect()` or `hash()` is dropped (for example, because of
+/// `tokio::time::timeout` or `tokio::select!`), the operation is aborted with `Breaker` in the same
+/// way as with `Breaker::abort()`.
+///
+/// Progress ticks are delivered via an async channel, which can be taken with `progress()`.
+/// `Walker` sends ticks into this channel directly, so no threads are spent on delivering them.
+/// The channel is bounded by the capacity of progress set in `Options` (`0` means no limit). As
+/// soon as the channel is full, the operation waits until the receiver takes ticks, so the
+/// receiver should be drained while the operation is running. Until the channel is taken, ticks
+/// aren't delivered into it.
+///
+/// # Example
+///
+/// ```
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> Result<(), fshasher::E> {
+/// use fshasher::{hasher, reader, Options};
+/// use std::env::temp_dir;
+///
+/// let mut walker = Options::from(temp_dir())?
+///     .progress(10)
+///     .walker()?
+///     .into_async();
+/// let mut progress = walker.progress().unwrap();
+/// let ticks = tokio::spawn(async move {
+///     while let Some(tick) = progress.recv().await {
+///         println!("{tick}");
+///     }
+/// });
+/// let hash = walker
+///     .collect()
+///     .await?
+///     .hash::<hasher::blake::Blake, reader::buffering::Buffering>()
+///     .await?;
+/// println!("Hash of {}: {hash:?}", temp_dir().display());
+/// // The channel of progress is closed as soon as the walker is dropped
+/// drop(walker);
+/// ticks.await.unwrap();
+/// # Ok(())
+/// # }
+/// ```
+pub struct AsyncWalker {
+    walker: Arc<Mutex<Walker>>,
+    breaker: Breaker,
+    tx_progress: Sender<Tick>,
+    rx_progress: Option<Receiver<Tick>>,
+}
+
+impl AsyncWalker {
+    /// Creates a new instance of `AsyncWalker`.
+    ///
+    /// # Parameters
+    ///
+    /// - `walker`: An instance of `Walker`.
+    ///
+    /// # Returns
+    ///
+    /// - A new instance of `AsyncWalker`.
+    pub fn new(walker: Walker) -> Self {
+        // Tokio's channels cannot have zero capacity; the maximum one is used as "unbounded"
+        let capacity = match walker.opt.as_ref().and_then(|opt| opt.progress) {
+            Some(0) => Semaphore::MAX_PERMITS,
+            Some(capacity) => capacity,
+            None => 1,
+        };
+        let (tx_progress, rx_progress) = channel(capacity);
+        Self {
+            breaker: walker.breaker(),
+            walker: Arc::new(Mutex::new(walker)),
+            tx_progress,
+            rx_progress: Some(rx_progress),
+        }
+    }
+
+    /// Collects file paths. The same as `Walker::collect()`, but doesn't block the async runtime.
+    ///
+    /// # Returns
+    ///
+    /// - A mutable reference to the instance of `AsyncWalker`.
+    ///
+    /// # Errors
+    ///
+    /// The same as `Walker::collect()`.
+    pub async fn collect(&mut self) -> Result<&mut Self, E> {
+        self.run(|walker| walker.collect().map(|_| ())).await?;
+        Ok(self)
+    }
+
+    /// Calculates a common hash. The same as `Walker::hash()`, but doesn't block the async runtime.
+    ///
+    /// # Returns
+    ///
+    /// - `Result<Vec<u8>, E>`: A hash calculated based on the collected paths.
+    ///
+    /// # Errors
+    ///
+    /// The same as `Walker::hash()`.
+    pub async fn hash<H: Hasher + 'static, R: Reader + 'static>(&mut self) -> Result<Vec<u8>, E>
+    where
+        E: From<<H as Hasher>::Error> + From<<R as Reader>::Error>,
+    {
+        self.run(|walker| walker.hash::<H, R>().map(|hash| hash.to_vec()))
+            .await
+    }
+
+    /// Calculates a common hash with the hasher and the reader defined in `Options`. The same as
+    /// `Walker::hash_dyn()`, but doesn't block the async runtime.
+    ///
+    /// # Returns
+    ///
+    /// - `Result<Vec<u8>, E>`: A hash calculated based on the collected paths.
+    ///
+    /// # Errors
+    ///
+    /// The same as `Walker::hash_dyn()`.
+    pub async fn hash_dyn(&mut self) -> Result<Vec<u8>, E> {
+        self.run(|walker| walker.hash_dyn().map(|hash| hash.to_vec()))
+            .await
+    }
+
+    /// Returns an async channel for tracking the progress of collecting and hashing. Ticks are sent
+    /// only if the capacity of progress is set in `Options`. Unlike `Walker::progress()`, the channel
+    /// is the same for all operations, so it can be taken only once.
+    ///
+    /// # Returns
+    ///
+    /// - `Option<Receiver<Tick>>`: A channel, or `None` if it has been taken already.
+    pub fn progress(&mut self) -> Option<Receiver<Tick>> {
+        self.rx_progress.take()
+    }
+
+    /// Returns a `Breaker` which can be used to abort collecting and hashing operations.
+    ///
+    /// # Returns
+    ///
+    /// - A new instance of `Breaker`.
+    pub fn breaker(&self) -> Breaker {
+        self.breaker.clone()
+    }
+
+    /// Gives access to the wrapped `Walker`, for example, to iterate over the results of hashing.
+    /// Waits until the current operation is done.
+    ///
+    /// # Returns
+    ///
+    /// - A guard of the wrapped `Walker`.
+    pub async fn walker(&self) -> MutexGuard<'_, Walker> {
+        self.walker.lock().await
+    }
+
+    /// Runs the operation in a blocking thread. The operation is aborted, if the returned future
+    /// is dropped before the operation is done.
+    async fn run<T: Send + 'static, F: FnOnce(&mut Walker) -> Result<T, E> + Send + 'static>(
+        &mut self,
+        operation: F,
+    ) -> Result<T, E> {
+        let mut walker = self.walker.clone().lock_owned().await;
+        // Armed only with the lock, so waiting for another operation doesn't abort it
+        let guard = AbortOnDrop(Some(self.breaker.clone()));
+        // `Walker` recreates its channel after hashing, so the sender is set before each operation.
+        // Until the async channel is taken, `Walker` keeps its own one, which never blocks.
+        if self.rx_progress.is_none() {
+            if let Some((progress, rx)) = walker.progress.as_mut() {
+                progress.tx = Tx::Async(self.tx_progress.clone());
+                *rx = None;
+            }
+        }
+        let result = task::spawn_blocking(move || operation(&mut walker))
+            .await
+            .map_err(|err| E::JoinError(err.to_string()))?;
+        guard.disarm();
+        result
+    }
+}
+
+impl Walker {
+    /// Converts `Walker` into `AsyncWalker`. Available with feature "tokio".
+    ///
+    /// # Returns
+    ///
+    /// - A new instance of `AsyncWalker`.
+    pub fn into_async(self) -> AsyncWalker {
+        AsyncWalker::new(self)
+    }
+}
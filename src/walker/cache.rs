@@ -0,0 +1,238 @@
+#[cfg(feature = "tracking")]
+use crate::walker::E;
+use crate::{hasher::HasherConfig, Hasher, Reader};
+#[cfg(feature = "tracking")]
+use bstorage::Storage;
+#[cfg(feature = "tracking")]
+use serde::{Deserialize, Serialize};
+use std::{
+    any::type_name,
+    collections::HashMap,
+    fs::Metadata,
+    mem,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Metadata of a file, which is used to detect changes without reading the file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "tracking", derive(Serialize, Deserialize))]
+struct Stamp {
+    size: u64,
+    /// Modification time as seconds and nanoseconds since `UNIX_EPOCH`.
+    mtime: (u64, u32),
+    /// Inode number (on Unix); always `0` on other platforms.
+    inode: u64,
+    /// Status change time as seconds and nanoseconds since `UNIX_EPOCH` (on Unix); always `(0, 0)`
+    /// on other platforms. It's changed by any write, even if the modification time is restored.
+    #[cfg_attr(feature = "tracking", serde(default))]
+    ctime: (u64, u32),
+}
+
+impl Stamp {
+    fn from(md: &Metadata) -> Option<Self> {
+        let mtime = md.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+        #[cfg(unix)]
+        let inode = {
+            use std::os::unix::fs::MetadataExt;
+            md.ino()
+        };
+        #[cfg(not(unix))]
+        let inode = 0;
+        #[cfg(unix)]
+        let ctime = {
+            use std::os::unix::fs::MetadataExt;
+            (
+                u64::try_from(md.ctime()).unwrap_or_default(),
+                u32::try_from(md.ctime_nsec()).unwrap_or_default(),
+            )
+        };
+        #[cfg(not(unix))]
+        let ctime = (0, 0);
+        Some(Self {
+            size: md.len(),
+            mtime: (mtime.as_secs(), mtime.subsec_nanos()),
+            inode,
+            ctime,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "tracking", derive(Serialize, Deserialize))]
+struct Record {
+    stamp: Stamp,
+    hash: Vec<u8>,
+}
+
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "tracking", derive(Serialize, Deserialize))]
+struct Records {
+    /// The time (seconds since `UNIX_EPOCH`) when collecting of the records has been started;
+    /// `0` if it's unknown.
+    #[cfg_attr(feature = "tracking", serde(default))]
+    started: u64,
+    files: HashMap<PathBuf, Record>,
+}
+
+impl Records {
+    fn new() -> Self {
+        Self {
+            started: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|time| time.as_secs())
+                .unwrap_or_default(),
+            files: HashMap::new(),
+        }
+    }
+}
+
+/// `Cache` keeps hashes of files along with their metadata (size, modification time, inode and
+/// status change time). If the metadata of a file hasn't been changed since the previous hashing,
+/// the cached hash is used instead of reading the file.
+///
+/// Like git's racy-index check, records of files modified at the same second the records have been
+/// started or later aren't trusted: such a file could be changed again after hashing within the
+/// precision of timestamps of the file system, so its metadata wouldn't be changed. These files are
+/// hashed again until their records are older than the next hashing.
+///
+/// `Cache` is shared between hashing workers. It holds the records of the previous hashing (read
+/// only) and collects the records of the current hashing, so files which don't exist anymore are
+/// dropped from the cache with the next hashing.
+///
+/// Records are kept separately for each combination of hasher, its configuration and reader,
+/// because the hash of the same file depends on all of them.
+#[derive(Debug)]
+pub struct Cache {
+    key: String,
+    previous: Records,
+    current: Mutex<Records>,
+}
+
+impl Cache {
+    /// Creates an empty cache for the given hasher (with configuration) and reader.
+    pub fn new<H: Hasher, R: Reader>(config: &HasherConfig) -> Self {
+        Self {
+            key: Self::key::<H, R>(config),
+            previous: Records::default(),
+            current: Mutex::new(Records::new()),
+        }
+    }
+
+    /// Checks whether the cache has been created for the given hasher (with configuration) and
+    /// reader.
+    pub fn is_for<H: Hasher, R: Reader>(&self, config: &HasherConfig) -> bool {
+        self.key == Self::key::<H, R>(config)
+    }
+
+    fn key<H: Hasher, R: Reader>(config: &HasherConfig) -> String {
+        let mut key = format!("{}:{}", type_name::<H>(), type_name::<R>());
+        if !config.is_empty() {
+            // The key of the hasher isn't revealed in the storage
+            key = format!("{key}:{}", config.fingerprint());
+        }
+        key
+    }
+
+    /// Makes the records of the current hashing the previous ones to prepare the cache for the next
+    /// hashing. The next records are considered as started from this moment, which is never later
+    /// than the start of the next hashing.
+    pub fn rotate(&mut self) {
+        if let Ok(current) = self.current.get_mut() {
+            self.previous = mem::replace(current, Records::new());
+        }
+    }
+
+    /// Loads the cache from the storage. If there are no records (or they cannot be read), an empty
+    /// cache is returned.
+    ///
+    /// # Parameters
+    ///
+    /// - `config`: The configuration of the hasher.
+    /// - `storage`: The path to the storage.
+    /// - `alias`: The alias of `Walker`'s options.
+    ///
+    /// # Returns
+    ///
+    /// - `Result<Self, E>`: A loaded cache or an error if the storage cannot be opened.
+    #[cfg(feature = "tracking")]
+    pub fn load<H: Hasher, R: Reader>(
+        config: &HasherConfig,
+        storage: &Path,
+        alias: &str,
+    ) -> Result<Self, E> {
+        let mut cache = Self::new::<H, R>(config);
+        cache.previous = Storage::create(storage)?.get_or_default(cache.storage_key(alias))?;
+        Ok(cache)
+    }
+
+    /// Saves the previous records (which are the records of the last hashing after `rotate()`)
+    /// into the storage.
+    ///
+    /// # Parameters
+    ///
+    /// - `storage`: The path to the storage.
+    /// - `alias`: The alias of `Walker`'s options.
+    ///
+    /// # Returns
+    ///
+    /// - `Result<(), E>`: An error if the storage cannot be written.
+    #[cfg(feature = "tracking")]
+    pub fn save(&self, storage: &Path, alias: &str) -> Result<(), E> {
+        Storage::create(storage)?.set(self.storage_key(alias), &self.previous)?;
+        Ok(())
+    }
+
+    #[cfg(feature = "tracking")]
+    fn storage_key(&self, alias: &str) -> String {
+        format!("{alias}:cache:{}", self.key)
+    }
+
+    /// Returns the cached hash of the file, if the metadata of the file hasn't been changed and the
+    /// file hasn't been modified after the previous records have been started (see `Cache`). On
+    /// success, the record is kept for the current hashing.
+    ///
+    /// # Parameters
+    ///
+    /// - `path`: The path to the file.
+    /// - `md`: The current metadata of the file.
+    ///
+    /// # Returns
+    ///
+    /// - `Option<Vec<u8>>`: The cached hash of the file.
+    pub fn get(&self, path: &Path, md: &Metadata) -> Option<Vec<u8>> {
+        let stamp = Stamp::from(md)?;
+        let record = self
+            .previous
+            .files
+            .get(path)
+            .filter(|record| record.stamp == stamp && stamp.mtime.0 < self.previous.started)?;
+        if let Ok(mut current) = self.current.lock() {
+            current.files.insert(path.to_path_buf(), record.clone());
+        }
+        Some(record.hash.clone())
+    }
+
+    /// Saves the hash of the file for the current hashing.
+    ///
+    /// # Parameters
+    ///
+    /// - `path`: The path to the file.
+    /// - `md`: The metadata of the file taken before reading.
+    /// - `hash`: The hash of the file.
+    pub fn set(&self, path: &Path, md: &Metadata, hash: &[u8]) {
+        let Some(stamp) = Stamp::from(md) else {
+            return;
+        };
+        if let Ok(mut current) = self.current.lock() {
+            current.files.insert(
+                path.to_path_buf(),
+                Record {
+                    stamp,
+                    hash: hash.to_vec(),
+                },
+            );
+        }
+    }
+}
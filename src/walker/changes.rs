@@ -0,0 +1,60 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+};
+
+/// Report of changes between two states of the destination: for example, between the current state
+/// and the state saved with the previous check (feature "tracking") or saved in a manifest.
+///
+/// Only files which have been successfully hashed are considered. Files that caused errors
+/// (for example, because of permissions) are not part of the state and will not appear in any list.
+/// All lists are sorted by path.
+#[derive(Debug, Default, Clone)]
+pub struct Changes {
+    /// Files that were not present in the previous state.
+    pub added: Vec<PathBuf>,
+    /// Files that were present in the previous state but are missing now.
+    pub removed: Vec<PathBuf>,
+    /// Files whose hash differs from the hash in the previous state.
+    pub modified: Vec<PathBuf>,
+    /// Files whose hash is the same as the hash in the previous state.
+    pub unchanged: Vec<PathBuf>,
+}
+
+impl Changes {
+    /// Compares the previous and the current states. The current state is expected to be sorted by path.
+    ///
+    /// # Parameters
+    ///
+    /// - `previous`: Files and their hashes of the previous state.
+    /// - `current`: Files and their hashes of the current state.
+    ///
+    /// # Returns
+    ///
+    /// - A new instance of `Changes`.
+    pub(crate) fn compare(previous: &[(PathBuf, Vec<u8>)], current: &[(PathBuf, Vec<u8>)]) -> Self {
+        let mut changes = Changes::default();
+        let previous: HashMap<&PathBuf, &Vec<u8>> = previous.iter().map(|(p, h)| (p, h)).collect();
+        let mut seen: HashSet<&PathBuf> = HashSet::new();
+        for (path, hash) in current.iter() {
+            seen.insert(path);
+            match previous.get(path) {
+                None => changes.added.push(path.to_owned()),
+                Some(prev) if *prev != hash => changes.modified.push(path.to_owned()),
+                Some(_) => changes.unchanged.push(path.to_owned()),
+            }
+        }
+        changes.removed = previous
+            .keys()
+            .filter(|p| !seen.contains(*p))
+            .map(|p| p.to_path_buf())
+            .collect();
+        changes.removed.sort();
+        changes
+    }
+
+    /// Returns `true` if no files have been added, removed or modified since the previous state.
+    pub fn is_same(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+}
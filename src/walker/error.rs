@@ -17,6 +17,8 @@ pub enum E {
     RelativePathAsEntry(PathBuf),
     #[error("Absolute path {0} cannot be used as filter (included/excluded).")]
     AbsolutePathAsFilter(String),
+    #[error("Path {0} cannot be used as file entry because it isn't a file")]
+    OnlyFileAsEntry(PathBuf),
     #[error("Path {0} cannot be used as cwd because it isn't folder")]
     OnlyFolderAsCwd(PathBuf),
     #[error("Path {0} cannot be used because it isn't absolute")]
@@ -63,6 +65,23 @@ pub enum E {
     InvalidRangesForScenarioStrategy(u64),
     #[error("Nested ReadingStrategy::Scenario isn't allowed")]
     NestedScenarioStrategy,
+    #[error("Unknown hasher \"{0}\"; custom hashers should be registered with Options::register_hasher()")]
+    UnknownHasher(String),
+    #[error("Hasher \"{0}\" isn't available, because feature \"{1}\" isn't enabled")]
+    DisabledHasher(String, String),
+    #[error("Hasher {0} doesn't support the given configuration")]
+    UnsupportedHasherConfig(String),
+    #[error("Invalid hasher configuration: {0}")]
+    InvalidHasherConfig(String),
+    #[error("Unknown reader \"{0}\"")]
+    UnknownReader(String),
+    #[error("Invalid manifest {0}; line {1}: {2}")]
+    InvalidManifest(PathBuf, usize, String),
+    #[error("Path {0} cannot be written into a manifest, because it isn't valid UTF-8")]
+    NonUtf8Path(PathBuf),
+    #[cfg(feature = "serde")]
+    #[error("Fail to serialize/deserialize JSON manifest: {0}")]
+    ManifestJson(String),
     #[cfg(feature = "tracking")]
     #[error("Storage error: {0}")]
     Storage(bstorage::E),
@@ -0,0 +1,233 @@
+use super::{Walker, E};
+use crate::{hasher, reader, Hasher};
+use std::{fmt, str::FromStr};
+
+/// A function which hashes collected paths of `Walker` with a particular hasher and the given
+/// reader. Such functions are made by `Options::register_hasher()` for custom hashers.
+pub type HashFn = for<'a> fn(&'a mut Walker, &ReaderKind) -> Result<&'a [u8], E>;
+
+/// Names of built-in hashers and the features, which they require.
+const BUILT_IN: &[(&str, &str)] = &[
+    ("sha256", "use_sha2"),
+    ("sha512", "use_sha2"),
+    ("sha1", "use_sha1"),
+    ("md5", "use_md5"),
+    ("xxh3-64", "use_xxh3"),
+    ("xxh3-128", "use_xxh3"),
+    ("crc32c", "use_crc32c"),
+];
+
+/// A hasher selected at runtime (for example, from a config file or a CLI flag). Used by
+/// `Walker::hash_dyn()`.
+///
+/// Built-in hashers are available only if the related features are enabled. Custom hashers
+/// should be registered with `Options::register_hasher()` and selected with `HasherKind::Custom`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum HasherKind {
+    /// `hasher::blake::Blake`.
+    #[default]
+    Blake,
+    /// `hasher::sha256::Sha256`; requires feature "use_sha2".
+    #[cfg(feature = "use_sha2")]
+    Sha256,
+    /// `hasher::sha512::Sha512`; requires feature "use_sha2".
+    #[cfg(feature = "use_sha2")]
+    Sha512,
+    /// `hasher::sha1::Sha1`; requires feature "use_sha1".
+    #[cfg(feature = "use_sha1")]
+    Sha1,
+    /// `hasher::md5::Md5`; requires feature "use_md5".
+    #[cfg(feature = "use_md5")]
+    Md5,
+    /// `hasher::xxh3_64::Xxh3_64`; requires feature "use_xxh3".
+    #[cfg(feature = "use_xxh3")]
+    Xxh3_64,
+    /// `hasher::xxh3_128::Xxh3_128`; requires feature "use_xxh3".
+    #[cfg(feature = "use_xxh3")]
+    Xxh3_128,
+    /// `hasher::crc32c::Crc32c`; requires feature "use_crc32c".
+    #[cfg(feature = "use_crc32c")]
+    Crc32c,
+    /// A custom hasher registered with `Options::register_hasher()` under the given name.
+    Custom(String),
+}
+
+impl FromStr for HasherKind {
+    type Err = E;
+
+    /// Parses the name of a hasher. Names of built-in hashers are: "blake", "sha256", "sha512",
+    /// "sha1", "md5", "xxh3-64", "xxh3-128" and "crc32c". Any other name is considered as the name
+    /// of a custom hasher.
+    ///
+    /// # Errors
+    ///
+    /// Returns `E::DisabledHasher` for the name of a built-in hasher, if the related feature isn't
+    /// enabled.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "blake" => Self::Blake,
+            #[cfg(feature = "use_sha2")]
+            "sha256" => Self::Sha256,
+            #[cfg(feature = "use_sha2")]
+            "sha512" => Self::Sha512,
+            #[cfg(feature = "use_sha1")]
+            "sha1" => Self::Sha1,
+            #[cfg(feature = "use_md5")]
+            "md5" => Self::Md5,
+            #[cfg(feature = "use_xxh3")]
+            "xxh3-64" => Self::Xxh3_64,
+            #[cfg(feature = "use_xxh3")]
+            "xxh3-128" => Self::Xxh3_128,
+            #[cfg(feature = "use_crc32c")]
+            "crc32c" => Self::Crc32c,
+            _ => {
+                if let Some((name, feature)) = BUILT_IN.iter().find(|(name, _)| *name == s) {
+                    return Err(E::DisabledHasher(name.to_string(), feature.to_string()));
+                }
+                Self::Custom(s.to_owned())
+            }
+        })
+    }
+}
+
+impl fmt::Display for HasherKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Blake => "blake",
+                #[cfg(feature = "use_sha2")]
+                Self::Sha256 => "sha256",
+                #[cfg(feature = "use_sha2")]
+                Self::Sha512 => "sha512",
+                #[cfg(feature = "use_sha1")]
+                Self::Sha1 => "sha1",
+                #[cfg(feature = "use_md5")]
+                Self::Md5 => "md5",
+                #[cfg(feature = "use_xxh3")]
+                Self::Xxh3_64 => "xxh3-64",
+                #[cfg(feature = "use_xxh3")]
+                Self::Xxh3_128 => "xxh3-128",
+                #[cfg(feature = "use_crc32c")]
+                Self::Crc32c => "crc32c",
+                Self::Custom(name) => name.as_str(),
+            }
+        )
+    }
+}
+
+/// A reader selected at runtime (for example, from a config file or a CLI flag). Used by
+/// `Walker::hash_dyn()`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum ReaderKind {
+    /// `reader::buffering::Buffering`.
+    #[default]
+    Buffering,
+    /// `reader::mapping::Mapping`.
+    Mapping,
+    /// `reader::md::Md`.
+    Md,
+}
+
+impl FromStr for ReaderKind {
+    type Err = E;
+
+    /// Parses the name of a reader: "buffering", "mapping" or "md".
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "buffering" => Ok(Self::Buffering),
+            "mapping" => Ok(Self::Mapping),
+            "md" => Ok(Self::Md),
+            _ => Err(E::UnknownReader(s.to_owned())),
+        }
+    }
+}
+
+impl fmt::Display for ReaderKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Buffering => "buffering",
+                Self::Mapping => "mapping",
+                Self::Md => "md",
+            }
+        )
+    }
+}
+
+/// Hashes collected paths of `Walker` with the given hasher and the reader selected at runtime.
+/// Instances of this function are used as `HashFn`.
+pub(crate) fn hash_with<'a, H: Hasher + 'static>(
+    walker: &'a mut Walker,
+    reader: &ReaderKind,
+) -> Result<&'a [u8], E>
+where
+    E: From<<H as Hasher>::Error>,
+{
+    match reader {
+        ReaderKind::Buffering => walker.hash::<H, reader::buffering::Buffering>(),
+        ReaderKind::Mapping => walker.hash::<H, reader::mapping::Mapping>(),
+        ReaderKind::Md => walker.hash::<H, reader::md::Md>(),
+    }
+}
+
+impl Walker {
+    /// Calculates a common hash with the hasher and the reader defined in `Options` (see
+    /// `Options::hasher()` and `Options::reader()`). It's the same as `hash()`, but hasher and
+    /// reader are selected at runtime instead of type parameters.
+    ///
+    /// # Returns
+    ///
+    /// - `Result<&[u8], E>`: A hash calculated based on the collected paths.
+    ///
+    /// # Errors
+    ///
+    /// The same as `hash()`. Besides, `E::UnknownHasher` is returned if `HasherKind::Custom` is used
+    /// with a name, which hasn't been registered with `Options::register_hasher()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fshasher::{walker::{HasherKind, ReaderKind}, Options};
+    /// use std::env::temp_dir;
+    ///
+    /// let mut walker = Options::from(temp_dir())
+    ///     .unwrap()
+    ///     .hasher("blake".parse::<HasherKind>().unwrap())
+    ///     .reader("buffering".parse::<ReaderKind>().unwrap())
+    ///     .walker()
+    ///     .unwrap();
+    /// let hash = walker.collect().unwrap().hash_dyn().unwrap();
+    /// println!("Hash of {}: {:?}", temp_dir().display(), hash);
+    /// ```
+    pub fn hash_dyn(&mut self) -> Result<&[u8], E> {
+        let opt = self.opt.as_ref().ok_or(E::IsNotInited)?;
+        let reader = opt.reader.clone();
+        let hash: HashFn = match &opt.hasher {
+            HasherKind::Blake => hash_with::<hasher::blake::Blake>,
+            #[cfg(feature = "use_sha2")]
+            HasherKind::Sha256 => hash_with::<hasher::sha256::Sha256>,
+            #[cfg(feature = "use_sha2")]
+            HasherKind::Sha512 => hash_with::<hasher::sha512::Sha512>,
+            #[cfg(feature = "use_sha1")]
+            HasherKind::Sha1 => hash_with::<hasher::sha1::Sha1>,
+            #[cfg(feature = "use_md5")]
+            HasherKind::Md5 => hash_with::<hasher::md5::Md5>,
+            #[cfg(feature = "use_xxh3")]
+            HasherKind::Xxh3_64 => hash_with::<hasher::xxh3_64::Xxh3_64>,
+            #[cfg(feature = "use_xxh3")]
+            HasherKind::Xxh3_128 => hash_with::<hasher::xxh3_128::Xxh3_128>,
+            #[cfg(feature = "use_crc32c")]
+            HasherKind::Crc32c => hash_with::<hasher::crc32c::Crc32c>,
+            HasherKind::Custom(name) => opt
+                .hashers
+                .iter()
+                .find_map(|(registered, hash)| (registered == name).then_some(*hash))
+                .ok_or_else(|| E::UnknownHasher(name.to_owned()))?,
+        };
+        hash(self, &reader)
+    }
+}
@@ -0,0 +1,206 @@
+use super::{closest_root, Walker, E};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    path::{Path, PathBuf},
+};
+
+/// What a file belongs to: an entry or a file added with `Options::file()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Origin {
+    /// The index of the entry in `Options::entries` (the closest one, if entries are nested).
+    Entry(usize),
+    /// The index of the file in `Options::files` (only for files outside of entries).
+    File(usize),
+}
+
+/// A key of a file, which doesn't depend on the location of entries: the origin of the file (an
+/// entry or a file added with `Options::file()`) and the path relative to it. Results of two
+/// checkouts of the same tree in different folders (or on different machines) have equal keys.
+///
+/// Keys are ordered by origins (entries in the order of `Options::entries`, then files added with
+/// `Options::file()` in the order of `Options::files`) and then by relative paths compared byte by
+/// byte, so the order doesn't depend on the platform.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FileKey {
+    /// The entry or the file added with `Options::file()`, which the path belongs to.
+    pub origin: Origin,
+    /// The path of the file relative to its entry with `/` as a separator (or the name of the file
+    /// for files added with `Options::file()`). Names are kept as they are encoded by the platform
+    /// (see `OsStr::as_encoded_bytes()`), so names, which aren't valid UTF-8, stay distinct.
+    pub relative: Vec<u8>,
+}
+
+impl FileKey {
+    fn new(origin: Origin, relative: &Path) -> Self {
+        let mut bytes = Vec::new();
+        for (n, cmp) in relative.components().enumerate() {
+            if n > 0 {
+                bytes.push(b'/');
+            }
+            bytes.extend_from_slice(cmp.as_os_str().as_encoded_bytes());
+        }
+        Self {
+            origin,
+            relative: bytes,
+        }
+    }
+}
+
+impl fmt::Display for FileKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let relative = String::from_utf8_lossy(&self.relative);
+        match self.origin {
+            Origin::Entry(index) => write!(f, "{index}:{relative}"),
+            Origin::File(index) => write!(f, "f{index}:{relative}"),
+        }
+    }
+}
+
+/// A file found by collecting, as it's listed by `Walker::list()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Listed {
+    /// The entry the file belongs to and the path relative to this entry.
+    pub key: FileKey,
+    /// The full path to the file.
+    pub path: PathBuf,
+    /// The size of the file in bytes; `None` if the metadata of the file isn't available.
+    pub size: Option<u64>,
+}
+
+impl Walker {
+    /// Collects files without hashing them and returns the list of files, which would be hashed.
+    /// It allows auditing the result of filtering before paying the I/O cost of hashing.
+    ///
+    /// Files are sorted by keys (see `FileKey`), so the order doesn't depend on the platform or on
+    /// the order of collecting.
+    ///
+    /// `list()` calls `collect()`, so `hash()` can be called after it without collecting again.
+    /// Paths, which caused errors during collecting, aren't listed; they stay in the `paths` field.
+    ///
+    /// # Returns
+    ///
+    /// - `Result<Vec<Listed>, E>`: The sorted list of files.
+    ///
+    /// # Errors
+    ///
+    /// The same as `collect()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fshasher::Options;
+    /// use std::env::temp_dir;
+    ///
+    /// let mut walker = Options::from(temp_dir()).unwrap().walker().unwrap();
+    /// for file in walker.list().unwrap() {
+    ///     println!("{}\t{:?}", file.key, file.size);
+    /// }
+    /// ```
+    pub fn list(&mut self) -> Result<Vec<Listed>, E> {
+        self.collect()?;
+        let roots = self.entry_roots()?;
+        let mut listed: Vec<Listed> = self
+            .paths
+            .iter()
+            .filter(|(_, state)| state.is_none())
+            .filter_map(|(path, _)| {
+                Some(Listed {
+                    key: self.key_with(path, &roots)?,
+                    path: path.to_owned(),
+                    size: path.metadata().ok().map(|md| md.len()),
+                })
+            })
+            .collect();
+        listed.sort_by(|a, b| (&a.key, &a.path).cmp(&(&b.key, &b.path)));
+        Ok(listed)
+    }
+
+    /// Returns the results of collecting and hashing keyed by entries and relative paths instead
+    /// of full paths. Unlike the `paths` field, the results of two checkouts of the same tree in
+    /// different folders can be compared directly, which makes them portable across machines and
+    /// CI workspaces.
+    ///
+    /// The values are the same as in the `paths` field: `None` right after `collect()`, the hash of
+    /// the file or the related error after `hash()`. If entries are nested, a file collected by
+    /// several entries is listed once.
+    ///
+    /// # Returns
+    ///
+    /// - `Result<BTreeMap<FileKey, &Option<Result<Vec<u8>, E>>>, E>`: The results sorted by keys.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `Walker` isn't initialized.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fshasher::{hasher, reader, Options};
+    /// use std::env::temp_dir;
+    ///
+    /// let mut walker = Options::from(temp_dir()).unwrap().walker().unwrap();
+    /// walker
+    ///     .collect()
+    ///     .unwrap()
+    ///     .hash::<hasher::blake::Blake, reader::buffering::Buffering>()
+    ///     .unwrap();
+    /// for (key, state) in walker.results().unwrap() {
+    ///     if let Some(Ok(hash)) = state {
+    ///         println!("{key}: {hash:?}");
+    ///     }
+    /// }
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn results(&self) -> Result<BTreeMap<FileKey, &Option<Result<Vec<u8>, E>>>, E> {
+        let roots = self.entry_roots()?;
+        let mut results = BTreeMap::new();
+        for (path, state) in self.paths.iter() {
+            if let Some(key) = self.key_with(path, &roots) {
+                results.entry(key).or_insert(state);
+            }
+        }
+        Ok(results)
+    }
+
+    /// Returns the key of a file (see `FileKey`).
+    ///
+    /// # Parameters
+    ///
+    /// - `path`: The full path to the file.
+    ///
+    /// # Returns
+    ///
+    /// - `Option<FileKey>`: The key; `None` if the file doesn't belong to any entry and isn't added
+    ///   with `Options::file()`, or if `Walker` isn't initialized.
+    pub fn key<P: AsRef<Path>>(&self, path: P) -> Option<FileKey> {
+        self.key_with(path.as_ref(), &self.entry_roots().ok()?)
+    }
+
+    fn entry_roots(&self) -> Result<Vec<PathBuf>, E> {
+        Ok(self
+            .opt
+            .as_ref()
+            .ok_or(E::IsNotInited)?
+            .entries
+            .iter()
+            .map(|en| en.entry.clone())
+            .collect())
+    }
+
+    fn key_with(&self, path: &Path, roots: &[PathBuf]) -> Option<FileKey> {
+        if let Some((index, relative)) = closest_root(path, roots) {
+            return Some(FileKey::new(Origin::Entry(index), relative));
+        }
+        let index = self
+            .opt
+            .as_ref()?
+            .files
+            .iter()
+            .position(|file| file == path)?;
+        Some(FileKey::new(
+            Origin::File(index),
+            Path::new(path.file_name()?),
+        ))
+    }
+}
@@ -0,0 +1,367 @@
+use super::{Changes, Walker, E};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::{
+    env,
+    fs::{self, File},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+/// Format of a manifest file.
+#[derive(Debug, Clone, Default)]
+pub enum ManifestFormat {
+    /// Text format compatible with `sha256sum` and `b3sum`: each line contains the hex hash of a
+    /// file, two spaces and the path to the file. Can be checked with `sha256sum -c` (or `b3sum -c`)
+    /// from the folder of the manifest, if the corresponding hasher has been used.
+    #[default]
+    Text,
+    /// JSON format. Available with feature "serde".
+    #[cfg(feature = "serde")]
+    Json,
+}
+
+#[cfg(feature = "serde")]
+#[derive(Debug, Serialize, Deserialize)]
+struct JsonManifest {
+    files: Vec<JsonManifestItem>,
+}
+
+#[cfg(feature = "serde")]
+#[derive(Debug, Serialize, Deserialize)]
+struct JsonManifestItem {
+    path: String,
+    hash: String,
+}
+
+/// `Manifest` keeps the hashes of files calculated by `Walker` and allows saving them into a file
+/// (to ship an integrity manifest alongside artifacts) and loading them back to compare with a
+/// fresh walk.
+///
+/// Paths are saved relative to the folder of the manifest file (with `/` as a separator), if files
+/// are located inside this folder; otherwise, full paths are saved. On loading, relative paths are
+/// resolved against the folder of the manifest file. Paths, which aren't valid UTF-8, cannot be
+/// saved.
+///
+/// # Example
+///
+/// ```
+/// use fshasher::{hasher, reader, walker::{Manifest, ManifestFormat}, Options};
+/// use std::{env::temp_dir, fs::{create_dir, remove_dir_all}};
+/// use uuid::Uuid;
+///
+/// let dest = temp_dir().join(Uuid::new_v4().to_string());
+/// create_dir(&dest).unwrap();
+/// let mut walker = Options::from(&dest).unwrap().walker().unwrap();
+/// walker
+///     .collect()
+///     .unwrap()
+///     .hash::<hasher::blake::Blake, reader::buffering::Buffering>()
+///     .unwrap();
+/// let manifest_path = dest.join("checksums.b3");
+/// walker.manifest().save(&manifest_path, ManifestFormat::Text).unwrap();
+/// // ... later
+/// let manifest = Manifest::load(&manifest_path).unwrap();
+/// let mut walker = Options::from(&dest).unwrap().walker().unwrap();
+/// walker
+///     .collect()
+///     .unwrap()
+///     .hash::<hasher::blake::Blake, reader::buffering::Buffering>()
+///     .unwrap();
+/// let changes = manifest.compare(&walker);
+/// // The manifest file itself is a new file in the destination
+/// assert_eq!(changes.added, vec![manifest_path]);
+/// remove_dir_all(&dest).unwrap();
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct Manifest {
+    /// Full paths to files and their hashes, sorted by path.
+    pub files: Vec<(PathBuf, Vec<u8>)>,
+}
+
+impl Manifest {
+    /// Creates a new `Manifest` from the results of `Walker`. Only files which have been successfully
+    /// hashed are included.
+    ///
+    /// # Parameters
+    ///
+    /// - `walker`: An instance of `Walker` after `hash()` has been called.
+    ///
+    /// # Returns
+    ///
+    /// - A new instance of `Manifest`.
+    pub fn from(walker: &Walker) -> Self {
+        let mut files: Vec<(PathBuf, Vec<u8>)> = walker
+            .iter()
+            .filter_map(|(p, h)| {
+                if let Some(Ok(h)) = h {
+                    Some((p.to_owned(), h.to_owned()))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        files.sort_by(|(a, _), (b, _)| a.cmp(b));
+        Self { files }
+    }
+
+    /// Loads a manifest from the file. The format is detected by the content: JSON (with feature
+    /// "serde") if the content starts with `{`; otherwise, the `sha256sum`/`b3sum` text format.
+    ///
+    /// # Parameters
+    ///
+    /// - `path`: The path to the manifest file.
+    ///
+    /// # Returns
+    ///
+    /// - `Result<Self, E>`: A loaded `Manifest` or an error if the file cannot be read or parsed.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, E> {
+        let path = absolute(path.as_ref())?;
+        let base = path.parent().unwrap_or(&path).to_path_buf();
+        let content = fs::read_to_string(&path).map_err(|err| (path.clone(), err))?;
+        let mut files = if content.trim_start().starts_with('{') {
+            Self::parse_json(&path, &base, &content)?
+        } else {
+            Self::parse_text(&path, &base, &content)?
+        };
+        files.sort_by(|(a, _), (b, _)| a.cmp(b));
+        Ok(Self { files })
+    }
+
+    /// Saves the manifest into the file.
+    ///
+    /// # Parameters
+    ///
+    /// - `path`: The path to the manifest file. If the file exists, it will be overwritten.
+    /// - `format`: The format of the manifest.
+    ///
+    /// # Returns
+    ///
+    /// - `Result<(), E>`: An error if the file cannot be written.
+    ///
+    /// # Errors
+    ///
+    /// Returns `E::NonUtf8Path` if the path of a listed file isn't valid UTF-8 (the file isn't
+    /// written in this case).
+    pub fn save<P: AsRef<Path>>(&self, path: P, format: ManifestFormat) -> Result<(), E> {
+        let path = absolute(path.as_ref())?;
+        let base = path.parent().unwrap_or(&path).to_path_buf();
+        let content = match format {
+            ManifestFormat::Text => self.to_text(&base)?,
+            #[cfg(feature = "serde")]
+            ManifestFormat::Json => self.to_json(&base)?,
+        };
+        let mut file = File::create(&path).map_err(|err| (path.clone(), err))?;
+        file.write_all(content.as_bytes())
+            .and_then(|_| file.flush())
+            .map_err(|err| (path.clone(), err))?;
+        Ok(())
+    }
+
+    /// Compares the manifest (as the previous state) with the results of `Walker` (as the current
+    /// state).
+    ///
+    /// # Parameters
+    ///
+    /// - `walker`: An instance of `Walker` after `hash()` has been called.
+    ///
+    /// # Returns
+    ///
+    /// - `Changes`: Lists of added, removed, modified and unchanged files.
+    pub fn compare(&self, walker: &Walker) -> Changes {
+        Changes::compare(&self.files, &Manifest::from(walker).files)
+    }
+
+    /// Returns the hash of the given file, if the file is listed in the manifest.
+    ///
+    /// # Parameters
+    ///
+    /// - `path`: The full path to the file.
+    ///
+    /// # Returns
+    ///
+    /// - `Option<&[u8]>`: The hash of the file.
+    pub fn get<P: AsRef<Path>>(&self, path: P) -> Option<&[u8]> {
+        self.files
+            .binary_search_by(|(p, _)| p.as_path().cmp(path.as_ref()))
+            .ok()
+            .map(|i| self.files[i].1.as_slice())
+    }
+
+    fn to_text(&self, base: &Path) -> Result<String, E> {
+        let mut content = String::new();
+        for (path, hash) in self.files.iter() {
+            let path = stored_path(path, base)?;
+            // Escaping in the same way as sha256sum and b3sum do
+            if path.contains(['\\', '\n', '\r']) {
+                content.push('\\');
+                content.push_str(&to_hex(hash));
+                content.push_str("  ");
+                content.push_str(
+                    &path
+                        .replace('\\', "\\\\")
+                        .replace('\n', "\\n")
+                        .replace('\r', "\\r"),
+                );
+            } else {
+                content.push_str(&to_hex(hash));
+                content.push_str("  ");
+                content.push_str(&path);
+            }
+            content.push('\n');
+        }
+        Ok(content)
+    }
+
+    fn parse_text(
+        filename: &Path,
+        base: &Path,
+        content: &str,
+    ) -> Result<Vec<(PathBuf, Vec<u8>)>, E> {
+        let mut files = Vec::new();
+        for (n, ln) in content.lines().enumerate() {
+            if ln.trim().is_empty() || ln.starts_with('#') {
+                continue;
+            }
+            let invalid = |msg: &str| E::InvalidManifest(filename.to_path_buf(), n + 1, msg.into());
+            let (escaped, ln) = if let Some(ln) = ln.strip_prefix('\\') {
+                (true, ln)
+            } else {
+                (false, ln)
+            };
+            let (hash, path) = ln
+                .split_once(' ')
+                .ok_or_else(|| invalid("expected \"<hash>  <path>\""))?;
+            // Second char is " " for text mode and "*" for binary mode
+            let path = path
+                .strip_prefix(' ')
+                .or_else(|| path.strip_prefix('*'))
+                .ok_or_else(|| invalid("expected \"<hash>  <path>\""))?;
+            let path = if escaped {
+                unescape(path).ok_or_else(|| invalid("invalid escaping"))?
+            } else {
+                path.to_owned()
+            };
+            let hash = from_hex(hash).ok_or_else(|| invalid("invalid hex hash"))?;
+            files.push((base.join(path), hash));
+        }
+        Ok(files)
+    }
+
+    #[cfg(feature = "serde")]
+    fn to_json(&self, base: &Path) -> Result<String, E> {
+        let manifest = JsonManifest {
+            files: self
+                .files
+                .iter()
+                .map(|(path, hash)| {
+                    Ok(JsonManifestItem {
+                        path: stored_path(path, base)?,
+                        hash: to_hex(hash),
+                    })
+                })
+                .collect::<Result<_, E>>()?,
+        };
+        serde_json::to_string_pretty(&manifest).map_err(|err| E::ManifestJson(err.to_string()))
+    }
+
+    #[cfg(feature = "serde")]
+    fn parse_json(
+        filename: &Path,
+        base: &Path,
+        content: &str,
+    ) -> Result<Vec<(PathBuf, Vec<u8>)>, E> {
+        let manifest: JsonManifest =
+            serde_json::from_str(content).map_err(|err| E::ManifestJson(err.to_string()))?;
+        let mut files = Vec::new();
+        for (n, item) in manifest.files.into_iter().enumerate() {
+            let hash = from_hex(&item.hash).ok_or_else(|| {
+                E::InvalidManifest(filename.to_path_buf(), n + 1, "invalid hex hash".into())
+            })?;
+            files.push((base.join(item.path), hash));
+        }
+        Ok(files)
+    }
+
+    #[cfg(not(feature = "serde"))]
+    fn parse_json(
+        filename: &Path,
+        _base: &Path,
+        _content: &str,
+    ) -> Result<Vec<(PathBuf, Vec<u8>)>, E> {
+        Err(E::InvalidManifest(
+            filename.to_path_buf(),
+            1,
+            "JSON manifest requires feature \"serde\"".into(),
+        ))
+    }
+}
+
+impl Walker {
+    /// Returns a `Manifest` with the hashes of files calculated by the last call of `hash()`.
+    ///
+    /// # Returns
+    ///
+    /// - A new instance of `Manifest`.
+    pub fn manifest(&self) -> Manifest {
+        Manifest::from(self)
+    }
+}
+
+fn absolute(path: &Path) -> Result<PathBuf, E> {
+    if path.is_absolute() {
+        Ok(path.to_path_buf())
+    } else {
+        Ok(env::current_dir()?.join(path))
+    }
+}
+
+/// Returns the path of a file as it's stored in a manifest: relative to the folder of the manifest
+/// (with `/` as a separator) or the full path, if the file is outside of this folder. Paths aren't
+/// converted lossily, so a non-UTF-8 path is an error rather than a different path.
+fn stored_path(path: &Path, base: &Path) -> Result<String, E> {
+    let non_utf8 = || E::NonUtf8Path(path.to_path_buf());
+    let Ok(relative) = path.strip_prefix(base) else {
+        return path
+            .to_str()
+            .map(|path| path.to_owned())
+            .ok_or_else(non_utf8);
+    };
+    Ok(relative
+        .components()
+        .map(|cmp| cmp.as_os_str().to_str().ok_or_else(non_utf8))
+        .collect::<Result<Vec<&str>, E>>()?
+        .join("/"))
+}
+
+fn unescape(path: &str) -> Option<String> {
+    let mut unescaped = String::new();
+    let mut chars = path.chars();
+    while let Some(ch) = chars.next() {
+        if ch == '\\' {
+            match chars.next()? {
+                '\\' => unescaped.push('\\'),
+                'n' => unescaped.push('\n'),
+                'r' => unescaped.push('\r'),
+                _ => return None,
+            }
+        } else {
+            unescaped.push(ch);
+        }
+    }
+    Some(unescaped)
+}
+
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+pub(crate) fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 || !hex.is_ascii() {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
@@ -0,0 +1,121 @@
+use super::E;
+use crate::{hasher::HasherConfig, Hasher};
+use std::{
+    collections::{BTreeMap, HashMap},
+    ffi::{OsStr, OsString},
+    path::{Component, Path, PathBuf},
+};
+
+/// Tag of a file among the children of a directory.
+const FILE_TAG: u8 = b'f';
+/// Tag of a nested directory among the children of a directory.
+const DIR_TAG: u8 = b'd';
+
+/// Hashes of directories by their paths.
+pub(crate) type DirHashes = HashMap<PathBuf, Vec<u8>>;
+
+/// Children of a directory sorted by names. A file has a hash; the hash of a nested directory is
+/// calculated later.
+type Children = BTreeMap<OsString, Option<Vec<u8>>>;
+
+/// Calculates hashes of directories as a Merkle tree: the hash of each directory is calculated
+/// from the names and hashes of its children (files and nested directories). Only directories,
+/// which contain files (directly or in nested directories), get hashes.
+///
+/// Each file belongs to every entry it's located in, so with nested entries the hash of the outer
+/// entry covers the files of the inner one, and the hash of a directory doesn't depend on entries.
+/// Files added with `Options::file()` belong to their folders (see `Options::roots()`). Files,
+/// which don't belong to any entry, are skipped.
+///
+/// # Parameters
+///
+/// - `hashes`: Paths and hashes of files.
+/// - `roots`: The paths of entries.
+/// - `config`: The configuration of the hasher.
+///
+/// # Returns
+///
+/// - `Result<(Vec<u8>, DirHashes), E>`: The summary hash and the hashes of
+///   directories. If there is only one entry, the summary hash is the hash of the entry;
+///   otherwise, it's calculated from the hashes of entries in the order of entries. The summary
+///   hash is empty if there are no files.
+pub(crate) fn merkle<'a, H: Hasher>(
+    hashes: impl Iterator<Item = (&'a Path, &'a [u8])>,
+    roots: &[PathBuf],
+    config: &HasherConfig,
+) -> Result<(Vec<u8>, DirHashes), E>
+where
+    E: From<<H as Hasher>::Error>,
+{
+    let mut tree: HashMap<PathBuf, Children> = HashMap::new();
+    for (path, hash) in hashes {
+        for root in roots.iter() {
+            let Ok(rel) = path.strip_prefix(root) else {
+                continue;
+            };
+            let names: Vec<&OsStr> = rel
+                .components()
+                .filter_map(|component| match component {
+                    Component::Normal(name) => Some(name),
+                    _ => None,
+                })
+                .collect();
+            // Directories of nested entries are the same nodes of the tree
+            let mut dir = root.to_path_buf();
+            for (i, name) in names.iter().enumerate() {
+                let children = tree.entry(dir.clone()).or_default();
+                if i == names.len() - 1 {
+                    children.insert(name.to_os_string(), Some(hash.to_vec()));
+                } else {
+                    children.entry(name.to_os_string()).or_insert(None);
+                    dir = dir.join(name);
+                }
+            }
+        }
+    }
+    // Nested directories are hashed before their parents
+    let mut dirs: Vec<PathBuf> = tree.keys().cloned().collect();
+    dirs.sort_by_key(|dir| std::cmp::Reverse(dir.components().count()));
+    let mut hashed: DirHashes = HashMap::new();
+    for dir in dirs.into_iter() {
+        let Some(children) = tree.remove(&dir) else {
+            continue;
+        };
+        let mut hasher = H::configured(config)?;
+        for (name, hash) in children.iter() {
+            let (tag, hash) = match hash {
+                Some(hash) => (FILE_TAG, hash.as_slice()),
+                None => {
+                    let Some(hash) = hashed.get(&dir.join(name)) else {
+                        unreachable!("Nested directory is always hashed before its parent");
+                    };
+                    (DIR_TAG, hash.as_slice())
+                }
+            };
+            hasher.absorb(&[tag])?;
+            hasher.absorb(name.as_encoded_bytes())?;
+            // Separator, which cannot be a part of name
+            hasher.absorb(&[0])?;
+            hasher.absorb(hash)?;
+        }
+        hasher.finish()?;
+        hashed.insert(dir, hasher.hash()?.to_vec());
+    }
+    let summary = if let [root] = roots {
+        hashed.get(root).cloned().unwrap_or_default()
+    } else {
+        let mut summary = H::configured(config)?;
+        let mut count = 0;
+        for hash in roots.iter().filter_map(|root| hashed.get(root)) {
+            summary.absorb(hash)?;
+            count += 1;
+        }
+        if count == 0 {
+            Vec::new()
+        } else {
+            summary.finish()?;
+            summary.hash()?.to_vec()
+        }
+    };
+    Ok((summary, hashed))
+}
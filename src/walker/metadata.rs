@@ -0,0 +1,122 @@
+use super::E;
+use crate::{hasher::HasherConfig, Hasher};
+use std::{fs, path::Path};
+
+/// Metadata of a file, which can be mixed into the hash of the file along with its content (see
+/// `Options::metadata()`). With mixed metadata, for example, changing permissions of a file
+/// changes its hash even if the content is the same.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MetaField {
+    /// Permission bits of a file (including the executable bit, setuid, setgid and sticky bits).
+    /// On non-Unix platforms only the read-only flag is used.
+    Mode,
+    /// The owner of a file: user ID and group ID. Ignored on non-Unix platforms.
+    Owner,
+    /// The target of a symlink as it's stored in the link (not resolved). Applied to files, which
+    /// are reached via symlinks; for regular files it's empty.
+    SymlinkTarget,
+    /// Extended attributes of a file: names and values sorted by names. Requires feature "xattrs";
+    /// available on Unix platforms only.
+    #[cfg(all(feature = "xattrs", unix))]
+    Xattrs,
+}
+
+impl MetaField {
+    /// Tag of the field in the hash of a file.
+    fn tag(&self) -> u8 {
+        match self {
+            Self::Mode => b'm',
+            Self::Owner => b'o',
+            Self::SymlinkTarget => b'l',
+            #[cfg(all(feature = "xattrs", unix))]
+            Self::Xattrs => b'x',
+        }
+    }
+
+    /// Reads the field of the given file as bytes.
+    fn read(&self, path: &Path) -> Result<Vec<u8>, E> {
+        Ok(match self {
+            #[cfg(unix)]
+            Self::Mode => {
+                use std::os::unix::fs::PermissionsExt;
+                (fs::metadata(path)?.permissions().mode() & 0o7777)
+                    .to_le_bytes()
+                    .to_vec()
+            }
+            #[cfg(not(unix))]
+            Self::Mode => vec![fs::metadata(path)?.permissions().readonly() as u8],
+            #[cfg(unix)]
+            Self::Owner => {
+                use std::os::unix::fs::MetadataExt;
+                let md = fs::metadata(path)?;
+                [md.uid().to_le_bytes(), md.gid().to_le_bytes()].concat()
+            }
+            #[cfg(not(unix))]
+            Self::Owner => Vec::new(),
+            Self::SymlinkTarget => {
+                if fs::symlink_metadata(path)?.is_symlink() {
+                    fs::read_link(path)?.as_os_str().as_encoded_bytes().to_vec()
+                } else {
+                    Vec::new()
+                }
+            }
+            #[cfg(all(feature = "xattrs", unix))]
+            Self::Xattrs => {
+                use std::os::unix::ffi::OsStrExt;
+                let mut names: Vec<_> = xattr::list_deref(path)?.collect();
+                names.sort();
+                let mut bytes = Vec::new();
+                for name in names.iter() {
+                    let value = xattr::get_deref(path, name)?.unwrap_or_default();
+                    bytes.extend_from_slice(&(name.len() as u64).to_le_bytes());
+                    bytes.extend_from_slice(name.as_bytes());
+                    bytes.extend_from_slice(&(value.len() as u64).to_le_bytes());
+                    bytes.extend_from_slice(&value);
+                }
+                bytes
+            }
+        })
+    }
+}
+
+/// Mixes the selected metadata of a file into the hash of its content.
+///
+/// # Parameters
+///
+/// - `path`: The path of the file.
+/// - `hash`: The hash of the content of the file.
+/// - `fields`: The fields of metadata to mix in; they are expected to be sorted and unique.
+/// - `config`: The configuration of the hasher.
+///
+/// # Returns
+///
+/// - `Result<Vec<u8>, E>`: The hash of the file. If no fields are selected, the hash of the content
+///   is returned as it is.
+///
+/// # Errors
+///
+/// Returns an error if the metadata of the file cannot be read or if the hasher fails.
+pub(crate) fn mix<H: Hasher>(
+    path: &Path,
+    hash: Vec<u8>,
+    fields: &[MetaField],
+    config: &HasherConfig,
+) -> Result<Vec<u8>, E>
+where
+    E: From<<H as Hasher>::Error>,
+{
+    if fields.is_empty() {
+        return Ok(hash);
+    }
+    let mut hasher = H::configured(config)?;
+    hasher.absorb(&hash)?;
+    for field in fields.iter() {
+        let value = field.read(path)?;
+        hasher.absorb(&[field.tag()])?;
+        // Length prefix keeps the boundaries of fields unambiguous
+        hasher.absorb(&(value.len() as u64).to_le_bytes())?;
+        hasher.absorb(&value)?;
+    }
+    hasher.finish()?;
+    Ok(hasher.hash()?.to_vec())
+}
@@ -1,30 +1,61 @@
+#[cfg(feature = "tokio")]
+mod asynchronous;
+pub(crate) mod cache;
+mod changes;
 mod error;
+mod kind;
+mod listing;
+pub(crate) mod manifest;
+mod merkle;
+mod metadata;
 pub(crate) mod options;
 mod pool;
 mod progress;
+mod stream;
 #[cfg(feature = "tracking")]
 mod tracking;
+mod verify;
+#[cfg(all(feature = "watcher", target_os = "linux"))]
+mod watcher;
 mod worker;
 
 use crate::{
-    collector::collect,
-    entry::{Entry, Filter},
+    collector::{self, collect_entry, Explanation},
+    entry::{Entry, Filter, PathFilter},
+    hasher::HasherConfig,
     Breaker, Hasher, Reader, Tolerance,
 };
+#[cfg(feature = "tokio")]
+pub use asynchronous::AsyncWalker;
+use cache::Cache;
+pub use changes::Changes;
 pub use error::E;
+pub use kind::{HashFn, HasherKind, ReaderKind};
+pub use listing::{FileKey, Listed, Origin};
 use log::{debug, error, warn};
-pub use options::{Options, ReadingStrategy};
+pub use manifest::{Manifest, ManifestFormat};
+use merkle::{merkle, DirHashes};
+pub use metadata::MetaField;
+pub use options::{Options, ReadingStrategy, SummaryMode};
 use pool::Pool;
 pub use progress::{JobType, Progress, ProgressChannel, Tick};
 use std::{
-    io, mem,
-    path::PathBuf,
-    sync::mpsc::{channel, Receiver, Sender},
+    collections::{HashMap, HashSet},
+    env, io, mem,
+    path::{Path, PathBuf},
+    sync::{
+        mpsc::{channel, Receiver, Sender},
+        Arc,
+    },
     thread::{self, JoinHandle},
     time::Instant,
 };
+pub use stream::HashStream;
 #[cfg(feature = "tracking")]
 pub use tracking::Tracking;
+pub use verify::{Verdict, Verification};
+#[cfg(all(feature = "watcher", target_os = "linux"))]
+pub use watcher::{WatchEvent, Watcher};
 pub use worker::Worker;
 
 /// The default minimum number of paths that will be given to a hash worker to calculate hashes.
@@ -82,6 +113,54 @@ pub enum Action {
 ///   file.
 type HashItem = (PathBuf, Option<Result<Vec<u8>, E>>);
 
+/// A result of hashing of a single file, as it's delivered by `HashStream`.
+type Streamed = (PathBuf, Result<Vec<u8>, E>);
+
+/// The summary hash, the results of hashing files and the hashes of folders (in Merkle mode).
+type HashingResult = Result<(Vec<u8>, Vec<HashItem>, DirHashes), E>;
+
+/// Hashing which has been started by `Walker` in a separate thread.
+struct Hashing {
+    handle: JoinHandle<HashingResult>,
+    cache: Option<Arc<Cache>>,
+    started: Instant,
+    total: usize,
+}
+
+/// Gathers the results of hashing reported by workers. If streaming is requested, each result is
+/// also sent into the stream; errors are moved into the stream, because `E` cannot be cloned.
+struct Results {
+    items: Vec<HashItem>,
+    stream: Option<Sender<Streamed>>,
+    /// The number of processed files, including failed ones.
+    count: usize,
+}
+
+impl Results {
+    fn new(stream: Option<Sender<Streamed>>) -> Self {
+        Self {
+            items: Vec::new(),
+            stream,
+            count: 0,
+        }
+    }
+
+    fn push(&mut self, path: PathBuf, result: Result<Vec<u8>, E>) {
+        self.count += 1;
+        match (&self.stream, result) {
+            (Some(stream), Ok(hash)) => {
+                // The receiver can be dropped; in this case hashing is going to be aborted anyway
+                let _ = stream.send((path.clone(), Ok(hash.clone())));
+                self.items.push((path, Some(Ok(hash))));
+            }
+            (Some(stream), Err(err)) => {
+                let _ = stream.send((path, Err(err)));
+            }
+            (None, result) => self.items.push((path, Some(result))),
+        }
+    }
+}
+
 /// `Walker` collects file paths according to a given pattern, then calculates the hash for each
 /// file and provides a combined hash for all files.
 ///
@@ -163,11 +242,22 @@ pub struct Walker {
     ///   file.
     pub paths: Vec<HashItem>,
 
+    /// Paths skipped during collecting along with the reasons, sorted by paths within each entry.
+    /// This field is populated by `collect()` only if `Options::record_skipped` is enabled.
+    pub skipped: Vec<Explanation>,
+
     /// The resulting hash. Set when `hash()` is called.
     hash: Option<Vec<u8>>,
 
     /// An instance of the channel for tracking the progress of path collection and hashing.
     progress: Option<ProgressChannel>,
+
+    /// The cache of hashes (if `Options::cache` is enabled). It's kept between calls of `hash()`
+    /// and, with the "tracking" feature, between application's runs.
+    cache: Option<Cache>,
+
+    /// The hashes of folders. Set when `hash()` is called with `SummaryMode::Merkle`.
+    dirs: DirHashes,
 }
 impl Walker {
     /// Creates a new instance of `Walker`.
@@ -185,8 +275,11 @@ impl Walker {
             opt: Some(opt),
             breaker: Breaker::new(),
             paths: Vec::new(),
+            skipped: Vec::new(),
             hash: None,
             progress,
+            cache: None,
+            dirs: HashMap::new(),
         }
     }
 
@@ -211,12 +304,14 @@ impl Walker {
         let opt = self.opt.as_mut().ok_or(E::IsNotInited)?;
         let progress = self.progress.as_ref().map(|(progress, _)| progress.clone());
         for entry in opt.entries.iter() {
-            let (collected, invalid) = collect(
+            let (collected, invalid) = collect_entry(
                 &progress,
                 entry,
+                &opt.global,
                 &self.breaker,
                 &opt.tolerance,
                 &opt.threads,
+                &opt.symlinks,
             )?;
             self.paths
                 .append(&mut collected.into_iter().map(|p| (p, None)).collect());
@@ -226,6 +321,26 @@ impl Walker {
                     .map(|(p, e)| (p, Some(Err(e.into()))))
                     .collect(),
             );
+            if opt.record_skipped {
+                self.skipped.append(&mut collector::skipped(
+                    entry,
+                    &opt.global,
+                    &opt.symlinks,
+                    &self.breaker,
+                )?);
+            }
+        }
+        // Files aren't filtered and go straight into hashing
+        if !opt.files.is_empty() {
+            let collected: HashSet<&PathBuf> = self.paths.iter().map(|(p, _)| p).collect();
+            let files: Vec<PathBuf> = opt
+                .files
+                .iter()
+                .filter(|file| !collected.contains(file))
+                .cloned()
+                .collect();
+            self.paths
+                .append(&mut files.into_iter().map(|p| (p, None)).collect());
         }
         debug!(
             "collected {} paths in {}µs / {}ms / {}s",
@@ -237,6 +352,70 @@ impl Walker {
         Ok(self)
     }
 
+    /// Explains whether the path would be collected by `collect()` and which rule decides it (for
+    /// context files, the file and the line of the rule). The path is checked against each entry it
+    /// belongs to with the global settings applied; the first entry, which accepts the path, is
+    /// reported. Files added with `Options::file()` are always accepted.
+    ///
+    /// Unlike `collect()`, this method doesn't read folders, so it can be used to check a single
+    /// path without collecting.
+    ///
+    /// # Parameters
+    ///
+    /// - `path`: The path to explain; a relative path is resolved against the current directory.
+    ///
+    /// # Returns
+    ///
+    /// - `Result<Explanation, E>`: The decision along with the decisive rule.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `Walker` isn't initialized or if a context file or git's files cannot
+    /// be read.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fshasher::{Entry, Filter, Options};
+    /// use std::env::temp_dir;
+    ///
+    /// let walker = Options::new()
+    ///     .entry(Entry::from(temp_dir()).unwrap())
+    ///     .unwrap()
+    ///     .exclude(Filter::Files("*.log"))
+    ///     .unwrap()
+    ///     .walker()
+    ///     .unwrap();
+    /// let explanation = walker.explain(temp_dir().join("a.log")).unwrap();
+    /// println!("{explanation}");
+    /// ```
+    pub fn explain<P: AsRef<Path>>(&self, path: P) -> Result<Explanation, E> {
+        let opt = self.opt.as_ref().ok_or(E::IsNotInited)?;
+        let path = if path.as_ref().is_absolute() {
+            path.as_ref().to_path_buf()
+        } else {
+            env::current_dir()?.join(path.as_ref())
+        };
+        if opt.files.contains(&path) {
+            return Ok(Explanation::file(&path));
+        }
+        let mut explanation = Explanation::outside(&path);
+        for entry in opt
+            .entries
+            .iter()
+            .filter(|entry| path.starts_with(&entry.entry))
+        {
+            let current = collector::explain(entry, &opt.global, &opt.symlinks, &path)?;
+            if current.accepted {
+                return Ok(current);
+            }
+            if explanation.reason == collector::Reason::OutsideEntry {
+                explanation = current;
+            }
+        }
+        Ok(explanation)
+    }
+
     /// Returns a `Breaker` which can be used to abort collecting and hashing operations.
     /// Interruption is done by calling the `abort()` method. The operation will be interrupted
     /// at the earliest possible time but not instantaneously.
@@ -322,9 +501,33 @@ impl Walker {
     where
         E: From<<H as Hasher>::Error> + From<<R as Reader>::Error>,
     {
-        let now = Instant::now();
+        match self.spawn_hashing::<H, R>(None)? {
+            Some(hashing) => self.finish_hashing(hashing),
+            None => Ok(&[]),
+        }
+    }
+
+    /// Starts hashing of collected paths in a separate thread.
+    ///
+    /// # Parameters
+    ///
+    /// - `stream`: If given, each result of hashing is sent into the channel as soon as it's
+    ///   reported by a worker. Errors are moved into the channel instead of being kept in `paths`.
+    ///
+    /// # Returns
+    ///
+    /// - `Result<Option<Hashing>, E>`: A handle of started hashing, or `None` if there is nothing
+    ///   to hash.
+    fn spawn_hashing<H: Hasher + 'static, R: Reader + 'static>(
+        &mut self,
+        stream: Option<Sender<Streamed>>,
+    ) -> Result<Option<Hashing>, E>
+    where
+        E: From<<H as Hasher>::Error> + From<<R as Reader>::Error>,
+    {
+        let started = Instant::now();
         if self.paths.is_empty() {
-            return Ok(&[]);
+            return Ok(None);
         }
         let opt = self.opt.as_mut().ok_or(E::IsNotInited)?;
         let tolerance = opt.tolerance.clone();
@@ -341,27 +544,42 @@ impl Walker {
             }
         }
         let threads = opt.threads.unwrap_or(cores);
-        let mut pool: Pool = Pool::new::<H, R>(
-            threads,
-            tx_queue.clone(),
-            &opt.reading_strategy,
-            &opt.tolerance,
-            &self.breaker,
-        );
+        // Invalid configuration of the hasher is reported before hashing, because otherwise it
+        // would be reported for each file (and can be ignored because of tolerance)
+        H::configured(&opt.hasher_config)?;
+        let config = opt.hasher_config.clone();
+        let cache = if opt.cache {
+            Some(Arc::new(match self.cache.take() {
+                Some(cache) if cache.is_for::<H, R>(&opt.hasher_config) => cache,
+                #[cfg(feature = "tracking")]
+                _ => Cache::load::<H, R>(&opt.hasher_config, &opt.storage, &tracking::alias(opt))?,
+                #[cfg(not(feature = "tracking"))]
+                _ => Cache::new::<H, R>(&opt.hasher_config),
+            }))
+        } else {
+            None
+        };
+        let mut pool: Pool =
+            Pool::new::<H, R>(threads, tx_queue.clone(), opt, &cache, &self.breaker);
         debug!("Created pool with {threads} workers for hashing");
         let mut paths = mem::take(&mut self.paths);
         let total = paths.len();
         let paths_per_jobs =
             ((total as f64 * 0.05).ceil() as usize).clamp(MIN_PATHS_PER_JOB, MAX_PATHS_PER_JOB);
+        let entries: Vec<PathBuf> = opt.roots();
+        let roots: Option<Vec<PathBuf>> = if opt.paths_in_summary {
+            Some(entries.clone())
+        } else {
+            None
+        };
+        let mode = opt.summary.clone();
 
-        type HashingResult<T> = Result<(T, Vec<HashItem>), E>;
-
-        let handle: JoinHandle<HashingResult<H>> = thread::spawn(move || {
+        let handle: JoinHandle<HashingResult> = thread::spawn(move || {
             fn check_err(
                 path: PathBuf,
                 err: E,
                 tolerance: &Tolerance,
-                hashes: &mut Vec<HashItem>,
+                hashes: &mut Results,
             ) -> Result<(), E> {
                 match tolerance {
                     Tolerance::StopOnErrors => {
@@ -370,11 +588,11 @@ impl Walker {
                     }
                     Tolerance::LogErrors => {
                         warn!("entry: {}; error: {err}", path.display());
-                        hashes.push((path, Some(Err(err))));
+                        hashes.push(path, Err(err));
                         Ok(())
                     }
                     Tolerance::DoNotLogErrors => {
-                        hashes.push((path, Some(Err(err))));
+                        hashes.push(path, Err(err));
                         Ok(())
                     }
                 }
@@ -383,7 +601,7 @@ impl Walker {
                 paths: &mut Vec<HashItem>,
                 paths_per_jobs: usize,
                 tolerance: &Tolerance,
-                hashes: &mut Vec<HashItem>,
+                hashes: &mut Results,
             ) -> Result<Vec<PathBuf>, E> {
                 let mut jobs = Vec::new();
                 while jobs.len() < paths_per_jobs && !paths.is_empty() {
@@ -392,7 +610,8 @@ impl Walker {
                         // Path marked by collector as caused error
                         continue;
                     }
-                    if !path.exists() {
+                    // Broken symlinks are hashed by their targets with SymlinkPolicy::HashTarget
+                    if !path.exists() && !path.is_symlink() {
                         check_err(
                             path,
                             io::Error::new(io::ErrorKind::NotFound, "File not found").into(),
@@ -410,7 +629,7 @@ impl Walker {
                 paths: &mut Vec<HashItem>,
                 paths_per_jobs: usize,
                 tolerance: &Tolerance,
-                hashes: &mut Vec<HashItem>,
+                hashes: &mut Results,
                 worker_id: Option<u16>,
             ) -> JobCollecting {
                 if paths.is_empty() {
@@ -463,8 +682,7 @@ impl Walker {
                 }
                 JobCollecting::Success
             }
-            let mut summary = H::new();
-            let mut hashes: Vec<HashItem> = Vec::new();
+            let mut hashes = Results::new(stream);
             let initialization = deligate(
                 pool.workers(),
                 &mut paths,
@@ -476,11 +694,10 @@ impl Walker {
             );
             if !matches!(initialization, JobCollecting::Success) {
                 pool.shutdown().wait();
-                summary.finish()?;
                 return if let JobCollecting::Err(err) = initialization {
                     Err(err)
                 } else {
-                    Ok((summary, hashes))
+                    Ok((Vec::new(), hashes.items, HashMap::new()))
                 };
             }
             let mut pending: Option<Action> = None;
@@ -501,14 +718,11 @@ impl Walker {
                             // If error reported by Worker, it's already not Tolerance::StopOnErrors
                             let _ = check_err(path, err, &tolerance, &mut hashes);
                         }
-                        hashes.append(
-                            &mut processed
-                                .into_iter()
-                                .map(|(p, h)| (p, Some(Ok(h))))
-                                .collect(),
-                        );
+                        for (path, hash) in processed.into_iter() {
+                            hashes.push(path, Ok(hash));
+                        }
                         if let Some(ref progress) = progress {
-                            progress.notify(JobType::Hashing, hashes.len(), total)
+                            progress.notify(JobType::Hashing, hashes.count, total)
                         }
                         match deligate(
                             pool.workers(),
@@ -549,31 +763,64 @@ impl Walker {
             if let Err(err) = outer {
                 Err(err)
             } else {
+                let mut hashes = hashes.items;
                 hashes.sort_by(|(a, _), (b, _)| a.cmp(b));
-                for (_, hash) in hashes.iter() {
+                let files = hashes.iter().filter_map(|(path, hash)| {
                     if let Some(Ok(hash)) = hash {
-                        summary.absorb(hash)?;
+                        Some((path.as_path(), hash.as_slice()))
+                    } else {
+                        None
                     }
-                }
-                summary.finish()?;
-                Ok((summary, hashes))
+                });
+                let (summary, dirs) = match mode {
+                    SummaryMode::Flat => (
+                        summarize::<H>(files, roots.as_deref(), &config)?,
+                        HashMap::new(),
+                    ),
+                    SummaryMode::Merkle => merkle::<H>(files, &entries, &config)?,
+                };
+                Ok((summary, hashes, dirs))
             }
         });
         self.progress = opt.progress.map(Progress::channel);
-        let (summary, mut hashes) = handle
+        Ok(Some(Hashing {
+            handle,
+            cache,
+            started,
+            total,
+        }))
+    }
+
+    /// Waits for the end of hashing started with `spawn_hashing()` and stores the results.
+    ///
+    /// # Parameters
+    ///
+    /// - `hashing`: The handle of started hashing.
+    ///
+    /// # Returns
+    ///
+    /// - `Result<&[u8], E>`: A hash calculated based on the collected paths.
+    fn finish_hashing(&mut self, hashing: Hashing) -> Result<&[u8], E> {
+        let Hashing {
+            handle,
+            cache,
+            started,
+            total,
+        } = hashing;
+        let opt = self.opt.as_ref().ok_or(E::IsNotInited)?;
+        let (summary, mut hashes, dirs) = handle
             .join()
             .map_err(|e| E::JoinError(format!("{e:?}")))??;
+        // All workers are down at this point, so the cache isn't shared anymore
+        if let Some(mut cache) = cache.and_then(Arc::into_inner) {
+            cache.rotate();
+            #[cfg(feature = "tracking")]
+            cache.save(&opt.storage, &tracking::alias(opt))?;
+            self.cache = Some(cache);
+        }
         self.paths = mem::take(&mut hashes);
-        let valid = self
-            .paths
-            .iter()
-            .filter(|(_, h)| if let Some(h) = h { h.is_ok() } else { false })
-            .count();
-        self.hash = Some(if valid == 0 || self.paths.is_empty() {
-            Vec::new()
-        } else {
-            summary.hash()?.to_vec()
-        });
+        self.hash = Some(summary);
+        self.dirs = dirs;
         self.progress = opt.progress.map(Progress::channel);
         let hash = if let Some(ref hash) = self.hash {
             hash
@@ -583,13 +830,58 @@ impl Walker {
         debug!(
             "hashing of {} paths in {}µs / {}ms / {}s",
             total,
-            now.elapsed().as_micros(),
-            now.elapsed().as_millis(),
-            now.elapsed().as_secs()
+            started.elapsed().as_micros(),
+            started.elapsed().as_millis(),
+            started.elapsed().as_secs()
         );
         Ok(hash)
     }
 
+    /// Returns the hash of a folder calculated with `SummaryMode::Merkle` by the last call of
+    /// `hash()`. The hash of a folder depends only on the names and content of files and nested
+    /// folders inside it, so it can be used to cache results per folder (for example, per package
+    /// in a monorepo) from a single walk. It doesn't depend on entries either: with nested entries,
+    /// the folder of the outer entry covers the files of the inner one.
+    ///
+    /// # Parameters
+    ///
+    /// - `path`: The path to the folder. A relative path is resolved against the current folder.
+    ///
+    /// # Returns
+    ///
+    /// - `Option<&[u8]>`: The hash of the folder, or `None` if hashing hasn't been done in Merkle
+    ///   mode or the folder doesn't contain any hashed files.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fshasher::{hasher, reader, Options, SummaryMode};
+    /// use std::env::temp_dir;
+    ///
+    /// let mut walker = Options::from(temp_dir())
+    ///     .unwrap()
+    ///     .summary(SummaryMode::Merkle)
+    ///     .walker()
+    ///     .unwrap();
+    /// let hash = walker
+    ///     .collect()
+    ///     .unwrap()
+    ///     .hash::<hasher::blake::Blake, reader::buffering::Buffering>()
+    ///     .unwrap()
+    ///     .to_vec();
+    /// if !hash.is_empty() {
+    ///     assert_eq!(walker.dir_hash(temp_dir()), Some(hash.as_slice()));
+    /// }
+    /// ```
+    pub fn dir_hash<P: AsRef<Path>>(&self, path: P) -> Option<&[u8]> {
+        let path = if path.as_ref().is_absolute() {
+            path.as_ref().to_path_buf()
+        } else {
+            env::current_dir().ok()?.join(path.as_ref())
+        };
+        self.dirs.get(&path).map(|hash| hash.as_slice())
+    }
+
     /// Returns an iterator to iterate over the collected `HashItem`s.
     ///
     /// # Returns
@@ -605,10 +897,93 @@ impl Walker {
     /// This method is used each time before `collect()` is called. It resets the previous state to default.
     fn reset(&mut self) {
         self.paths = Vec::new();
+        self.skipped = Vec::new();
         self.hash = None;
+        self.dirs = HashMap::new();
         self.breaker.reset();
     }
 }
+
+/// Calculates the summary hash of files. The hashes of files are absorbed in the given order; if
+/// `roots` are given, the relative path of each file is absorbed along with its hash.
+///
+/// # Parameters
+///
+/// - `hashes`: Paths and hashes of files, sorted by paths.
+/// - `roots`: The paths of entries, if paths should be included into the summary hash.
+/// - `config`: The configuration of the hasher.
+///
+/// # Returns
+///
+/// - `Result<Vec<u8>, E>`: The summary hash; an empty vector if there are no files.
+pub(crate) fn summarize<'a, H: Hasher>(
+    hashes: impl Iterator<Item = (&'a Path, &'a [u8])>,
+    roots: Option<&[PathBuf]>,
+    config: &HasherConfig,
+) -> Result<Vec<u8>, E>
+where
+    E: From<<H as Hasher>::Error>,
+{
+    let mut summary = H::configured(config)?;
+    let mut count = 0;
+    for (path, hash) in hashes {
+        if let Some(roots) = roots {
+            summary.absorb(relative_path(path, roots).as_bytes())?;
+            // Separator, which cannot be a part of path
+            summary.absorb(&[0])?;
+        }
+        summary.absorb(hash)?;
+        count += 1;
+    }
+    if count == 0 {
+        return Ok(Vec::new());
+    }
+    summary.finish()?;
+    Ok(summary.hash()?.to_vec())
+}
+
+/// Returns the path of a file relative to the entry it belongs to, using `/` as a separator. If
+/// entries are nested, the closest entry is used. If the file doesn't belong to any entry (for
+/// example, it has been reached via symlink), the full path is used.
+///
+/// # Parameters
+///
+/// - `path`: The full path to the file.
+/// - `roots`: The paths of entries.
+///
+/// # Returns
+///
+/// - `String`: The normalized relative path.
+pub(crate) fn relative_path(path: &Path, roots: &[PathBuf]) -> String {
+    closest_root(path, roots)
+        .map(|(_, rel)| {
+            rel.components()
+                .map(|cmp| cmp.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/")
+        })
+        .unwrap_or_else(|| path.to_string_lossy().to_string())
+}
+
+/// Finds the closest entry of the file. It's the same entry `relative_path()` uses.
+///
+/// # Parameters
+///
+/// - `path`: The full path to the file.
+/// - `roots`: The paths of entries.
+///
+/// # Returns
+///
+/// - `Option<(usize, &Path)>`: The index of the entry in `roots` along with the path relative to
+///   it; `None` if the file doesn't belong to any entry.
+pub(crate) fn closest_root<'a>(path: &'a Path, roots: &[PathBuf]) -> Option<(usize, &'a Path)> {
+    roots
+        .iter()
+        .enumerate()
+        .filter_map(|(index, root)| path.strip_prefix(root).ok().map(|rel| (index, rel)))
+        .min_by_key(|(_, rel)| rel.components().count())
+}
+
 /// An iterator over the calculated hashes in a `Walker`.
 ///
 /// `WalkerIter` is used to iterate over `HashItem` that represent the paths and their corresponding hashes
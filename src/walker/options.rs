@@ -1,10 +1,20 @@
-use super::{Entry, Filter, Walker, E};
-use crate::collector::Tolerance;
+use super::{
+    kind::{hash_with, HashFn, HasherKind, ReaderKind},
+    metadata::MetaField,
+    Entry, Filter, PathFilter, Walker, E,
+};
 #[cfg(feature = "tracking")]
 use crate::walker::tracking::get_default_path;
-#[cfg(feature = "tracking")]
-use std::path::PathBuf;
-use std::{ops::Range, path::Path, thread};
+use crate::{
+    collector::{SymlinkPolicy, Tolerance},
+    hasher::HasherConfig,
+    Hasher,
+};
+use std::{
+    ops::Range,
+    path::{Path, PathBuf},
+    thread,
+};
 
 pub(crate) const MIN_THREADS_COUNT: usize = 1;
 pub(crate) const MAX_THREADS_MLT_TO_CORES: usize = 2;
@@ -27,6 +37,18 @@ pub enum ReadingStrategy {
     Scenario(Vec<(Range<u64>, Box<ReadingStrategy>)>),
 }
 
+/// Defines how the summary hash is composed from the hashes of files.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum SummaryMode {
+    /// The hashes of files (sorted by paths) are absorbed into the summary hash one by one.
+    #[default]
+    Flat,
+    /// Each folder gets its own hash, calculated from the names and hashes of its children (files
+    /// and nested folders), like a Merkle tree. The hash of any folder is available with
+    /// `Walker::dir_hash()`. Names are always included, so `Options::paths_in_summary` isn't used.
+    Merkle,
+}
+
 /// Configuration options for the `Walker`.
 #[derive(Default, Debug, Clone)]
 pub struct Options {
@@ -39,6 +61,10 @@ pub struct Options {
     /// List of entries (paths) to be processed.
     pub entries: Vec<Entry>,
 
+    /// List of files to be hashed along with files collected from entries. Files aren't filtered
+    /// and go straight into hashing.
+    pub files: Vec<PathBuf>,
+
     /// Global entry settings that apply to all entries.
     pub global: Entry,
 
@@ -52,6 +78,43 @@ pub struct Options {
     /// Strategy for reading files.
     pub reading_strategy: ReadingStrategy,
 
+    /// Defines how symlinks are handled during collecting and hashing.
+    pub symlinks: SymlinkPolicy,
+
+    /// If `true`, `Walker::collect()` records paths, which aren't collected, along with the reasons
+    /// (see `Walker::skipped`).
+    pub record_skipped: bool,
+
+    /// If `true`, the path of each file relative to its entry is mixed into the summary hash along
+    /// with the file's hash. In this case, renaming or moving files changes the summary hash.
+    pub paths_in_summary: bool,
+
+    /// Defines how the summary hash is composed from the hashes of files.
+    pub summary: SummaryMode,
+
+    /// If `true`, hashes of files are cached along with their metadata (size, modification time,
+    /// inode); files with unchanged metadata are not read again. With the "tracking" feature, the
+    /// cache is saved into the storage.
+    pub cache: bool,
+
+    /// The hasher used by `Walker::hash_dyn()`.
+    pub hasher: HasherKind,
+
+    /// The reader used by `Walker::hash_dyn()`.
+    pub reader: ReaderKind,
+
+    /// The configuration of the hasher (key, context, length of output). It's used to construct
+    /// hashers with `Hasher::configured()` for files and for the summary hash.
+    pub hasher_config: HasherConfig,
+
+    /// Fields of metadata (permissions, owner, etc.) mixed into the hash of each file along with
+    /// its content. Sorted and without duplicates.
+    pub metadata: Vec<MetaField>,
+
+    /// Custom hashers registered with `register_hasher()`, available for `Walker::hash_dyn()` with
+    /// `HasherKind::Custom`.
+    pub hashers: Vec<(String, HashFn)>,
+
     /// A path to store states of checks. Used with "tracking" feature
     #[cfg(feature = "tracking")]
     pub storage: PathBuf,
@@ -67,10 +130,21 @@ impl Options {
         Self {
             tolerance: Tolerance::LogErrors,
             entries: Vec::new(),
+            files: Vec::new(),
             global: Entry::default(),
             progress: None,
             threads: None,
             reading_strategy: ReadingStrategy::default(),
+            symlinks: SymlinkPolicy::default(),
+            record_skipped: false,
+            paths_in_summary: false,
+            summary: SummaryMode::default(),
+            cache: false,
+            hasher: HasherKind::default(),
+            reader: ReaderKind::default(),
+            hasher_config: HasherConfig::default(),
+            metadata: Vec::new(),
+            hashers: Vec::new(),
             #[cfg(feature = "tracking")]
             storage: get_default_path(),
         }
@@ -89,10 +163,21 @@ impl Options {
         Ok(Self {
             tolerance: Tolerance::LogErrors,
             entries: vec![Entry::from(path)?],
+            files: Vec::new(),
             global: Entry::default(),
             progress: None,
             threads: None,
             reading_strategy: ReadingStrategy::default(),
+            symlinks: SymlinkPolicy::default(),
+            record_skipped: false,
+            paths_in_summary: false,
+            summary: SummaryMode::default(),
+            cache: false,
+            hasher: HasherKind::default(),
+            reader: ReaderKind::default(),
+            hasher_config: HasherConfig::default(),
+            metadata: Vec::new(),
+            hashers: Vec::new(),
             #[cfg(feature = "tracking")]
             storage: get_default_path(),
         })
@@ -124,6 +209,41 @@ impl Options {
         Ok(self)
     }
 
+    /// Sets the way symlinks are handled. By default, symlinks are followed (`SymlinkPolicy::Follow`).
+    /// Symlinks leading to loops are never followed.
+    ///
+    /// # Parameters
+    ///
+    /// - `policy`: The way symlinks are handled.
+    ///
+    /// # Returns
+    ///
+    /// - An `Options` instance.
+    pub fn symlinks(mut self, policy: SymlinkPolicy) -> Self {
+        self.symlinks = policy;
+        self
+    }
+
+    /// Enables recording of skipped paths for debugging filters. With this option, `Walker::collect()`
+    /// looks through the entries once more after collecting and puts each path, which isn't collected,
+    /// into `Walker::skipped` along with the decisive rule. The content of a skipped folder isn't
+    /// listed; the folder is recorded instead.
+    ///
+    /// The additional traversal is done in the current thread, so this option is intended for
+    /// debugging rather than for regular use.
+    ///
+    /// # Parameters
+    ///
+    /// - `value`: `true` to record skipped paths.
+    ///
+    /// # Returns
+    ///
+    /// - An `Options` instance.
+    pub fn record_skipped(mut self, value: bool) -> Self {
+        self.record_skipped = value;
+        self
+    }
+
     /// Sets the number of threads to use for collecting and hashing.
     ///
     /// # Parameters
@@ -175,6 +295,156 @@ impl Options {
         self
     }
 
+    /// Defines whether the paths of files (relative to their entries) should be included in the summary
+    /// hash. By default, only the content of files affects the summary hash, which means renaming
+    /// `a.txt` to `b.txt` or moving a file into another subfolder doesn't change it. With this option
+    /// enabled, the structure of the destination becomes a part of the state.
+    ///
+    /// Relative paths are normalized to use `/` as a separator, so the summary hash doesn't depend on
+    /// the platform or on the location of entries.
+    ///
+    /// This option is disabled by default to keep summary hashes compatible with previous versions;
+    /// it's going to be enabled by default with the next major version.
+    ///
+    /// # Parameters
+    ///
+    /// - `value`: `true` to include relative paths into the summary hash.
+    ///
+    /// # Returns
+    ///
+    /// - An `Options` instance.
+    pub fn paths_in_summary(mut self, value: bool) -> Self {
+        self.paths_in_summary = value;
+        self
+    }
+
+    /// Sets the way the summary hash is composed. With `SummaryMode::Merkle`, each folder gets its
+    /// own hash, which is available with `Walker::dir_hash()`.
+    ///
+    /// # Parameters
+    ///
+    /// - `mode`: The mode of composition.
+    ///
+    /// # Returns
+    ///
+    /// - An `Options` instance.
+    pub fn summary(mut self, mode: SummaryMode) -> Self {
+        self.summary = mode;
+        self
+    }
+
+    /// Enables the cache of hashes. Each time a file is hashed, its hash is saved along with the
+    /// metadata of the file (size, modification time, and inode and status change time on Unix).
+    /// Next time, if the metadata of the file hasn't been changed, the cached hash is used and the
+    /// file isn't read at all. This gives nearly the speed of the `Md` reader, but with real content
+    /// hashes of changed files.
+    ///
+    /// The cache is kept by `Walker` between calls of `hash()`. With the "tracking" feature, the cache
+    /// is also saved into the storage (see `storage()`), so it works between application's runs.
+    ///
+    /// Files modified at the same second the previous hashing has been started (or later) are
+    /// hashed again, because their changes during hashing might not be reflected by timestamps
+    /// (like git's racy-index check). On platforms other than Unix, a file modified without
+    /// changing its size and modification time will not be detected as changed.
+    ///
+    /// # Parameters
+    ///
+    /// - `value`: `true` to enable the cache.
+    ///
+    /// # Returns
+    ///
+    /// - An `Options` instance.
+    pub fn cache(mut self, value: bool) -> Self {
+        self.cache = value;
+        self
+    }
+
+    /// Sets the hasher used by `Walker::hash_dyn()`.
+    ///
+    /// # Parameters
+    ///
+    /// - `hasher`: The hasher to use; custom hashers should be registered with `register_hasher()`.
+    ///
+    /// # Returns
+    ///
+    /// - An `Options` instance.
+    pub fn hasher(mut self, hasher: HasherKind) -> Self {
+        self.hasher = hasher;
+        self
+    }
+
+    /// Sets the reader used by `Walker::hash_dyn()`.
+    ///
+    /// # Parameters
+    ///
+    /// - `reader`: The reader to use.
+    ///
+    /// # Returns
+    ///
+    /// - An `Options` instance.
+    pub fn reader(mut self, reader: ReaderKind) -> Self {
+        self.reader = reader;
+        self
+    }
+
+    /// Sets the configuration of the hasher, for example, a key for keyed hashing or a context
+    /// string for derive-key mode. The configuration should be supported by the hasher used;
+    /// otherwise, hashing fails.
+    ///
+    /// # Parameters
+    ///
+    /// - `config`: The configuration of the hasher.
+    ///
+    /// # Returns
+    ///
+    /// - An `Options` instance.
+    pub fn hasher_config(mut self, config: HasherConfig) -> Self {
+        self.hasher_config = config;
+        self
+    }
+
+    /// Adds a field of metadata to be mixed into the hash of each file along with its content. For
+    /// example, with `MetaField::Mode`, making a file executable changes its hash. The order of
+    /// calls doesn't matter; adding the same field twice has no effect.
+    ///
+    /// The cache of hashes (see `cache()`) keeps hashes of contents only, so changes of the mixed
+    /// metadata are always detected.
+    ///
+    /// # Parameters
+    ///
+    /// - `field`: The field of metadata to mix in.
+    ///
+    /// # Returns
+    ///
+    /// - An `Options` instance.
+    pub fn metadata(mut self, field: MetaField) -> Self {
+        if let Err(pos) = self.metadata.binary_search(&field) {
+            self.metadata.insert(pos, field);
+        }
+        self
+    }
+
+    /// Registers a custom hasher, which can be selected at runtime with `HasherKind::Custom(name)`
+    /// and used by `Walker::hash_dyn()`. If a hasher with the same name has been registered
+    /// already, it's replaced.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: The name of the hasher.
+    ///
+    /// # Returns
+    ///
+    /// - An `Options` instance.
+    pub fn register_hasher<H: Hasher + 'static, S: AsRef<str>>(mut self, name: S) -> Self
+    where
+        E: From<<H as Hasher>::Error>,
+    {
+        let name = name.as_ref().to_owned();
+        self.hashers.retain(|(registered, _)| registered != &name);
+        self.hashers.push((name, hash_with::<H>));
+        self
+    }
+
     /// Adds a path to the list of entries to be processed.
     ///
     /// # Parameters
@@ -206,7 +476,64 @@ impl Options {
         Ok(self)
     }
 
-    /// Adds an include filter to the global entry.
+    /// Adds a file to be hashed along with files collected from entries. The file isn't filtered:
+    /// neither filters nor context files are applied to it. If the file is collected from an entry
+    /// as well, it's hashed only once.
+    ///
+    /// # Parameters
+    ///
+    /// - `path`: The absolute path to the file.
+    ///
+    /// # Returns
+    ///
+    /// - `Result<Self, E>`: An instance of `Options` or an error if the path is relative or isn't a
+    ///   path to an existing file.
+    pub fn file<P: AsRef<Path>>(mut self, path: P) -> Result<Self, E> {
+        let path = path.as_ref().to_path_buf();
+        if !path.is_absolute() {
+            return Err(E::RelativePathAsEntry(path));
+        } else if !path.is_file() {
+            return Err(E::OnlyFileAsEntry(path));
+        }
+        if !self.files.contains(&path) {
+            self.files.push(path);
+        }
+        Ok(self)
+    }
+
+    /// Adds files to be hashed along with files collected from entries. The same as `file()`, but
+    /// for a list of files.
+    ///
+    /// # Parameters
+    ///
+    /// - `paths`: The absolute paths to files.
+    ///
+    /// # Returns
+    ///
+    /// - `Result<Self, E>`: An instance of `Options` or an error if at least one of the paths is
+    ///   relative or isn't a path to an existing file.
+    pub fn files<I: IntoIterator<Item = P>, P: AsRef<Path>>(mut self, paths: I) -> Result<Self, E> {
+        for path in paths.into_iter() {
+            self = self.file(path)?;
+        }
+        Ok(self)
+    }
+
+    /// Returns the folders, which paths of files are related to: paths of entries and folders of
+    /// files added with `file()`. Used to make relative paths for the summary hash.
+    pub(crate) fn roots(&self) -> Vec<PathBuf> {
+        let mut roots: Vec<PathBuf> = self.entries.iter().map(|en| en.entry.clone()).collect();
+        for parent in self.files.iter().filter_map(|file| file.parent()) {
+            if !roots.iter().any(|root| root == parent) {
+                roots.push(parent.to_path_buf());
+            }
+        }
+        roots
+    }
+
+    /// Adds an include filter to the global entry. Global filters are applied to each entry in
+    /// addition to the entry's own filters: a path is collected only if it's accepted by both. If
+    /// both the entry and the global entry have include filters, a path should match both of them.
     ///
     /// # Parameters
     ///
@@ -220,7 +547,9 @@ impl Options {
         Ok(self)
     }
 
-    /// Adds an exclude filter to the global entry.
+    /// Adds an exclude filter to the global entry. Global filters are applied to each entry in
+    /// addition to the entry's own filters: a path excluded by the global entry will not be
+    /// collected even if it's explicitly included by the entry.
     ///
     /// # Parameters
     ///
@@ -234,6 +563,26 @@ impl Options {
         Ok(self)
     }
 
+    /// Adds a custom filter to the global entry. Global filters are applied to each entry in
+    /// addition to the entry's own filters. See `Entry::path_filter()`.
+    ///
+    /// # Parameters
+    ///
+    /// - `identity`: A string, which identifies the filter in the tracking key.
+    /// - `filter`: The filter (an implementation of `PathFilter` or a closure).
+    ///
+    /// # Returns
+    ///
+    /// - An `Options` instance.
+    pub fn path_filter<S: AsRef<str>, F: PathFilter + 'static>(
+        mut self,
+        identity: S,
+        filter: F,
+    ) -> Self {
+        self.global = self.global.path_filter(identity, filter);
+        self
+    }
+
     /// Sets the path to the storage for saving hashes for the "tracking" feature. `fshasher` will use this storage to save hashes and later compare
     /// them with new hashes to determine if changes have occurred.
     ///
@@ -264,7 +613,7 @@ impl Options {
     /// - `String` - hash calculated based on entries
     #[cfg(feature = "tracking")]
     pub fn hash(&self) -> Vec<u8> {
-        format!(
+        let mut hash = format!(
             "{}:{}",
             self.entries
                 .iter()
@@ -272,9 +621,39 @@ impl Options {
                 .collect::<Vec<String>>()
                 .join(";"),
             self.global
-        )
-        .as_bytes()
-        .to_vec()
+        );
+        // Files are added only if they are defined to keep the hash of previous versions
+        if !self.files.is_empty() {
+            hash = format!(
+                "{hash}:{}",
+                self.files
+                    .iter()
+                    .map(|file| file.display().to_string())
+                    .collect::<Vec<String>>()
+                    .join(";")
+            );
+        }
+        // The same for the policy of symlinks: it's added only if it isn't the default one
+        if self.symlinks != SymlinkPolicy::default() {
+            hash = format!("{hash}:symlinks={:?}", self.symlinks);
+        }
+        if self.paths_in_summary {
+            hash = format!("{hash}:paths_in_summary");
+        }
+        if self.summary != SummaryMode::default() {
+            hash = format!("{hash}:summary={:?}", self.summary);
+        }
+        if !self.metadata.is_empty() {
+            let mut fields = self.metadata.clone();
+            fields.sort();
+            fields.dedup();
+            hash = format!("{hash}:metadata={fields:?}");
+        }
+        // The key of the hasher isn't revealed in the storage
+        if !self.hasher_config.is_empty() {
+            hash = format!("{hash}:hasher={}", self.hasher_config.fingerprint());
+        }
+        hash.as_bytes().to_vec()
     }
     /// Creates a `Walker` with the specified hasher and reader.
     ///
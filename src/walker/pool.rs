@@ -1,6 +1,9 @@
-use super::{Action, ReadingStrategy, Worker};
-use crate::{breaker::Breaker, walker, Hasher, Reader, Tolerance};
-use std::{slice::Iter, sync::mpsc::Sender};
+use super::{cache::Cache, Action, Options, Worker};
+use crate::{breaker::Breaker, walker, Hasher, Reader};
+use std::{
+    slice::Iter,
+    sync::{mpsc::Sender, Arc},
+};
 
 /// Created by the `Walker` function to manage available workers. Each worker takes a vector of file paths and manages the calculation of their hashes.
 /// To calculate a hash, the worker reads the file with the given reader and provides the file's content to the hasher, which returns the hash of the file.
@@ -17,7 +20,8 @@ impl Pool {
     ///
     /// - `count`: The number of workers to create.
     /// - `tx_queue`: The sender channel for sending actions to the workers.
-    /// - `reading_strategy`: The strategy for reading files.
+    /// - `opt`: The options of `Walker`, which are given to each worker: the configuration of the
+    ///   hasher, the tolerance level and the reading strategy, which defines how files are read:
     ///   - `ReadingStrategy::Buffer` - Each file will be read in the "classic" way using a limited size buffer, chunk by
     ///     chunk until the end. The hasher will receive small chunks of data to calculate the hash of the file. This strategy
     ///     doesn't load the CPU much, but it entails many IO operations.
@@ -27,6 +31,7 @@ impl Pool {
     ///     the full content of the file to the hasher.
     ///   - `ReadingStrategy::Scenario(..)` - The scenario strategy can be used to combine different strategies according to the
     ///     file's size.
+    /// - `cache`: The cache of hashes shared between workers, if caching is enabled.
     /// - `breaker`: The breaker to handle interruptions.
     ///
     /// # Returns
@@ -35,8 +40,8 @@ impl Pool {
     pub fn new<H: Hasher + 'static, R: Reader + 'static>(
         count: usize,
         tx_queue: Sender<Action>,
-        reading_strategy: &ReadingStrategy,
-        tolerance: &Tolerance,
+        opt: &Options,
+        cache: &Option<Arc<Cache>>,
         breaker: &Breaker,
     ) -> Self
     where
@@ -46,8 +51,8 @@ impl Pool {
         for id in 0..count {
             workers.push(Worker::run::<H, R>(
                 tx_queue.clone(),
-                reading_strategy.clone(),
-                tolerance.clone(),
+                opt.clone(),
+                cache.clone(),
                 breaker.clone(),
                 id as u16,
             ));
@@ -63,7 +68,7 @@ impl Pool {
     /// # Returns
     ///
     /// - `Iter<Worker>`: An iterator over the workers.
-    pub fn iter(&self) -> Iter<Worker> {
+    pub fn iter(&self) -> Iter<'_, Worker> {
         self.workers.iter()
     }
 
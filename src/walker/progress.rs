@@ -62,6 +62,10 @@ pub type ProgressChannel = (Progress, Option<Receiver<Tick>>);
 pub enum Tx {
     Unbound(Sender<Tick>),
     Bound(SyncSender<Tick>),
+    /// The async channel of `AsyncWalker`. Available with feature "tokio". Ticks are sent with
+    /// `blocking_send()`, so it should be used only from a blocking thread.
+    #[cfg(feature = "tokio")]
+    Async(tokio::sync::mpsc::Sender<Tick>),
 }
 
 impl Tx {
@@ -82,6 +86,8 @@ impl Tx {
                 Err(TrySendError::Full(_)) => false,
                 Err(_err) => true,
             },
+            #[cfg(feature = "tokio")]
+            Self::Async(tx) => tx.blocking_send(tick).is_err(),
         }
     }
 }
@@ -98,12 +104,12 @@ impl Progress {
     /// # Parameters
     ///
     /// - `capacity`: The capacity of the channel. If `0`, an unbounded channel is created.
-    ///     The usage of an unbounded channel provides more detailed (not frequent) updates of progress.
-    ///     In other words, a `Tick` will be sent for any update of the `Walker` and `collect()` state.
-    ///     However, in the case of many files (more than 1K), this becomes impractical. At some point,
-    ///     the size of the channel queue can reach very large values. The common recommendation is to
-    ///     use a limited channel with a capacity between 10 and 100. This range is sufficient to
-    ///     maintain a good frequency of messages while keeping the channel's queue manageable.
+    ///   The usage of an unbounded channel provides more detailed (not frequent) updates of progress.
+    ///   In other words, a `Tick` will be sent for any update of the `Walker` and `collect()` state.
+    ///   However, in the case of many files (more than 1K), this becomes impractical. At some point,
+    ///   the size of the channel queue can reach very large values. The common recommendation is to
+    ///   use a limited channel with a capacity between 10 and 100. This range is sufficient to
+    ///   maintain a good frequency of messages while keeping the channel's queue manageable.
     ///
     /// Note: Regardless of whether a bounded or unbounded channel is created, `Walker` and
     /// `collect()` send a `Tick` with each update of their state. This can result in many messages
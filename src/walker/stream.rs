@@ -0,0 +1,123 @@
+use super::{Hashing, Streamed, Walker, E};
+use crate::{Hasher, Reader};
+use std::sync::mpsc::{channel, Receiver};
+
+/// An iterator over the results of hashing, which are yielded as soon as workers report them. It's
+/// returned by `Walker::hash_stream()`.
+///
+/// Files are yielded in the order of processing (not sorted). Each item is a path and the hash of
+/// the file, or the related error (if the tolerance level allows continuing hashing).
+///
+/// As soon as all files are processed, the iteration ends and the results are stored in `Walker`
+/// in the same way as with `Walker::hash()`, except errors: they are moved into the stream and
+/// aren't kept in the `paths` field. The summary hash can be taken with `finish()`.
+///
+/// If `HashStream` is dropped before all files are processed, hashing is aborted.
+pub struct HashStream<'a> {
+    walker: Option<&'a mut Walker>,
+    rx: Receiver<Streamed>,
+    hashing: Option<Hashing>,
+    result: Option<Result<(), E>>,
+}
+
+impl<'a> HashStream<'a> {
+    /// Drains the stream (if it hasn't been iterated to the end yet) and returns the summary hash.
+    ///
+    /// # Returns
+    ///
+    /// - `Result<&[u8], E>`: A hash calculated based on the collected paths.
+    ///
+    /// # Errors
+    ///
+    /// The same as `Walker::hash()`.
+    pub fn finish(mut self) -> Result<&'a [u8], E> {
+        for _ in self.by_ref() {}
+        self.result.take().unwrap_or(Ok(()))?;
+        let Some(walker) = self.walker.take() else {
+            unreachable!("Walker is taken only by finish()");
+        };
+        Ok(walker.hash.as_deref().unwrap_or(&[]))
+    }
+
+    fn complete(&mut self) {
+        let (Some(walker), Some(hashing)) = (self.walker.as_mut(), self.hashing.take()) else {
+            return;
+        };
+        self.result = Some(walker.finish_hashing(hashing).map(|_| ()));
+    }
+}
+
+impl Iterator for HashStream<'_> {
+    type Item = Streamed;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Ok(item) = self.rx.recv() {
+            Some(item)
+        } else {
+            self.complete();
+            None
+        }
+    }
+}
+
+impl Drop for HashStream<'_> {
+    fn drop(&mut self) {
+        let (Some(walker), Some(hashing)) = (self.walker.as_mut(), self.hashing.take()) else {
+            return;
+        };
+        walker.breaker.abort();
+        let _ = walker.finish_hashing(hashing);
+        // Stream has been dropped by consumer; it isn't an abort of walker's operations
+        walker.breaker.reset();
+    }
+}
+
+impl Walker {
+    /// Starts hashing and returns a stream of results, which are yielded as soon as workers report
+    /// them. It allows processing files (uploading, indexing, etc.) while hashing continues. As
+    /// well as `hash()`, it should be used in pair with `collect()`.
+    ///
+    /// # Returns
+    ///
+    /// - `Result<HashStream<'_>, E>`: An iterator over paths and results of hashing. The summary
+    ///   hash is available with `HashStream::finish()` at the end.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if hashing cannot be started. Errors of hashing itself are
+    /// returned by `HashStream::finish()` or, in the case of errors related to files, yielded by
+    /// the stream (according to tolerance settings).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fshasher::{hasher, reader, Options};
+    /// use std::env::temp_dir;
+    ///
+    /// let mut walker = Options::from(temp_dir()).unwrap().walker().unwrap();
+    /// walker.collect().unwrap();
+    /// let mut stream = walker
+    ///     .hash_stream::<hasher::blake::Blake, reader::buffering::Buffering>()
+    ///     .unwrap();
+    /// for (path, result) in stream.by_ref() {
+    ///     println!("{}: {result:?}", path.display());
+    /// }
+    /// let hash = stream.finish();
+    /// println!("Hash of {}: {hash:?}", temp_dir().display());
+    /// ```
+    pub fn hash_stream<H: Hasher + 'static, R: Reader + 'static>(
+        &mut self,
+    ) -> Result<HashStream<'_>, E>
+    where
+        E: From<<H as Hasher>::Error> + From<<R as Reader>::Error>,
+    {
+        let (tx, rx) = channel();
+        let hashing = self.spawn_hashing::<H, R>(Some(tx))?;
+        Ok(HashStream {
+            walker: Some(self),
+            rx,
+            hashing,
+            result: None,
+        })
+    }
+}
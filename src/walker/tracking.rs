@@ -1,4 +1,7 @@
-use crate::{walker::E, Hasher, Reader, Walker};
+use crate::{
+    walker::{Changes, E},
+    Hasher, Options, Reader, Walker,
+};
 use blake3::Hasher as BlakeHasher;
 use bstorage::Storage;
 use serde::{Deserialize, Serialize};
@@ -16,11 +19,25 @@ pub(crate) fn get_storage_name() -> String {
     "tracking".to_owned()
 }
 
+/// Returns the key of records related to the given options in the storage.
+pub(crate) fn alias(opt: &Options) -> String {
+    BlakeHasher::new()
+        .update(&opt.hash())
+        .finalize()
+        .to_string()
+}
+
 #[derive(Debug, Default, Serialize, Deserialize)]
 struct PreviousHash {
     hash: Vec<u8>,
 }
 
+/// Stored separately from `PreviousHash` to keep records of previous versions readable.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PreviousFiles {
+    files: Vec<(PathBuf, Vec<u8>)>,
+}
+
 /// Available with feature "tracking". Provides the `is_same()` method.
 /// `fshasher` will create storage to save information about recently calculated hashes. Using the `is_same()` method
 /// it will be possible to detect if any changes have occurred or not.
@@ -68,6 +85,43 @@ pub trait Tracking {
     fn is_same<H: Hasher + 'static, R: Reader + 'static>(&mut self) -> Result<bool, E>
     where
         E: From<<H as Hasher>::Error> + From<<R as Reader>::Error>;
+
+    /// Returns a per-file report of changes of the destination since the last check. In contrast to
+    /// `is_same()`, it allows to find out which files exactly have been added, removed or modified.
+    ///
+    /// Both `is_same()` and `changes()` share the same storage, so they can be mixed; each call
+    /// saves the current state as the state of the last check.
+    ///
+    /// # Returns
+    ///
+    /// - `Changes` - lists of added, removed, modified and unchanged files.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the hashing or reading operation fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fshasher::{hasher, reader, Entry, Options, Tolerance, Tracking};
+    /// use std::env::temp_dir;
+    ///
+    /// let mut walker = Options::new()
+    ///     .entry(Entry::from(temp_dir()).unwrap())
+    ///     .unwrap()
+    ///     .tolerance(Tolerance::LogErrors)
+    ///     .walker()
+    ///     .unwrap();
+    /// let changes = walker
+    ///     .changes::<hasher::blake::Blake, reader::buffering::Buffering>()
+    ///     .unwrap();
+    /// for path in changes.added.iter().chain(changes.modified.iter()) {
+    ///     println!("Should be rebuilt: {}", path.display());
+    /// }
+    /// ```
+    fn changes<H: Hasher + 'static, R: Reader + 'static>(&mut self) -> Result<Changes, E>
+    where
+        E: From<<H as Hasher>::Error> + From<<R as Reader>::Error>;
 }
 
 impl Tracking for Walker {
@@ -75,27 +129,67 @@ impl Tracking for Walker {
     where
         E: From<<H as Hasher>::Error> + From<<R as Reader>::Error>,
     {
-        let opt = self.opt.as_ref().ok_or(E::IsNotInited)?;
-        let storage = opt.storage.clone();
-        let alias = BlakeHasher::new()
-            .update(&opt.hash())
-            .finalize()
-            .to_string();
-        let nhash = self.collect()?.hash::<H, R>()?.to_vec();
-        let mut storage = Storage::create(storage)?;
-        let mut phash: PreviousHash = storage.get_or_default(&alias)?;
-        let changed = phash.hash == nhash;
-        phash.hash = nhash;
-        storage.set(alias, &phash)?;
-        Ok(changed)
+        Ok(check::<H, R>(self)?.0)
     }
+
+    fn changes<H: Hasher + 'static, R: Reader + 'static>(&mut self) -> Result<Changes, E>
+    where
+        E: From<<H as Hasher>::Error> + From<<R as Reader>::Error>,
+    {
+        Ok(check::<H, R>(self)?.1)
+    }
+}
+
+/// Calculates the current state of the destination, compares it with the saved one and saves the
+/// current state instead.
+///
+/// # Returns
+///
+/// - `(bool, Changes)` - `true` if the summary hash is the same and the per-file report.
+fn check<H: Hasher + 'static, R: Reader + 'static>(
+    walker: &mut Walker,
+) -> Result<(bool, Changes), E>
+where
+    E: From<<H as Hasher>::Error> + From<<R as Reader>::Error>,
+{
+    let opt = walker.opt.as_ref().ok_or(E::IsNotInited)?;
+    let storage = opt.storage.clone();
+    let alias = alias(opt);
+    let nhash = walker.collect()?.hash::<H, R>()?.to_vec();
+    let nfiles: Vec<(PathBuf, Vec<u8>)> = walker
+        .iter()
+        .filter_map(|(p, h)| {
+            if let Some(Ok(h)) = h {
+                Some((p.to_owned(), h.to_owned()))
+            } else {
+                None
+            }
+        })
+        .collect();
+    let mut storage = Storage::create(storage)?;
+    let mut phash: PreviousHash = storage.get_or_default(&alias)?;
+    let mut pfiles: PreviousFiles = storage.get_or_default(format!("{alias}:files"))?;
+    let same = phash.hash == nhash;
+    let changes = Changes::compare(&pfiles.files, &nfiles);
+    phash.hash = nhash;
+    pfiles.files = nfiles;
+    storage.set(&alias, &phash)?;
+    storage.set(format!("{alias}:files"), &pfiles)?;
+    Ok((same, changes))
 }
 
 #[cfg(test)]
 mod test {
-    use std::{env::temp_dir, fs::remove_dir_all};
+    use std::{
+        env::temp_dir,
+        fs::{remove_dir_all, remove_file},
+    };
 
-    use crate::{hasher, reader, test::usecase::*, Entry, Options, Tolerance, Tracking, E};
+    use crate::{
+        hasher, reader,
+        test::{usecase::*, utils::create_text_file},
+        Entry, Options, Tolerance, Tracking, E,
+    };
 
     #[test]
     fn tracking() -> Result<(), E> {
@@ -145,4 +239,46 @@ mod test {
         remove_dir_all(custom_path)?;
         Ok(())
     }
+
+    #[test]
+    fn tracking_changes() -> Result<(), E> {
+        let usecase = UseCase::unnamed(2, 2, 2, &[])?;
+        let custom_path = temp_dir().join(uuid::Uuid::new_v4().to_string());
+        let mut walker = Options::new()
+            .entry(Entry::from(&usecase.root)?)?
+            .tolerance(Tolerance::LogErrors)
+            .storage(&custom_path)?
+            .walker()?;
+        // Never checked before: all files are new
+        let changes = walker.changes::<hasher::blake::Blake, reader::buffering::Buffering>()?;
+        assert!(!changes.is_same());
+        assert_eq!(changes.added.len(), usecase.files.len());
+        assert!(changes.removed.is_empty());
+        assert!(changes.modified.is_empty());
+        assert!(changes.unchanged.is_empty());
+        // Nothing changed
+        let changes = walker.changes::<hasher::blake::Blake, reader::buffering::Buffering>()?;
+        assert!(changes.is_same());
+        assert_eq!(changes.unchanged.len(), usecase.files.len());
+        // One file modified
+        usecase.change(1)?;
+        let changes = walker.changes::<hasher::blake::Blake, reader::buffering::Buffering>()?;
+        assert!(!changes.is_same());
+        assert_eq!(changes.modified.len(), 1);
+        assert_eq!(changes.unchanged.len(), usecase.files.len() - 1);
+        // One file removed and one added
+        let removed = usecase.files[0].clone();
+        let added = usecase.root.join("added.file");
+        remove_file(&removed)?;
+        create_text_file(&added, "added")?;
+        let changes = walker.changes::<hasher::blake::Blake, reader::buffering::Buffering>()?;
+        assert_eq!(changes.removed, vec![removed]);
+        assert_eq!(changes.added, vec![added]);
+        assert!(changes.modified.is_empty());
+        // Shared storage with is_same()
+        assert!(walker.is_same::<hasher::blake::Blake, reader::buffering::Buffering>()?);
+        usecase.clean()?;
+        remove_dir_all(custom_path)?;
+        Ok(())
+    }
 }
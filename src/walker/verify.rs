@@ -0,0 +1,187 @@
+use super::{Manifest, Walker, E};
+use crate::{Hasher, Reader};
+use std::{
+    collections::HashSet,
+    env, io, mem,
+    path::{Path, PathBuf},
+};
+
+/// The result of checking a single file against a manifest.
+#[derive(Debug)]
+pub enum Verdict {
+    /// The file is listed in the manifest and its hash matches the expected one.
+    Ok,
+    /// The file is listed in the manifest, but its hash differs from the expected one.
+    ///
+    /// # Fields
+    ///
+    /// * `expected` - The hash from the manifest.
+    /// * `actual` - The calculated hash.
+    Mismatch { expected: Vec<u8>, actual: Vec<u8> },
+    /// The file is listed in the manifest, but doesn't exist. Symlinks aren't followed, so a
+    /// dangling symlink isn't missing (it's hashed as any other listed file).
+    Missing,
+    /// The file has been found by collecting, but isn't listed in the manifest.
+    Unexpected,
+    /// The file is listed in the manifest, but cannot be hashed; or the path (a file or a folder)
+    /// cannot be read during collecting. Contains the related error.
+    Unreadable(E),
+}
+
+impl Verdict {
+    /// Returns `true` if the verdict is `Verdict::Ok`.
+    pub fn is_ok(&self) -> bool {
+        matches!(self, Verdict::Ok)
+    }
+}
+
+/// The result of `Walker::verify()`: a verdict for each file listed in the manifest and for each
+/// file found by collecting but not listed in the manifest.
+#[derive(Debug, Default)]
+pub struct Verification {
+    /// Paths and related verdicts, sorted by path.
+    pub files: Vec<(PathBuf, Verdict)>,
+}
+
+impl Verification {
+    /// Returns `true` if all files listed in the manifest have been found with expected hashes and
+    /// no unexpected files have been found.
+    pub fn is_ok(&self) -> bool {
+        self.files.iter().all(|(_, verdict)| verdict.is_ok())
+    }
+
+    /// Returns an iterator over files which didn't pass the check.
+    ///
+    /// # Returns
+    ///
+    /// - An iterator over paths and related verdicts, excluding `Verdict::Ok`.
+    pub fn failed(&self) -> impl Iterator<Item = &(PathBuf, Verdict)> {
+        self.files.iter().filter(|(_, verdict)| !verdict.is_ok())
+    }
+}
+
+impl Walker {
+    /// Checks files against a previously saved manifest (the `sha256sum -c` workflow for whole
+    /// trees). The manifest can be made by `Manifest::save()` or by `sha256sum`/`b3sum`; in the
+    /// last case, the corresponding hasher should be used.
+    ///
+    /// Only files listed in the manifest are hashed. Besides, paths are collected from the entries
+    /// of `Walker` (as with `collect()`, with all filters applied) to detect files, which aren't
+    /// listed in the manifest. The manifest file itself is never reported as unexpected. Paths,
+    /// which cannot be read during collecting, are reported as unreadable.
+    ///
+    /// Hashing is done in the same way as with `hash()`, so the settings of `Options` (reading
+    /// strategy, number of threads, tolerance) are applied. After the check, the `paths` field
+    /// contains the listed files with their hashes; errors of unreadable files are moved into
+    /// verdicts.
+    ///
+    /// # Parameters
+    ///
+    /// - `path`: The path to the manifest (checksums) file.
+    ///
+    /// # Returns
+    ///
+    /// - `Result<Verification, E>`: Verdicts for each file.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the manifest cannot be loaded or if the operation has been
+    /// interrupted. With `Tolerance::StopOnErrors`, it also returns an error on the first file, which
+    /// cannot be hashed; otherwise, such files get `Verdict::Unreadable`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fshasher::{hasher, reader, walker::ManifestFormat, Options};
+    /// use std::{env::temp_dir, fs::{create_dir, remove_dir_all, write}};
+    /// use uuid::Uuid;
+    ///
+    /// let dest = temp_dir().join(Uuid::new_v4().to_string());
+    /// create_dir(&dest).unwrap();
+    /// write(dest.join("a.txt"), "a").unwrap();
+    /// let mut walker = Options::from(&dest).unwrap().walker().unwrap();
+    /// walker
+    ///     .collect()
+    ///     .unwrap()
+    ///     .hash::<hasher::blake::Blake, reader::buffering::Buffering>()
+    ///     .unwrap();
+    /// let manifest = dest.join("checksums.b3");
+    /// walker.manifest().save(&manifest, ManifestFormat::Text).unwrap();
+    /// let verification = walker
+    ///     .verify::<hasher::blake::Blake, reader::buffering::Buffering, _>(&manifest)
+    ///     .unwrap();
+    /// assert!(verification.is_ok());
+    /// remove_dir_all(&dest).unwrap();
+    /// ```
+    pub fn verify<H: Hasher + 'static, R: Reader + 'static, P: AsRef<Path>>(
+        &mut self,
+        path: P,
+    ) -> Result<Verification, E>
+    where
+        E: From<<H as Hasher>::Error> + From<<R as Reader>::Error>,
+    {
+        let path = if path.as_ref().is_absolute() {
+            path.as_ref().to_path_buf()
+        } else {
+            env::current_dir()?.join(path.as_ref())
+        };
+        let manifest = Manifest::load(&path)?;
+        self.collect()?;
+        let listed: HashSet<&PathBuf> = manifest.files.iter().map(|(p, _)| p).collect();
+        let mut files: Vec<(PathBuf, Verdict)> = Vec::new();
+        let mut unreadable: HashSet<PathBuf> = HashSet::new();
+        for (p, state) in mem::take(&mut self.paths) {
+            if p == path {
+                continue;
+            }
+            match state {
+                // Errors of collecting are kept in paths along with the related paths
+                Some(Err(err)) => {
+                    unreadable.insert(p.clone());
+                    files.push((p, Verdict::Unreadable(err)));
+                }
+                _ if !listed.contains(&p) => files.push((p, Verdict::Unexpected)),
+                _ => {}
+            }
+        }
+        // Missing files are verdicts, not errors, so they aren't passed for hashing. Symlinks aren't
+        // followed to check the presence.
+        for (p, _) in manifest.files.iter() {
+            if unreadable.contains(p) {
+                continue;
+            }
+            match p.symlink_metadata() {
+                Ok(_) => self.paths.push((p.to_owned(), None)),
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                    files.push((p.to_owned(), Verdict::Missing));
+                }
+                Err(err) => {
+                    files.push((p.to_owned(), Verdict::Unreadable((p.clone(), err).into())))
+                }
+            }
+        }
+        self.hash::<H, R>()?;
+        for (path, state) in self.paths.iter_mut() {
+            let verdict = match state.take() {
+                Some(Ok(actual)) => {
+                    let expected = manifest.get(&*path).unwrap_or_default();
+                    let verdict = if actual == expected {
+                        Verdict::Ok
+                    } else {
+                        Verdict::Mismatch {
+                            expected: expected.to_vec(),
+                            actual: actual.clone(),
+                        }
+                    };
+                    *state = Some(Ok(actual));
+                    verdict
+                }
+                Some(Err(err)) => Verdict::Unreadable(err),
+                None => continue,
+            };
+            files.push((path.to_owned(), verdict));
+        }
+        files.sort_by(|(a, _), (b, _)| a.cmp(b));
+        Ok(Verification { files })
+    }
+}
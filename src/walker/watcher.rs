@@ -0,0 +1,713 @@
+use super::{merkle, summarize, worker::hash_file, Changes, Options, SummaryMode, Walker, E};
+use crate::{
+    breaker::Breaker,
+    collector::{self, Context},
+    entry::Entry,
+    Hasher, Reader, Tolerance,
+};
+use inotify::{EventMask, Inotify, WatchDescriptor, WatchMask};
+use log::{debug, error, warn};
+use std::{
+    collections::{hash_map, BTreeMap, HashMap, HashSet},
+    fs::read_dir,
+    io, mem,
+    os::fd::{AsRawFd, FromRawFd, OwnedFd},
+    path::{Path, PathBuf},
+    sync::{
+        mpsc::{channel, Receiver, Sender},
+        Arc, RwLock,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+/// How long `Watcher` waits for new events before processing already received events. Editors and
+/// build tools usually touch many files at once; this delay allows handling them in one go.
+const DEBOUNCE: Duration = Duration::from_millis(50);
+/// Size of the buffer for reading inotify events.
+const EVENTS_BUFFER_SIZE: usize = 4096;
+
+/// Events emitted by `Watcher`.
+#[derive(Debug)]
+pub enum WatchEvent {
+    /// Files have been changed.
+    ///
+    /// # Fields
+    ///
+    /// * `changes` - Touched files: added, removed and modified; `unchanged` contains files which
+    ///   were touched, but their content is the same.
+    /// * `hash` - The updated summary hash.
+    Changed { changes: Changes, hash: Vec<u8> },
+    /// An error happened during watching. With `Tolerance::StopOnErrors`, `Watcher` stops
+    /// watching after this event; otherwise, errors of hashing are only logged (according to the
+    /// tolerance level) and aren't reported as events.
+    Error(E),
+}
+
+/// `Watcher` keeps the summary hash of the destination up to date. Created by `Walker::watch()`.
+/// Available on Linux with feature "watcher".
+///
+/// `Watcher` uses inotify to observe the folders of entries. Only touched files are hashed
+/// again; a touched file is taken into account only if it's accepted by the filters of the entry,
+/// the global filters and the context files (in the same way as during collecting). If a context
+/// file is touched (with `GitAware`, also git's index or exclude files) or the queue of inotify
+/// events overflows, paths are collected again.
+///
+/// Changes are reported with `WatchEvent` via the channel, which can be taken with `events()`.
+/// `Watcher` stops watching on drop or on calling `stop()`.
+pub struct Watcher {
+    breaker: Breaker,
+    waker: Arc<Waker>,
+    hash: Arc<RwLock<Vec<u8>>>,
+    rx: Option<Receiver<WatchEvent>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Watcher {
+    /// Returns a channel to receive `WatchEvent`s. The channel can be taken only once.
+    ///
+    /// # Returns
+    ///
+    /// - `Option<Receiver<WatchEvent>>`: A channel, or `None` if it has been taken already.
+    pub fn events(&mut self) -> Option<Receiver<WatchEvent>> {
+        self.rx.take()
+    }
+
+    /// Returns the current summary hash.
+    ///
+    /// # Returns
+    ///
+    /// - `Result<Vec<u8>, E>`: The current summary hash.
+    pub fn hash(&self) -> Result<Vec<u8>, E> {
+        Ok(self.hash.read()?.clone())
+    }
+
+    /// Checks whether `Watcher` is still watching. `Watcher` stops on `stop()` or on an error with
+    /// `Tolerance::StopOnErrors`.
+    pub fn is_running(&self) -> bool {
+        self.handle
+            .as_ref()
+            .map(|handle| !handle.is_finished())
+            .unwrap_or_default()
+    }
+
+    /// Stops watching and waits for the watching thread to finish.
+    pub fn stop(&mut self) {
+        self.breaker.abort();
+        self.waker.wake();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for Watcher {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+impl Walker {
+    /// Creates a `Watcher`, which keeps the summary hash up to date. Paths are collected and hashed
+    /// before this method returns, so the summary hash is available right away; after that, only
+    /// touched files are hashed. Available on Linux with feature "watcher".
+    ///
+    /// The summary hash made by `Watcher` is equal to the hash made by `collect()` and `hash()` with
+    /// the same options.
+    ///
+    /// # Returns
+    ///
+    /// - `Result<Watcher, E>`: A running `Watcher`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if collecting or hashing fails (see `collect()` and `hash()`) or if inotify
+    /// cannot be initialized.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fshasher::{hasher, reader, walker::WatchEvent, Options};
+    /// use std::{env::temp_dir, fs::{create_dir, remove_dir_all}};
+    /// use uuid::Uuid;
+    ///
+    /// let dest = temp_dir().join(Uuid::new_v4().to_string());
+    /// create_dir(&dest).unwrap();
+    /// let mut watcher = Options::from(&dest)
+    ///     .unwrap()
+    ///     .walker()
+    ///     .unwrap()
+    ///     .watch::<hasher::blake::Blake, reader::buffering::Buffering>()
+    ///     .unwrap();
+    /// let events = watcher.events().unwrap();
+    /// println!("Current hash: {:?}", watcher.hash().unwrap());
+    /// // events.recv() blocks until something has been changed
+    /// if let Ok(WatchEvent::Changed { hash, .. }) = events.try_recv() {
+    ///     println!("Updated hash: {hash:?}");
+    /// }
+    /// watcher.stop();
+    /// remove_dir_all(&dest).unwrap();
+    /// ```
+    pub fn watch<H: Hasher + 'static, R: Reader + 'static>(mut self) -> Result<Watcher, E>
+    where
+        E: From<<H as Hasher>::Error> + From<<R as Reader>::Error>,
+    {
+        let hash = self.collect()?.hash::<H, R>()?.to_vec();
+        let opt = self.opt.clone().ok_or(E::IsNotInited)?;
+        let files: BTreeMap<PathBuf, Vec<u8>> = self
+            .paths
+            .drain(..)
+            .filter_map(|(path, hash)| {
+                if let Some(Ok(hash)) = hash {
+                    Some((path, hash))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        let mut state = State {
+            inotify: Inotify::init()?,
+            watches: HashMap::new(),
+            files,
+            contexts: HashMap::new(),
+            sources: HashSet::new(),
+            source_folders: HashSet::new(),
+            walker: self,
+            opt,
+        };
+        state.watch_entries()?;
+        // Own breaker is used, because the breaker of walker is reset with each collecting
+        let breaker = Breaker::new();
+        let waker = Arc::new(Waker::new()?);
+        let hash = Arc::new(RwLock::new(hash));
+        let (tx, rx): (Sender<WatchEvent>, Receiver<WatchEvent>) = channel();
+        let handle = {
+            let hash = hash.clone();
+            let breaker = breaker.clone();
+            let waker = waker.clone();
+            thread::spawn(move || state.listen::<H, R>(tx, hash, breaker, waker))
+        };
+        Ok(Watcher {
+            breaker,
+            waker,
+            hash,
+            rx: Some(rx),
+            handle: Some(handle),
+        })
+    }
+}
+
+/// The state of watching, which is owned by the watching thread.
+struct State {
+    inotify: Inotify,
+    /// Watched folders.
+    watches: HashMap<WatchDescriptor, PathBuf>,
+    /// Current hashes of files.
+    files: BTreeMap<PathBuf, Vec<u8>>,
+    /// Contexts of entries (by indexes of entries), which are kept until paths are collected again,
+    /// so context files aren't read for each touched path.
+    contexts: HashMap<usize, Context>,
+    /// Files, which define the view of git on entries, but aren't collected from them (see
+    /// `collector::git_sources()`).
+    sources: HashSet<PathBuf>,
+    /// Folders, which are watched only because of `sources`.
+    source_folders: HashSet<PathBuf>,
+    /// `Walker` is used to collect paths again, if it's required.
+    walker: Walker,
+    /// A copy of walker's options.
+    opt: Options,
+}
+
+impl State {
+    /// Main loop of watching: waits for inotify events, collects touched files and processes them
+    /// as soon as there are no new events during `DEBOUNCE`. Without pending changes, the thread
+    /// sleeps until the next event or until `Waker` is woken up by `Watcher::stop()`.
+    fn listen<H: Hasher + 'static, R: Reader + 'static>(
+        mut self,
+        tx: Sender<WatchEvent>,
+        hash: Arc<RwLock<Vec<u8>>>,
+        breaker: Breaker,
+        waker: Arc<Waker>,
+    ) where
+        E: From<<H as Hasher>::Error> + From<<R as Reader>::Error>,
+    {
+        let mut buffer = [0u8; EVENTS_BUFFER_SIZE];
+        let mut touched: HashSet<PathBuf> = HashSet::new();
+        let mut recollect = false;
+        let mut last = Instant::now();
+        let report = |err: E| {
+            error!("Watcher error: {err}");
+            let _ = tx.send(WatchEvent::Error(err));
+        };
+        while !breaker.is_aborted() {
+            let pending = !touched.is_empty() || recollect;
+            let timeout = pending.then(|| DEBOUNCE.saturating_sub(last.elapsed()));
+            if let Err(err) = wait(&self.inotify, &waker, timeout) {
+                report(err.into());
+                break;
+            }
+            if breaker.is_aborted() {
+                break;
+            }
+            let events = match self.inotify.read_events(&mut buffer) {
+                Ok(events) => events
+                    .map(|event| (event.wd, event.mask, event.name.map(PathBuf::from)))
+                    .collect::<Vec<(WatchDescriptor, EventMask, Option<PathBuf>)>>(),
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => Vec::new(),
+                Err(err) => {
+                    report(err.into());
+                    break;
+                }
+            };
+            if !events.is_empty() {
+                for (wd, mask, name) in events.into_iter() {
+                    if let Err(err) = self.consider(wd, mask, name, &mut touched, &mut recollect) {
+                        report(err);
+                        return;
+                    }
+                }
+                last = Instant::now();
+                continue;
+            }
+            if !pending || last.elapsed() < DEBOUNCE {
+                continue;
+            }
+            let result = if recollect {
+                self.recollect::<H, R>(&touched, &breaker)
+            } else {
+                self.update::<H, R>(&touched, &breaker)
+            };
+            touched.clear();
+            recollect = false;
+            let changes = match result {
+                Ok(changes) => changes,
+                Err(E::Aborted) => break,
+                Err(err) => {
+                    report(err);
+                    break;
+                }
+            };
+            if changes.added.is_empty() && changes.removed.is_empty() && changes.modified.is_empty()
+            {
+                continue;
+            }
+            let summary = match self.summary::<H>() {
+                Ok(summary) => summary,
+                Err(err) => {
+                    report(err);
+                    break;
+                }
+            };
+            debug!(
+                "Watcher: added {}, removed {}, modified {}",
+                changes.added.len(),
+                changes.removed.len(),
+                changes.modified.len()
+            );
+            match hash.write() {
+                Ok(mut hash) => *hash = summary.clone(),
+                Err(err) => {
+                    report(err.into());
+                    break;
+                }
+            }
+            // Receiver might be dropped, if a user is interested only in the hash
+            let _ = tx.send(WatchEvent::Changed {
+                changes,
+                hash: summary,
+            });
+        }
+    }
+
+    /// Handles a single inotify event.
+    fn consider(
+        &mut self,
+        wd: WatchDescriptor,
+        mask: EventMask,
+        name: Option<PathBuf>,
+        touched: &mut HashSet<PathBuf>,
+        recollect: &mut bool,
+    ) -> Result<(), E> {
+        if mask.contains(EventMask::Q_OVERFLOW) {
+            warn!("Watcher: queue of events overflowed; paths will be collected again");
+            *recollect = true;
+            return Ok(());
+        }
+        if mask.contains(EventMask::IGNORED) {
+            // Folder has been removed or unmounted
+            self.watches.remove(&wd);
+            return Ok(());
+        }
+        let (Some(parent), Some(name)) = (self.watches.get(&wd), name) else {
+            return Ok(());
+        };
+        let path = parent.join(name);
+        if self.sources.contains(&path) {
+            debug!("Watcher: {} has been changed", path.display());
+            *recollect = true;
+            return Ok(());
+        }
+        if self.source_folders.contains(parent) {
+            return Ok(());
+        }
+        if mask.contains(EventMask::ISDIR) {
+            if mask.intersects(EventMask::CREATE | EventMask::MOVED_TO) {
+                let mut files = Vec::new();
+                self.watch_folder(&path, &mut files)?;
+                touched.extend(files);
+            } else if mask.intersects(EventMask::DELETE | EventMask::MOVED_FROM) {
+                self.unwatch_folder(&path);
+                touched.extend(
+                    self.files
+                        .keys()
+                        .filter(|p| p.starts_with(&path))
+                        .cloned()
+                        .collect::<Vec<PathBuf>>(),
+                );
+            }
+        } else {
+            if self.is_context_file(&path) {
+                *recollect = true;
+            }
+            touched.insert(path);
+        }
+        Ok(())
+    }
+
+    /// Hashes touched files again.
+    fn update<H: Hasher + 'static, R: Reader + 'static>(
+        &mut self,
+        touched: &HashSet<PathBuf>,
+        breaker: &Breaker,
+    ) -> Result<Changes, E>
+    where
+        E: From<<H as Hasher>::Error> + From<<R as Reader>::Error>,
+    {
+        let mut accepted = Vec::new();
+        for path in touched.iter() {
+            if path.is_file() && self.is_accepted(path)? {
+                accepted.push(path.to_owned());
+            }
+        }
+        let removed: Vec<PathBuf> = touched
+            .iter()
+            .filter(|p| !accepted.contains(p))
+            .cloned()
+            .collect();
+        self.apply::<H, R>(accepted, removed, breaker)
+    }
+
+    /// Collects paths again and hashes touched and new files.
+    fn recollect<H: Hasher + 'static, R: Reader + 'static>(
+        &mut self,
+        touched: &HashSet<PathBuf>,
+        breaker: &Breaker,
+    ) -> Result<Changes, E>
+    where
+        E: From<<H as Hasher>::Error> + From<<R as Reader>::Error>,
+    {
+        debug!("Watcher: collecting paths again");
+        // Context files might be changed
+        self.contexts.clear();
+        self.walker.collect()?;
+        let collected: HashSet<PathBuf> = self
+            .walker
+            .paths
+            .drain(..)
+            .filter_map(|(path, state)| if state.is_none() { Some(path) } else { None })
+            .collect();
+        // New folders might be accepted now
+        self.watch_entries()?;
+        let accepted: Vec<PathBuf> = collected
+            .iter()
+            .filter(|p| touched.contains(*p) || !self.files.contains_key(*p))
+            .cloned()
+            .collect();
+        let removed: Vec<PathBuf> = self
+            .files
+            .keys()
+            .filter(|p| !collected.contains(*p))
+            .cloned()
+            .collect();
+        self.apply::<H, R>(accepted, removed, breaker)
+    }
+
+    /// Hashes accepted files and drops removed files.
+    fn apply<H: Hasher + 'static, R: Reader + 'static>(
+        &mut self,
+        accepted: Vec<PathBuf>,
+        removed: Vec<PathBuf>,
+        breaker: &Breaker,
+    ) -> Result<Changes, E>
+    where
+        E: From<<H as Hasher>::Error> + From<<R as Reader>::Error>,
+    {
+        let mut changes = Changes::default();
+        for path in removed.into_iter() {
+            if self.files.remove(&path).is_some() {
+                changes.removed.push(path);
+            }
+        }
+        for path in accepted.into_iter() {
+            let hash = match hash_file::<H, R>(&path, &self.opt, None, breaker) {
+                Ok(hash) => hash,
+                Err(E::Aborted) => return Err(E::Aborted),
+                Err(err) => {
+                    match self.opt.tolerance {
+                        Tolerance::StopOnErrors => return Err(E::Bound(path, Box::new(err))),
+                        Tolerance::LogErrors => warn!("entry: {}; error: {err}", path.display()),
+                        Tolerance::DoNotLogErrors => {}
+                    }
+                    if self.files.remove(&path).is_some() {
+                        changes.removed.push(path);
+                    }
+                    continue;
+                }
+            };
+            match self.files.insert(path.clone(), hash.clone()) {
+                None => changes.added.push(path),
+                Some(previous) if previous != hash => changes.modified.push(path),
+                Some(_) => changes.unchanged.push(path),
+            }
+        }
+        changes.added.sort();
+        changes.removed.sort();
+        changes.modified.sort();
+        changes.unchanged.sort();
+        Ok(changes)
+    }
+
+    /// Calculates the summary hash in the same way as `Walker::hash()` does.
+    fn summary<H: Hasher>(&self) -> Result<Vec<u8>, E>
+    where
+        E: From<<H as Hasher>::Error>,
+    {
+        if matches!(self.opt.summary, SummaryMode::Merkle) {
+            let entries: Vec<PathBuf> = self.opt.roots();
+            return merkle::<H>(
+                self.files
+                    .iter()
+                    .map(|(path, hash)| (path.as_path(), hash.as_slice())),
+                &entries,
+                &self.opt.hasher_config,
+            )
+            .map(|(summary, _)| summary);
+        }
+        let roots: Option<Vec<PathBuf>> = if self.opt.paths_in_summary {
+            Some(self.opt.roots())
+        } else {
+            None
+        };
+        summarize::<H>(
+            self.files
+                .iter()
+                .map(|(path, hash)| (path.as_path(), hash.as_slice())),
+            roots.as_deref(),
+            &self.opt.hasher_config,
+        )
+    }
+
+    /// Checks whether the path is accepted by at least one entry or is one of files added to
+    /// options explicitly.
+    fn is_accepted(&mut self, path: &Path) -> Result<bool, E> {
+        if self.opt.files.iter().any(|file| file == path) {
+            return Ok(true);
+        }
+        for (index, entry) in self.opt.entries.iter().enumerate() {
+            if !path.starts_with(&entry.entry) {
+                continue;
+            }
+            let context = match self.contexts.entry(index) {
+                hash_map::Entry::Occupied(context) => context.into_mut(),
+                hash_map::Entry::Vacant(context) => {
+                    context.insert(collector::context(entry, &self.opt.global)?)
+                }
+            };
+            if collector::accepted(entry, &self.opt.global, &self.opt.symlinks, context, path)? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Checks whether the file is a context file of an entry it belongs to.
+    fn is_context_file(&self, path: &Path) -> bool {
+        let Some(filename) = path.file_name() else {
+            return false;
+        };
+        self.opt
+            .entries
+            .iter()
+            .filter(|entry| path.starts_with(&entry.entry))
+            .any(|entry| collector::is_context_file(entry, &self.opt.global, filename))
+    }
+
+    fn watch_entries(&mut self) -> Result<(), E> {
+        let roots: Vec<PathBuf> = self
+            .opt
+            .entries
+            .iter()
+            .map(|entry: &Entry| entry.entry.clone())
+            .collect();
+        for root in roots.iter() {
+            // Files are already known
+            self.watch_folder(root, &mut Vec::new())?;
+        }
+        // Folders of files added explicitly are watched without nested folders
+        let parents: Vec<PathBuf> = self
+            .opt
+            .files
+            .iter()
+            .filter_map(|file| file.parent())
+            .filter(|parent| !self.watches.values().any(|watched| watched == parent))
+            .map(|parent| parent.to_path_buf())
+            .collect();
+        for parent in parents.into_iter() {
+            self.add_watch(&parent)?;
+        }
+        // Folders of git's index and exclude files; they might be missing (for example, if there
+        // is no global exclude file)
+        for entry in self.opt.entries.iter() {
+            self.sources
+                .extend(collector::git_sources(entry, &self.opt.global));
+        }
+        let folders: HashSet<PathBuf> = self
+            .sources
+            .iter()
+            .filter_map(|source| source.parent())
+            .filter(|folder| folder.is_dir())
+            .map(|folder| folder.to_path_buf())
+            .collect();
+        for folder in folders.into_iter() {
+            if !self.watches.values().any(|watched| watched == &folder) {
+                self.add_watch(&folder)?;
+                self.source_folders.insert(folder);
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds a watch for the folder (without nested folders).
+    fn add_watch(&mut self, path: &Path) -> Result<(), E> {
+        let wd = self
+            .inotify
+            .watches()
+            .add(
+                path,
+                WatchMask::CLOSE_WRITE
+                    | WatchMask::MODIFY
+                    | WatchMask::ATTRIB
+                    | WatchMask::CREATE
+                    | WatchMask::DELETE
+                    | WatchMask::MOVE
+                    | WatchMask::ONLYDIR,
+            )
+            .map_err(|err| E::IOError(path.to_path_buf(), err))?;
+        self.watches.insert(wd, path.to_path_buf());
+        Ok(())
+    }
+
+    /// Adds a watch for the folder and all nested accepted folders. Files found in these folders
+    /// are added to `files`.
+    fn watch_folder(&mut self, path: &Path, files: &mut Vec<PathBuf>) -> Result<(), E> {
+        if !path.is_dir() || (!self.is_root(path) && !self.is_accepted(path)?) {
+            return Ok(());
+        }
+        self.add_watch(path)?;
+        // Folder can be removed in the meantime
+        let Ok(els) = read_dir(path) else {
+            return Ok(());
+        };
+        for el in els.flatten() {
+            let path = el.path();
+            if path.is_dir() {
+                self.watch_folder(&path, files)?;
+            } else {
+                files.push(path);
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes watches of the folder and all nested folders.
+    fn unwatch_folder(&mut self, path: &Path) {
+        let wds: Vec<WatchDescriptor> = self
+            .watches
+            .iter()
+            .filter(|(_, p)| p.starts_with(path))
+            .map(|(wd, _)| wd.clone())
+            .collect();
+        for wd in wds.into_iter() {
+            self.watches.remove(&wd);
+            // Watch is already removed by the system, if the folder doesn't exist
+            let _ = self.inotify.watches().remove(wd);
+        }
+    }
+
+    fn is_root(&self, path: &Path) -> bool {
+        self.opt.entries.iter().any(|entry| entry.entry == path)
+    }
+}
+
+/// Wakes up the watching thread, which waits for inotify events, to let it notice that watching
+/// has been stopped.
+struct Waker(OwnedFd);
+
+impl Waker {
+    fn new() -> io::Result<Self> {
+        // SAFETY: eventfd() has no preconditions; the returned descriptor isn't owned by anyone else
+        let fd = unsafe { libc::eventfd(0, libc::EFD_CLOEXEC | libc::EFD_NONBLOCK) };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        // SAFETY: the descriptor is valid and open
+        Ok(Self(unsafe { OwnedFd::from_raw_fd(fd) }))
+    }
+
+    fn wake(&self) {
+        let value: u64 = 1;
+        // SAFETY: writes 8 bytes of a valid u64 into an open eventfd. The counter can only
+        // overflow after 2^64 - 1 calls, so the result can be ignored
+        let _ = unsafe {
+            libc::write(
+                self.0.as_raw_fd(),
+                (&value as *const u64).cast(),
+                mem::size_of::<u64>(),
+            )
+        };
+    }
+}
+
+/// Blocks until there are inotify events, `Waker` is woken up or the timeout (if any) is over.
+fn wait(inotify: &Inotify, waker: &Waker, timeout: Option<Duration>) -> io::Result<()> {
+    let mut fds = [
+        libc::pollfd {
+            fd: inotify.as_raw_fd(),
+            events: libc::POLLIN,
+            revents: 0,
+        },
+        libc::pollfd {
+            fd: waker.0.as_raw_fd(),
+            events: libc::POLLIN,
+            revents: 0,
+        },
+    ];
+    // Rounded up, so the timeout is over after waking up
+    let timeout = timeout.map_or(-1, |timeout| {
+        timeout
+            .as_micros()
+            .div_ceil(1000)
+            .min(libc::c_int::MAX as u128) as libc::c_int
+    });
+    loop {
+        // SAFETY: `fds` is a valid array of `pollfd` of the given length
+        let result = unsafe { libc::poll(fds.as_mut_ptr(), fds.len() as libc::nfds_t, timeout) };
+        if result >= 0 {
+            return Ok(());
+        }
+        let err = io::Error::last_os_error();
+        if err.kind() != io::ErrorKind::Interrupted {
+            return Err(err);
+        }
+    }
+}
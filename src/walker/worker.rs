@@ -1,7 +1,8 @@
-use super::{Action, ReadingStrategy, E};
-use crate::{breaker::Breaker, Hasher, Reader, Tolerance};
+use super::{cache::Cache, metadata, Action, Options, ReadingStrategy, E};
+use crate::{breaker::Breaker, Hasher, Reader, SymlinkPolicy, Tolerance};
 use log::error;
 use std::{
+    fs::read_link,
     path::{Path, PathBuf},
     sync::{
         atomic::{AtomicBool, Ordering},
@@ -39,7 +40,9 @@ impl Worker {
     /// # Parameters
     ///
     /// - `tx_queue`: The sender channel for sending actions to the pool.
-    /// - `reading_strategy`: The strategy for reading files.
+    /// - `opt`: The options of `Walker`; the reading strategy, the configuration of the hasher and
+    ///   the tolerance level are used.
+    /// - `cache`: The cache of hashes; if given, files with unchanged metadata are not read.
     /// - `breaker`: The breaker to handle interruptions.
     ///
     /// # Returns
@@ -50,11 +53,10 @@ impl Worker {
     ///
     /// `Worker` doesn't stop the listener loop on IO errors (from hasher or reader), but reports the error
     /// to `Walker`.
-
     pub fn run<H: Hasher + 'static, R: Reader + 'static>(
         tx_queue: Sender<Action>,
-        reading_strategy: ReadingStrategy,
-        tolerance: Tolerance,
+        opt: Options,
+        cache: Option<Arc<Cache>>,
         breaker: Breaker,
         id: u16,
     ) -> Self
@@ -66,14 +68,14 @@ impl Worker {
         let available_inner = available.clone();
         let handle = thread::spawn(move || {
             let response = |action: Action| {
-                tx_queue.send(action).inspect_err(|_err| 
+                tx_queue.send(action).inspect_err(|_err|
                     error!(
                         "Hasher worker cannot communicate with pool. Channel error. Worker will be closed"
                     )
                 )
             };
             let report = |action: Action| {
-                tx_queue.send(action).inspect_err(|_err| 
+                tx_queue.send(action).inspect_err(|_err|
                     error!(
                         "Hasher worker cannot communicate with pool. Channel error. Worker will be closed"
                     )
@@ -92,10 +94,10 @@ impl Worker {
                     if breaker.is_aborted() {
                         break 'outer;
                     }
-                    match hash_file::<H, R>(&path, &reading_strategy, &breaker) {
+                    match hash_file::<H, R>(&path, &opt, cache.as_deref(), &breaker) {
                         Ok(hasher) => collected.push((path, hasher)),
                         Err(err) => {
-                            if matches!(tolerance, Tolerance::StopOnErrors) {
+                            if matches!(opt.tolerance, Tolerance::StopOnErrors) {
                                 let _ = report(Action::Error(path, err));
                                 break 'outer;
                             } else {
@@ -158,24 +160,64 @@ impl Worker {
     }
 }
 
-/// Hashes the content of a file based on the given reading strategy.
+/// Hashes a file: the content of the file is hashed based on the given reading strategy and the
+/// selected metadata of the file (see `Options::metadata()`) is mixed in.
 ///
 /// # Parameters
 ///
 /// - `path`: The path of the file to be hashed.
-/// - `reading_strategy`: The strategy to use for reading the file.
+/// - `opt`: The options of `Walker`; the reading strategy, the configuration of the hasher and the
+///   fields of metadata are used.
+/// - `cache`: The cache of hashes. If the metadata of the file hasn't been changed since the previous
+///   hashing, the cached hash of the content is used without reading the file.
 /// - `breaker`: The breaker to handle interruptions.
 ///
 /// # Returns
 ///
-/// - `Result<H, E>`: The resulting hasher instance with the file's hash or an error if the operation fails.
+/// - `Result<Vec<u8>, E>`: The hash of the file or an error if the operation fails.
 ///
 /// # Errors
 ///
-/// This function will return an error if the operation is interrupted or if there is an issue with reading the file.
-fn hash_file<H: Hasher, R: Reader>(
+/// This function will return an error if the operation is interrupted or if there is an issue with reading the file
+/// or its metadata.
+pub(crate) fn hash_file<H: Hasher, R: Reader>(
     path: &Path,
-    reading_strategy: &ReadingStrategy,
+    opt: &Options,
+    cache: Option<&Cache>,
+    breaker: &Breaker,
+) -> Result<Vec<u8>, E>
+where
+    E: From<<R as Reader>::Error> + From<<H as Hasher>::Error>,
+{
+    let hash = if opt.symlinks == SymlinkPolicy::HashTarget && path.is_symlink() {
+        hash_link::<H>(path, opt, breaker)?
+    } else {
+        hash_content::<H, R>(path, opt, cache, breaker)?
+    };
+    metadata::mix::<H>(path, hash, &opt.metadata, &opt.hasher_config)
+}
+
+/// Hashes the target string of a symlink (used with `SymlinkPolicy::HashTarget`). The target isn't
+/// resolved, so broken symlinks are hashed as well.
+fn hash_link<H: Hasher>(path: &Path, opt: &Options, breaker: &Breaker) -> Result<Vec<u8>, E>
+where
+    E: From<<H as Hasher>::Error>,
+{
+    if breaker.is_aborted() {
+        return Err(E::Aborted);
+    }
+    let mut hasher = H::configured(&opt.hasher_config)?;
+    hasher.absorb(read_link(path)?.as_os_str().as_encoded_bytes())?;
+    hasher.finish()?;
+    Ok(hasher.hash()?.to_vec())
+}
+
+/// Hashes the content of a file. The cache (if given) keeps hashes of contents only, so changing
+/// the fields of metadata doesn't invalidate it.
+fn hash_content<H: Hasher, R: Reader>(
+    path: &Path,
+    opt: &Options,
+    cache: Option<&Cache>,
     breaker: &Breaker,
 ) -> Result<Vec<u8>, E>
 where
@@ -187,7 +229,19 @@ where
     if !path.exists() {
         return Err(E::FileDoesNotExists(path.to_path_buf()));
     }
-    let mut hasher = H::new();
+    // Metadata is taken before reading, so if the file is changed during reading, it will be
+    // hashed again next time.
+    let cached = match cache {
+        Some(cache) => {
+            let md = path.metadata()?;
+            if let Some(hash) = cache.get(path, &md) {
+                return Ok(hash);
+            }
+            Some((cache, md))
+        }
+        None => None,
+    };
+    let mut hasher = H::configured(&opt.hasher_config)?;
     let mut reader = R::new(path);
     let mut apply = |reading_strategy: &ReadingStrategy| {
         match reading_strategy {
@@ -218,9 +272,9 @@ where
         };
         Ok(())
     };
-    match reading_strategy {
+    match &opt.reading_strategy {
         ReadingStrategy::Buffer | ReadingStrategy::Complete | ReadingStrategy::MemoryMapped => {
-            apply(reading_strategy)?;
+            apply(&opt.reading_strategy)?;
         }
         ReadingStrategy::Scenario(scenario) => {
             let md = path.metadata()?;
@@ -238,5 +292,9 @@ where
         }
     };
     hasher.finish()?;
-    Ok(hasher.hash()?.to_vec())
+    let hash = hasher.hash()?.to_vec();
+    if let Some((cache, md)) = cached {
+        cache.set(path, &md, &hash);
+    }
+    Ok(hash)
 }
@@ -0,0 +1,126 @@
+use fshasher::{hasher, reader, walker::Manifest, Options};
+use std::{
+    env::temp_dir,
+    fs::{create_dir_all, remove_dir_all, write},
+    path::{Path, PathBuf},
+    process::{Command, Output},
+};
+use uuid::Uuid;
+
+/// A temporary tree of files, which is removed on drop.
+struct Tree {
+    root: PathBuf,
+}
+
+impl Tree {
+    fn new() -> Self {
+        let root = temp_dir().join(Uuid::new_v4().to_string());
+        for (path, content) in [("a.txt", "a"), ("sub/b.txt", "b"), ("sub/deep/c.txt", "c")] {
+            let path = root.join(path);
+            create_dir_all(path.parent().unwrap()).unwrap();
+            write(path, content).unwrap();
+        }
+        Self { root }
+    }
+}
+
+impl Drop for Tree {
+    fn drop(&mut self) {
+        let _ = remove_dir_all(&self.root);
+    }
+}
+
+fn run(cwd: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_fshasher"))
+        .current_dir(cwd)
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+fn summary(root: &Path) -> String {
+    let mut walker = Options::from(root).unwrap().walker().unwrap();
+    walker
+        .collect()
+        .unwrap()
+        .hash::<hasher::blake::Blake, reader::buffering::Buffering>()
+        .unwrap()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+#[test]
+fn exit_codes() {
+    let tree = Tree::new();
+    let expected = summary(&tree.root);
+    let output = run(&tree.root, &["."]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output), format!("{expected}\n"));
+    // The hash matches the expected one (case insensitive)
+    let output = run(&tree.root, &[".", "--expect", &expected.to_uppercase()]);
+    assert_eq!(output.status.code(), Some(0));
+    // The hash doesn't match the expected one, but it's printed anyway
+    let output = run(&tree.root, &[".", "--expect", "00"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), format!("{expected}\n"));
+    // Errors
+    assert_eq!(run(&tree.root, &["missing"]).status.code(), Some(2));
+    assert_eq!(
+        run(&tree.root, &[".", "--exclude", "unknown:*"])
+            .status
+            .code(),
+        Some(2)
+    );
+    assert_eq!(
+        run(&tree.root, &[".", "--hasher", "unknown"]).status.code(),
+        Some(2)
+    );
+}
+
+#[test]
+fn list() {
+    let tree = Tree::new();
+    let output = run(&tree.root, &[".", "--list"]);
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = stdout(&output);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 4);
+    let paths: Vec<&str> = lines[..3]
+        .iter()
+        .map(|ln| ln.split_once("  ").unwrap().1)
+        .collect();
+    assert_eq!(paths, ["./a.txt", "./sub/b.txt", "./sub/deep/c.txt"]);
+    assert_eq!(lines[3], summary(&tree.root));
+    // Paths don't depend on the location of the tree
+    let other = Tree::new();
+    assert_eq!(stdout, self::stdout(&run(&other.root, &[".", "--list"])));
+    assert!(!stdout.contains(tree.root.to_string_lossy().as_ref()));
+}
+
+#[test]
+fn manifest_round_trip() {
+    let tree = Tree::new();
+    let mut walker = Options::from(&tree.root).unwrap().walker().unwrap();
+    walker
+        .collect()
+        .unwrap()
+        .hash::<hasher::blake::Blake, reader::buffering::Buffering>()
+        .unwrap();
+    let stdout = stdout(&run(&tree.root, &[".", "--list"]));
+    let listing: Vec<&str> = stdout.lines().collect();
+    // Without the last line (the summary hash) the output is a manifest
+    let filename = tree.root.join("checksums.b3");
+    write(
+        &filename,
+        format!("{}\n", listing[..listing.len() - 1].join("\n")),
+    )
+    .unwrap();
+    let manifest = Manifest::load(&filename).unwrap();
+    assert_eq!(manifest.files.len(), 3);
+    assert!(manifest.compare(&walker).is_same());
+}